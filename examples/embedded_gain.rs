@@ -0,0 +1,134 @@
+// Minimal gain plugin embedding the analyser's audio side
+//
+// Shows that `spectrum_analyser::prelude` is enough to run the spectrum and meter
+// producers inside another plugin. The analyser's own entry point has to be left out:
+//
+//     cargo build --example embedded_gain --no-default-features
+
+use nih_plug::prelude::*;
+use spectrum_analyser::prelude::*;
+use std::sync::Arc;
+
+struct EmbeddedGain {
+    params: Arc<EmbeddedGainParams>,
+    sample_rate: f32,
+    spectrum_producer: SpectrumProducer,
+    meter_producer: MeterProducer,
+    // A real plugin hands these to its editor to draw from
+    _spectrum_consumer: SpectrumConsumer,
+    _meter_consumer: MeterConsumer,
+}
+
+#[derive(Params)]
+struct EmbeddedGainParams {
+    #[id = "gain"]
+    pub gain: FloatParam,
+}
+
+impl Default for EmbeddedGain {
+    fn default() -> Self {
+        let (spectrum_producer, spectrum_consumer) = SpectrumProducer::new();
+        let (meter_producer, meter_consumer) = create_meter_channels();
+
+        Self {
+            params: Arc::new(EmbeddedGainParams::default()),
+            sample_rate: 44100.0,
+            spectrum_producer,
+            meter_producer,
+            _spectrum_consumer: spectrum_consumer,
+            _meter_consumer: meter_consumer,
+        }
+    }
+}
+
+impl Default for EmbeddedGainParams {
+    fn default() -> Self {
+        Self {
+            gain: FloatParam::new(
+                "Gain",
+                util::db_to_gain(0.0),
+                FloatRange::Skewed {
+                    min: util::db_to_gain(-30.0),
+                    max: util::db_to_gain(30.0),
+                    factor: FloatRange::gain_skew_factor(-30.0, 30.0),
+                },
+            )
+            .with_unit(" dB")
+            .with_value_to_string(formatters::v2s_f32_gain_to_db(2))
+            .with_string_to_value(formatters::s2v_f32_gain_to_db()),
+        }
+    }
+}
+
+impl Plugin for EmbeddedGain {
+    const NAME: &'static str = "Embedded Gain";
+    const VENDOR: &'static str = "Cmdv";
+    const URL: &'static str = env!("CARGO_PKG_HOMEPAGE");
+    const EMAIL: &'static str = "info@cmdv.me";
+
+    const VERSION: &'static str = env!("CARGO_PKG_VERSION");
+
+    const AUDIO_IO_LAYOUTS: &'static [AudioIOLayout] = &[AudioIOLayout {
+        main_input_channels: NonZeroU32::new(2),
+        main_output_channels: NonZeroU32::new(2),
+        ..AudioIOLayout::const_default()
+    }];
+
+    type SysExMessage = ();
+    type BackgroundTask = ();
+
+    fn params(&self) -> Arc<dyn Params> {
+        self.params.clone()
+    }
+
+    fn initialize(
+        &mut self,
+        _audio_io_layout: &AudioIOLayout,
+        buffer_config: &BufferConfig,
+        _context: &mut impl InitContext<Self>,
+    ) -> bool {
+        self.sample_rate = buffer_config.sample_rate;
+        true
+    }
+
+    fn process_stopped(&mut self) {
+        self.spectrum_producer.write_silence();
+        self.meter_producer.write_silence();
+    }
+
+    fn process(
+        &mut self,
+        buffer: &mut Buffer,
+        _aux: &mut AuxiliaryBuffers,
+        _context: &mut impl ProcessContext<Self>,
+    ) -> ProcessStatus {
+        for channel_samples in buffer.iter_samples() {
+            let gain = self.params.gain.smoothed.next();
+            for sample in channel_samples {
+                *sample *= gain;
+            }
+        }
+
+        // Analyse the signal after the gain, as the embedding plugin outputs it
+        self.spectrum_producer.process(
+            buffer,
+            self.sample_rate,
+            TiltLevel::Subtle,
+            SpectrumSpeed::Medium,
+            ResolutionLevel::Medium,
+        );
+        self.meter_producer.update_peaks(buffer);
+
+        ProcessStatus::Normal
+    }
+}
+
+impl ClapPlugin for EmbeddedGain {
+    const CLAP_ID: &'static str = "com.your-domain.embedded-gain";
+    const CLAP_DESCRIPTION: Option<&'static str> = Some("Gain with an embedded analyser");
+    const CLAP_MANUAL_URL: Option<&'static str> = Some(Self::URL);
+    const CLAP_SUPPORT_URL: Option<&'static str> = None;
+    const CLAP_FEATURES: &'static [ClapFeature] = &[ClapFeature::AudioEffect, ClapFeature::Stereo];
+}
+
+nih_export_clap!(EmbeddedGain);
@@ -0,0 +1,75 @@
+// Times the compile-time sized analysis against the Vec-based path the producer runs
+//
+//     cargo run --release --example fixed_spectrum_bench
+//
+// Both paths window, transform and scale the same frame. Exits with an error if the
+// fixed path is more than `ALLOWED_SLOWDOWN` slower, so it can gate a change.
+
+use spectrum_analyser::prelude::*;
+use std::hint::black_box;
+use std::process::ExitCode;
+use std::time::{Duration, Instant};
+
+const FFT_SIZE: usize = 4096;
+const BINS: usize = FFT_SIZE / 2 + 1;
+const WARMUP_FRAMES: usize = 200;
+const TIMED_FRAMES: usize = 5000;
+
+/// Timing noise tolerated before the fixed path counts as a regression
+const ALLOWED_SLOWDOWN: f64 = 1.1;
+
+fn main() -> ExitCode {
+    let frame: [f32; FFT_SIZE] = std::array::from_fn(|index| {
+        (index as f32 * 1000.0 / 48000.0 * std::f32::consts::TAU).sin() * 0.25
+    });
+
+    // Today's path: windowed copy, planned FFT and a freshly allocated spectrum per frame
+    let window = WindowType::Hann.generate::<f32>(FFT_SIZE);
+    let fft = realfft::RealFftPlanner::<f32>::new().plan_fft_forward(FFT_SIZE);
+    let mut time_domain = vec![0.0; FFT_SIZE];
+    let mut frequency_domain = fft.make_output_vec();
+    let mut scratch = fft.make_scratch_vec();
+    let dynamic = time_frames(|| {
+        time_domain.copy_from_slice(black_box(&frame));
+        apply_window(&mut time_domain, &window);
+        fft.process_with_scratch(&mut time_domain, &mut frequency_domain, &mut scratch)
+            .unwrap();
+        black_box(compute_magnitude_spectrum(&frequency_domain, FFT_SIZE));
+    });
+
+    let mut fixed_spectrum = Box::new(FixedSpectrum4096::new(WindowType::Hann));
+    let mut output = [0.0; BINS];
+    let fixed = time_frames(|| {
+        fixed_spectrum
+            .analyze(black_box(&frame), &mut output)
+            .unwrap();
+        black_box(&output);
+    });
+
+    let ratio = fixed.as_secs_f64() / dynamic.as_secs_f64();
+    println!("{FFT_SIZE}-point frame, mean of {TIMED_FRAMES}:");
+    println!("  dynamic {:>8.2} us", per_frame_us(dynamic));
+    println!("  fixed   {:>8.2} us  ({ratio:.2}x)", per_frame_us(fixed));
+
+    if ratio > ALLOWED_SLOWDOWN {
+        eprintln!("fixed path is slower than the dynamic path by more than {ALLOWED_SLOWDOWN}x");
+        return ExitCode::FAILURE;
+    }
+    ExitCode::SUCCESS
+}
+
+/// Total time of `TIMED_FRAMES` runs of `analyze`, after a warm-up
+fn time_frames(mut analyze: impl FnMut()) -> Duration {
+    for _ in 0..WARMUP_FRAMES {
+        analyze();
+    }
+    let start = Instant::now();
+    for _ in 0..TIMED_FRAMES {
+        analyze();
+    }
+    start.elapsed()
+}
+
+fn per_frame_us(total: Duration) -> f64 {
+    total.as_secs_f64() * 1e6 / TIMED_FRAMES as f64
+}
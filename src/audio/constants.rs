@@ -1,33 +1,54 @@
 /// Shared audio processing constants
 /// Only constants used across multiple modules are kept here
+use crate::GridDensity;
 
 // === SHARED FREQUENCY RANGE ===
 /// Frequency range for analysis and display (20Hz - 20kHz)
+/// MIN_FREQUENCY is the default lower bound; the display can extend below it
 pub const MIN_FREQUENCY: f32 = 20.0;
 pub const MAX_FREQUENCY: f32 = 20000.0;
 
 // === SHARED DISPLAY RANGE ===
-/// dB range for spectrum display (-100 to 0 dB)
+/// Default dB range for displays without an amplitude range setting (-100 to 0 dB)
 pub const MAX_DB: f32 = 0.0;
 pub const MIN_DB: f32 = -100.0;
-pub const DB_RANGE: f32 = MAX_DB - MIN_DB; // 100dB total range
+
+/// Most dB grid lines drawn across any amplitude range
+const MAX_DB_MARKERS: f32 = 10.0;
 
 // === SHARED DISPLAY FUNCTIONS ===
 
-/// Convert frequency to logarithmic display position (0.0 to 1.0)
+/// Convert frequency to logarithmic display position (0.0 = min, 1.0 = max)
 /// Used by spectrum display and frequency-based UI components
-pub fn freq_to_log_position(freq: f32) -> f32 {
-    (freq / MIN_FREQUENCY).log10() / (MAX_FREQUENCY / MIN_FREQUENCY).log10()
+pub fn freq_to_log_position(freq: f32, min: f32, max: f32) -> f32 {
+    (freq / min).log10() / (max / min).log10()
+}
+
+/// Convert logarithmic display position (0.0 to 1.0) back to frequency
+/// Inverse of `freq_to_log_position` for the same range
+/// Used to turn an X pixel position into a frequency for cursor features
+pub fn log_position_to_freq(pos: f32, min: f32, max: f32) -> f32 {
+    min * (max / min).powf(pos)
+}
+
+/// Convert dB to normalized display position (0.0 = min_db, 1.0 = max_db)
+/// Used by the grid and its labels; pass the active amplitude range
+pub fn db_to_normalized(db: f32, min_db: f32, max_db: f32) -> f32 {
+    ((db - min_db) / (max_db - min_db)).max(0.0).min(1.0)
 }
 
-/// Convert dB to normalized display position (0.0 = MIN_DB, 1.0 = MAX_DB)
-/// Used by spectrum and meter displays  
-pub fn db_to_normalized(db: f32) -> f32 {
-    ((db - MIN_DB) / DB_RANGE).max(0.0).min(1.0)
+/// Convert normalized display position (0.0 = min_db, 1.0 = max_db) back to dB
+/// Inverse of `db_to_normalized` for the given range; pass the active amplitude range
+#[allow(dead_code)] // Used by upcoming hover readout
+pub fn normalized_to_db(normalized: f32, min_db: f32, max_db: f32) -> f32 {
+    min_db + normalized * (max_db - min_db)
 }
 
 /// Standard frequency markers for grid
+/// Markers below the active lower bound are skipped by the callers
 pub const FREQUENCY_MARKERS: &[(f32, &str)] = &[
+    (5.0, "5"),
+    (10.0, "10"),
     (20.0, "20"),
     (50.0, "50"),
     (100.0, "100"),
@@ -40,49 +61,332 @@ pub const FREQUENCY_MARKERS: &[(f32, &str)] = &[
     (20000.0, "20K"),
 ];
 
-/// Standard dB markers for grid
-pub const DB_MARKERS: &[(f32, &str)] = &[
-    (0.0, "0"),
-    (-20.0, "-20"),
-    (-40.0, "-40"),
-    (-60.0, "-60"),
-    (-80.0, "-80"),
-    (-100.0, "-100"),
-];
+/// Frequency markers at or above `min_freq` (markers are sorted ascending)
+pub fn visible_frequency_markers(min_freq: f32) -> &'static [(f32, &'static str)] {
+    let first_visible = FREQUENCY_MARKERS
+        .iter()
+        .position(|&(freq, _)| freq >= min_freq)
+        .unwrap_or(FREQUENCY_MARKERS.len());
+    &FREQUENCY_MARKERS[first_visible..]
+}
+
+/// Smallest round dB spacing that keeps the range within `MAX_DB_MARKERS` steps
+pub fn db_marker_step(min_db: f32, max_db: f32) -> f32 {
+    let span = (max_db - min_db).abs();
+    [5.0, 10.0, 20.0, 25.0, 50.0]
+        .into_iter()
+        .find(|&step| span / step <= MAX_DB_MARKERS)
+        .unwrap_or(100.0)
+}
+
+/// dB marker values at multiples of `step` from `max_db` down to `min_db` (inclusive)
+/// Values are multiples of the step, so a 0 to -60 range in 10 dB steps gives
+/// 0, -10, ... -60 and nothing else
+pub fn generate_db_markers(min_db: f32, max_db: f32, step: f32) -> Vec<f32> {
+    if step <= 0.0 || max_db < min_db {
+        return Vec::new();
+    }
+
+    let first = (max_db / step).floor() as i32;
+    let last = (min_db / step).ceil() as i32;
+    (last..=first).rev().map(|i| i as f32 * step).collect()
+}
+
+/// dB grid markers for an amplitude range, with an automatically chosen step
+/// The density scales the step; with minors off only the range endpoints are drawn
+/// The shader grid, canvas grid and labels all use this so they never disagree
+pub fn db_grid_markers(min_db: f32, max_db: f32, density: GridDensity) -> Vec<f32> {
+    match density.db_step_scale() {
+        Some(scale) => generate_db_markers(min_db, max_db, db_marker_step(min_db, max_db) * scale),
+        None => vec![max_db, min_db],
+    }
+}
+
+/// Label text for a dB marker ("0", "-20", "-2.5", ...)
+/// Whole values drop the decimal so the usual steps stay compact
+pub fn format_db_marker(db: f32) -> String {
+    // Avoid "-0" for the top marker
+    let db = if db == 0.0 { 0.0 } else { db };
+    if db.fract() == 0.0 {
+        format!("{:.0}", db)
+    } else {
+        format!("{:.1}", db)
+    }
+}
 
 /// Generate frequency grid lines algorithmically
-/// Creates a professional-looking logarithmic frequency grid
-/// Returns (frequency, is_major) tuples
-pub fn generate_frequency_grid_positions() -> Vec<(f32, bool)> {
+/// Major lines at each decade (100 Hz, 1 kHz, 10 kHz), minor lines at the other
+/// `multipliers` of each decade (2x and 5x for the normal density)
+/// Returns (frequency, is_major) tuples within `min_freq..=max_freq`, lowest first
+pub fn generate_frequency_grid_positions(
+    min_freq: f32,
+    max_freq: f32,
+    multipliers: &[f32],
+) -> Vec<(f32, bool)> {
+    let mut frequencies = Vec::new();
+    if min_freq <= 0.0 || max_freq < min_freq {
+        return frequencies;
+    }
+
+    let first_decade = min_freq.log10().floor() as i32;
+    let last_decade = max_freq.log10().floor() as i32;
+    for exponent in first_decade..=last_decade {
+        let decade = 10.0_f32.powi(exponent);
+        for &multiplier in multipliers {
+            let freq = decade * multiplier;
+            if (min_freq..=max_freq).contains(&freq) {
+                frequencies.push((freq, multiplier == 1.0));
+            }
+        }
+    }
+
+    frequencies
+}
+
+/// Intervals drawn by the musical grid as (frequency ratio, is_major)
+/// Octaves of the root are major lines, the perfect fifth above each is minor
+pub const MUSICAL_GRID_INTERVALS: &[(f32, bool)] = &[(1.0, true), (1.5, false)];
+
+/// Frequency of the note `semitone` steps above C in octave 0 (A4 = 440 Hz)
+pub fn root_note_frequency(semitone: u8) -> f32 {
+    // A4 sits 57 semitones above C0
+    440.0 * 2.0_f32.powf((semitone as f32 - 57.0) / 12.0)
+}
+
+/// Equal-tempered note frequency nearest to `frequency_hz` (A4 = 440 Hz)
+pub fn nearest_note_frequency(frequency_hz: f32) -> f32 {
+    let semitones_from_a4 = (12.0 * (frequency_hz / 440.0).log2()).round();
+    440.0 * 2.0_f32.powf(semitones_from_a4 / 12.0)
+}
+
+/// Generate grid lines at musical intervals above `root_hz`
+/// Returns (frequency, is_major) tuples within `min_freq..=max_freq`
+pub fn generate_musical_grid_positions(
+    root_hz: f32,
+    min_freq: f32,
+    max_freq: f32,
+) -> Vec<(f32, bool)> {
     let mut frequencies = Vec::new();
 
-    // Major lines at decade boundaries: 100Hz, 1kHz, 10kHz
-    let major_frequencies = &[100.0, 1000.0, 10000.0];
+    let mut octave_root = root_hz;
+    while octave_root <= max_freq {
+        for &(ratio, is_major) in MUSICAL_GRID_INTERVALS {
+            let freq = octave_root * ratio;
+            if (min_freq..=max_freq).contains(&freq) {
+                frequencies.push((freq, is_major));
+            }
+        }
+        octave_root *= 2.0;
+    }
+
+    frequencies
+}
 
-    // Lines at every step within each decade
-    // 20-100Hz: every 10Hz (20, 30, 40, 50, 60, 70, 80, 90, 100)
-    for i in 2..=10 {
-        let freq = i as f32 * 10.0;
-        let is_major = major_frequencies.contains(&freq);
-        frequencies.push((freq, is_major));
+/// Frequency grid lines for the active grid style within `min_freq..=max_freq`
+/// Uses the musical grid when a root is given, the decade grid otherwise
+/// Density sets the decade grid's minors; the musical grid only drops its minors when off
+/// The shader grid and the canvas grid both draw from this, so their lines always match
+pub fn grid_positions(
+    min_freq: f32,
+    max_freq: f32,
+    musical_root_hz: Option<f32>,
+    density: GridDensity,
+) -> Vec<(f32, bool)> {
+    let mut positions = match musical_root_hz {
+        Some(root_hz) => generate_musical_grid_positions(root_hz, min_freq, max_freq),
+        None => generate_frequency_grid_positions(min_freq, max_freq, density.decade_multipliers()),
+    };
+    if density == GridDensity::Off {
+        positions.retain(|&(_, is_major)| is_major);
     }
+    positions
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
 
-    // 100-1000Hz: every 100Hz (200, 300, 400, 500, 600, 700, 800, 900, 1000)
-    for i in 2..=10 {
-        let freq = i as f32 * 100.0;
-        let is_major = major_frequencies.contains(&freq);
-        frequencies.push((freq, is_major));
+    /// Tolerance for positions and levels that go through log/pow
+    const EPSILON: f32 = 1e-5;
+
+    /// Log-spaced frequencies across the display range, lowest first
+    fn sweep_frequencies() -> impl Iterator<Item = f32> {
+        (0..=200).map(|i| log_position_to_freq(i as f32 / 200.0, MIN_FREQUENCY, MAX_FREQUENCY))
     }
 
-    // 1000-10000Hz: every 1000Hz (2k, 3k, 4k, 5k, 6k, 7k, 8k, 9k, 10k)
-    for i in 2..=10 {
-        let freq = i as f32 * 1000.0;
-        let is_major = major_frequencies.contains(&freq);
-        frequencies.push((freq, is_major));
+    #[test]
+    fn frequency_range_maps_to_unit_interval() {
+        assert_eq!(
+            freq_to_log_position(MIN_FREQUENCY, MIN_FREQUENCY, MAX_FREQUENCY),
+            0.0
+        );
+        assert!(
+            (freq_to_log_position(MAX_FREQUENCY, MIN_FREQUENCY, MAX_FREQUENCY) - 1.0).abs()
+                < EPSILON
+        );
+        assert_eq!(
+            log_position_to_freq(0.0, MIN_FREQUENCY, MAX_FREQUENCY),
+            MIN_FREQUENCY
+        );
+        assert!(
+            (log_position_to_freq(1.0, MIN_FREQUENCY, MAX_FREQUENCY) - MAX_FREQUENCY).abs() < 0.1
+        );
     }
 
-    // Add 20kHz endpoint
-    frequencies.push((20000.0, false));
+    #[test]
+    fn frequency_position_is_monotonic() {
+        let positions: Vec<f32> = sweep_frequencies()
+            .map(|freq| freq_to_log_position(freq, MIN_FREQUENCY, MAX_FREQUENCY))
+            .collect();
+        assert!(positions.windows(2).all(|pair| pair[0] < pair[1]));
+    }
 
-    frequencies
+    #[test]
+    fn frequency_position_round_trips() {
+        for freq in sweep_frequencies() {
+            let position = freq_to_log_position(freq, MIN_FREQUENCY, MAX_FREQUENCY);
+            let round_trip = log_position_to_freq(position, MIN_FREQUENCY, MAX_FREQUENCY);
+            assert!(
+                (round_trip - freq).abs() <= freq * EPSILON,
+                "{freq} Hz came back as {round_trip}"
+            );
+        }
+        for i in 0..=100 {
+            let position = i as f32 / 100.0;
+            let freq = log_position_to_freq(position, MIN_FREQUENCY, MAX_FREQUENCY);
+            let round_trip = freq_to_log_position(freq, MIN_FREQUENCY, MAX_FREQUENCY);
+            assert!(
+                (round_trip - position).abs() < EPSILON,
+                "{position} came back as {round_trip}"
+            );
+        }
+    }
+
+    #[test]
+    fn frequency_markers_sit_on_the_log_axis() {
+        // 20 Hz to 20 kHz is three decades, so each decade marker is a third further along
+        let one_khz = FREQUENCY_MARKERS
+            .iter()
+            .find(|&&(_, label)| label == "1K")
+            .unwrap()
+            .0;
+        let position = freq_to_log_position(one_khz, MIN_FREQUENCY, MAX_FREQUENCY);
+        assert!((position - 50.0_f32.log10() / 3.0).abs() < EPSILON);
+
+        for &(freq, label) in visible_frequency_markers(MIN_FREQUENCY) {
+            let position = freq_to_log_position(freq, MIN_FREQUENCY, MAX_FREQUENCY);
+            assert!(
+                (-EPSILON..=1.0 + EPSILON).contains(&position),
+                "{label} at {position}"
+            );
+        }
+    }
+
+    #[test]
+    fn level_range_maps_to_unit_interval() {
+        assert_eq!(db_to_normalized(MIN_DB, MIN_DB, MAX_DB), 0.0);
+        assert_eq!(db_to_normalized(MAX_DB, MIN_DB, MAX_DB), 1.0);
+        assert_eq!(db_to_normalized(-50.0, MIN_DB, MAX_DB), 0.5);
+        // Levels outside the range clamp to its edges
+        assert_eq!(db_to_normalized(MIN_DB - 40.0, MIN_DB, MAX_DB), 0.0);
+        assert_eq!(db_to_normalized(MAX_DB + 6.0, MIN_DB, MAX_DB), 1.0);
+    }
+
+    #[test]
+    fn level_position_round_trips() {
+        for (min_db, max_db) in [(MIN_DB, MAX_DB), (-60.0, 0.0), (-120.0, 0.0)] {
+            for i in 0..=120 {
+                let db = min_db + (max_db - min_db) * i as f32 / 120.0;
+                let round_trip =
+                    normalized_to_db(db_to_normalized(db, min_db, max_db), min_db, max_db);
+                assert!(
+                    (round_trip - db).abs() < EPSILON * 100.0,
+                    "{db} dB came back as {round_trip}"
+                );
+            }
+        }
+    }
+
+    #[test]
+    fn zero_to_minus_sixty_labels_every_ten_db() {
+        let expected = [0.0, -10.0, -20.0, -30.0, -40.0, -50.0, -60.0];
+        assert_eq!(generate_db_markers(-60.0, 0.0, 10.0), expected);
+        assert_eq!(db_grid_markers(-60.0, 0.0, GridDensity::Normal), expected);
+
+        let labels: Vec<String> = expected.iter().map(|&db| format_db_marker(db)).collect();
+        assert_eq!(labels, ["0", "-10", "-20", "-30", "-40", "-50", "-60"]);
+    }
+
+    #[test]
+    fn db_markers_stay_inside_the_range() {
+        // Ends off the step grid are not labelled, only the multiples between them
+        assert_eq!(
+            generate_db_markers(-55.0, 3.0, 10.0),
+            [0.0, -10.0, -20.0, -30.0, -40.0, -50.0]
+        );
+        assert_eq!(generate_db_markers(-3.0, 6.0, 2.5), [5.0, 2.5, 0.0, -2.5]);
+        assert!(generate_db_markers(0.0, -60.0, 10.0).is_empty());
+        assert!(generate_db_markers(-60.0, 0.0, 0.0).is_empty());
+
+        // The automatic step keeps any range to a readable number of markers
+        for (min_db, max_db) in [(-12.0, 0.0), (-60.0, 0.0), (-100.0, 20.0), (-144.0, 0.0)] {
+            let markers = db_grid_markers(min_db, max_db, GridDensity::Normal);
+            assert!(
+                markers.len() as f32 <= MAX_DB_MARKERS + 1.0,
+                "{min_db}..{max_db} dB gave {markers:?}"
+            );
+            assert!(markers.iter().all(|db| (min_db..=max_db).contains(db)));
+        }
+        assert_eq!(db_grid_markers(-60.0, 0.0, GridDensity::Off), [0.0, -60.0]);
+    }
+
+    #[test]
+    fn decade_grid_is_one_two_five_with_decade_majors() {
+        let positions = generate_frequency_grid_positions(20.0, 20000.0, &[1.0, 2.0, 5.0]);
+        assert_eq!(
+            positions,
+            [
+                (20.0, false),
+                (50.0, false),
+                (100.0, true),
+                (200.0, false),
+                (500.0, false),
+                (1000.0, true),
+                (2000.0, false),
+                (5000.0, false),
+                (10000.0, true),
+                (20000.0, false),
+            ]
+        );
+    }
+
+    #[test]
+    fn decade_grid_follows_the_frequency_range() {
+        assert_eq!(
+            generate_frequency_grid_positions(150.0, 3000.0, &[1.0, 2.0, 5.0]),
+            [
+                (200.0, false),
+                (500.0, false),
+                (1000.0, true),
+                (2000.0, false)
+            ]
+        );
+        assert!(generate_frequency_grid_positions(0.0, 20000.0, &[1.0]).is_empty());
+        assert!(generate_frequency_grid_positions(1000.0, 100.0, &[1.0]).is_empty());
+
+        // Density picks the minors; off keeps only the decades
+        assert_eq!(
+            grid_positions(20.0, 20000.0, None, GridDensity::Off),
+            [(100.0, true), (1000.0, true), (10000.0, true)]
+        );
+        for density in [GridDensity::Sparse, GridDensity::Normal, GridDensity::Dense] {
+            let positions = grid_positions(20.0, 20000.0, None, density);
+            assert!(positions.windows(2).all(|pair| pair[0].0 < pair[1].0));
+            assert_eq!(
+                positions.iter().filter(|&&(_, is_major)| is_major).count(),
+                3,
+                "{density:?} should keep the three decade majors"
+            );
+        }
+    }
 }
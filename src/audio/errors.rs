@@ -20,6 +20,14 @@ pub enum SpectrumError {
     /// Window size mismatch
     #[error("Window size mismatch: expected {expected}, got {actual}")]
     WindowSizeMismatch { expected: usize, actual: usize },
+
+    /// A resource kept failing to read on consecutive UI ticks
+    #[error("Failed to read {resource} {consecutive} times in a row: {reason}")]
+    RepeatedReadFailure {
+        resource: String,
+        consecutive: u32,
+        reason: String,
+    },
 }
 
 /// Errors that can occur during metering
@@ -35,6 +43,58 @@ pub enum MeterError {
     LockFailed,
 }
 
+/// UI ticks a read may fail in a row before it is reported
+/// A few misses are normal lock contention with the audio thread; this is ~0.5 s at 60 Hz
+pub const READ_FAILURE_WARNING_TICKS: u32 = 30;
+
+/// Counts consecutive failed reads of one shared resource on the UI thread
+/// The `_or_silence` fallbacks keep drawing, this keeps the failure from going unnoticed
+#[derive(Debug)]
+pub struct ReadFailureTracker {
+    resource: &'static str,
+    consecutive: u32,
+    last_reason: String,
+    logged: bool,
+}
+
+impl ReadFailureTracker {
+    pub fn new(resource: &'static str) -> Self {
+        Self {
+            resource,
+            consecutive: 0,
+            last_reason: String::new(),
+            logged: false,
+        }
+    }
+
+    /// Record the outcome of one read; a success clears the count
+    /// The first failure is logged once, later ones only counted
+    pub fn record<T, E: std::fmt::Display>(&mut self, result: &Result<T, E>) {
+        match result {
+            Ok(_) => self.consecutive = 0,
+            Err(error) => {
+                if !self.logged {
+                    nih_plug::nih_log!("Failed to read {}: {}", self.resource, error);
+                    self.logged = true;
+                }
+                self.consecutive = self.consecutive.saturating_add(1);
+                self.last_reason = error.to_string();
+            }
+        }
+    }
+
+    /// The failure to show once reads have failed `READ_FAILURE_WARNING_TICKS` times in a row
+    pub fn error(&self) -> Option<SpectrumError> {
+        (self.consecutive >= READ_FAILURE_WARNING_TICKS).then(|| {
+            SpectrumError::RepeatedReadFailure {
+                resource: self.resource.to_string(),
+                consecutive: self.consecutive,
+                reason: self.last_reason.clone(),
+            }
+        })
+    }
+}
+
 /// Result type for spectrum operations
 pub type SpectrumResult<T> = Result<T, SpectrumError>;
 
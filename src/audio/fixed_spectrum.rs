@@ -0,0 +1,156 @@
+// Spectrum analysis with a compile-time FFT size
+//
+// For embedders who fix their FFT size at build time. The spectrum is written into a
+// `[f32; BINS]` the caller owns, so it can live on the stack with no length checks.
+// Windowing and dB scaling are the same generic helpers the plugin's producer runs.
+
+use realfft::{num_complex::Complex32, RealFftPlanner, RealToComplex};
+use std::sync::Arc;
+
+use super::errors::{SpectrumError, SpectrumResult};
+use super::spectrum::{apply_window, compute_magnitude_spectrum_into};
+use super::window_functions::WindowType;
+
+/// Magnitude spectrum of `N` samples into `BINS = N / 2 + 1` dB values
+///
+/// `BINS` is a separate parameter because `[f32; N / 2 + 1]` needs the unstable
+/// `generic_const_exprs`; the aliases below spell it out. Both sizes are checked when
+/// the type is instantiated, so a bad pair fails to compile:
+///
+/// ```compile_fail
+/// use spectrum_analyser::prelude::*;
+/// // N must be a power of two
+/// let _ = FixedSpectrum::<1000, 501>::new(WindowType::Hann);
+/// ```
+///
+/// ```compile_fail
+/// use spectrum_analyser::prelude::*;
+/// // BINS must be N / 2 + 1
+/// let _ = FixedSpectrum::<1024, 512>::new(WindowType::Hann);
+/// ```
+pub struct FixedSpectrum<const N: usize, const BINS: usize> {
+    /// Pre-computed window coefficients
+    window: [f32; N],
+    /// Windowed frame; the FFT overwrites it
+    time_domain: [f32; N],
+    frequency_domain: [Complex32; BINS],
+    scratch: Vec<Complex32>,
+    fft: Arc<dyn RealToComplex<f32>>,
+}
+
+/// 1024-point analysis, 513 bins
+pub type FixedSpectrum1024 = FixedSpectrum<1024, 513>;
+
+/// 2048-point analysis, 1025 bins
+pub type FixedSpectrum2048 = FixedSpectrum<2048, 1025>;
+
+/// 4096-point analysis, 2049 bins, the producer's default FFT size
+pub type FixedSpectrum4096 = FixedSpectrum<4096, 2049>;
+
+impl<const N: usize, const BINS: usize> FixedSpectrum<N, BINS> {
+    /// Evaluated in `new`, failing the build for an invalid size pair
+    const VALID_SIZES: () = assert!(
+        N.is_power_of_two() && N >= 4 && BINS == N / 2 + 1,
+        "FixedSpectrum needs a power-of-two N of at least 4 and BINS = N / 2 + 1"
+    );
+
+    /// Plan the FFT and compute the window; allocates, so call it off the audio thread
+    pub fn new(window_type: WindowType) -> Self {
+        #[allow(clippy::let_unit_value)]
+        let () = Self::VALID_SIZES;
+
+        let fft = RealFftPlanner::<f32>::new().plan_fft_forward(N);
+        let mut window = [0.0; N];
+        window.copy_from_slice(&window_type.generate::<f32>(N));
+
+        Self {
+            window,
+            time_domain: [0.0; N],
+            frequency_domain: [Complex32::new(0.0, 0.0); BINS],
+            scratch: fft.make_scratch_vec(),
+            fft,
+        }
+    }
+
+    /// Window `frame`, transform it and write each bin's level (dBFS) into `output`
+    ///
+    /// Runs without allocating. Levels are scaled like the producer's full-resolution
+    /// spectrum, before tilt, smoothing and reference normalisation.
+    pub fn analyze(&mut self, frame: &[f32; N], output: &mut [f32; BINS]) -> SpectrumResult<()> {
+        self.time_domain.copy_from_slice(frame);
+        apply_window(&mut self.time_domain, &self.window);
+        self.fft
+            .process_with_scratch(
+                &mut self.time_domain,
+                &mut self.frequency_domain,
+                &mut self.scratch,
+            )
+            .map_err(|_| SpectrumError::FftFailed)?;
+        compute_magnitude_spectrum_into(&self.frequency_domain, N, output);
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::audio::spectrum::compute_magnitude_spectrum;
+
+    /// The same frame through the Vec-based helpers the producer uses
+    fn dynamic_spectrum(frame: &[f32], window_type: WindowType) -> Vec<f32> {
+        let mut samples = frame.to_vec();
+        apply_window(&mut samples, &window_type.generate::<f32>(frame.len()));
+        let fft = RealFftPlanner::<f32>::new().plan_fft_forward(samples.len());
+        let mut bins = fft.make_output_vec();
+        fft.process(&mut samples, &mut bins).unwrap();
+        compute_magnitude_spectrum(&bins, frame.len())
+    }
+
+    /// A few tones over LCG noise, so every bin carries something
+    fn test_frame<const N: usize>() -> [f32; N] {
+        let mut state: u64 = 0x5eed;
+        std::array::from_fn(|index| {
+            state = state
+                .wrapping_mul(6364136223846793005)
+                .wrapping_add(1442695040888963407);
+            let noise = ((state >> 33) as f32 / (1u64 << 31) as f32 - 0.5) * 1e-3;
+            let phase = index as f32 / N as f32 * std::f32::consts::TAU;
+            0.5 * (phase * 37.0).sin() + 0.25 * (phase * 101.5).sin() + noise
+        })
+    }
+
+    #[test]
+    fn fixed_spectrum_matches_the_dynamic_path() {
+        for window_type in [WindowType::Hann, WindowType::Hamming, WindowType::Blackman] {
+            let frame = test_frame::<1024>();
+            let mut fixed = FixedSpectrum1024::new(window_type);
+            let mut output = [0.0; 513];
+            fixed.analyze(&frame, &mut output).unwrap();
+
+            let dynamic = dynamic_spectrum(&frame, window_type);
+            assert_eq!(dynamic.len(), output.len());
+            for (bin, (&fixed_db, &dynamic_db)) in output.iter().zip(&dynamic).enumerate() {
+                assert_eq!(
+                    fixed_db, dynamic_db,
+                    "{window_type:?} bin {bin}: fixed {fixed_db} vs dynamic {dynamic_db}"
+                );
+            }
+        }
+    }
+
+    #[test]
+    fn fixed_spectrum_reuses_its_buffers_between_frames() {
+        let mut fixed = FixedSpectrum::<256, 129>::new(WindowType::Hann);
+        let loud = test_frame::<256>();
+        let quiet = loud.map(|sample| sample * 0.1);
+        let (mut first, mut second, mut again) = ([0.0; 129], [0.0; 129], [0.0; 129]);
+
+        fixed.analyze(&loud, &mut first).unwrap();
+        fixed.analyze(&quiet, &mut second).unwrap();
+        fixed.analyze(&loud, &mut again).unwrap();
+
+        // A tenth of the level is 20 dB down, and nothing carries over between frames
+        assert!((first[37] - second[37] - 20.0).abs() < 1e-3);
+        assert_eq!(first, again);
+    }
+}
@@ -1,29 +1,90 @@
 use super::errors::{MeterError, MeterResult};
+use crate::PeakReadoutChannel;
 use atomic_float::AtomicF32;
 use nih_plug::prelude::*;
 use std::convert::TryFrom;
-use std::sync::{atomic::Ordering, Arc};
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::Arc;
+use std::time::{Duration, Instant};
 
-/// Smoothing factors for level meters
-/// These values are calibrated to match professional meter behavior
-const METER_ATTACK: f32 = 0.6; // Moderate attack (not too jumpy)
-const METER_RELEASE: f32 = 0.01; // Faster release for quicker decay
+/// Default meter ballistics, shared with the plugin parameters
+pub const DEFAULT_METER_ATTACK_MS: f32 = 5.0; // Fast enough to catch transients
+pub const DEFAULT_METER_RELEASE_MS: f32 = 300.0; // Slow enough not to flicker
 
-/// Peak hold time in update cycles (approximately 1 second at 60fps)
-const PEAK_HOLD_CYCLES: u32 = 60;
+/// How long a new peak is held before it is released (wall clock, any frame rate)
+const PEAK_HOLD_DURATION: Duration = Duration::from_secs(1);
 
-/// Silence threshold - below this level, trigger faster decay
-const SILENCE_THRESHOLD_DB: f32 = -50.0;
+/// Ticks closer together than this are skipped and counted in the next update
+/// Hosts can deliver queued frames in a burst, so this is not an error
+const MIN_UPDATE_INTERVAL: Duration = Duration::from_millis(1);
 
-/// Delay in frames before applying fast decay to silent signals
-const SILENCE_DECAY_DELAY_FRAMES: u32 = 30; // About 0.5 seconds at 60fps
+/// How long the input must stay below the silence threshold before fast decay starts
+const SILENCE_GRACE_PERIOD: Duration = Duration::from_millis(500);
 
-/// Linear decay rate for silence (dB per frame)
-const SILENCE_DECAY_RATE_DB_PER_FRAME: f32 = 0.5;
+/// Silence decay defaults, shared with the plugin parameters
+/// At 60 dB/s a full-scale meter is dark about 1.5 s after the grace period
+pub const DEFAULT_SILENCE_THRESHOLD_DB: f32 = -60.0;
+pub const DEFAULT_SILENCE_FALL_DB_PER_S: f32 = 60.0;
 
 /// Minimum displayable level (silence floor)
 const METER_FLOOR_DB: f32 = -80.0;
 
+/// Samples per channel the DC offset mean spans before older blocks are halved away
+const DC_OFFSET_WINDOW_SAMPLES: u64 = 480_000; // About 10 seconds at 48kHz
+
+/// DC offset (fraction of full scale) above which the readout warns
+pub const DC_OFFSET_WARNING_RATIO: f32 = 0.005;
+
+/// Largest L/R peak and RMS difference still treated as identical channels (dB)
+pub const MONO_MAX_DIFFERENCE_DB: f32 = 0.1;
+
+/// Smallest L/R correlation still treated as identical channels
+pub const MONO_MIN_CORRELATION: f32 = 0.999;
+
+/// How long the channels must match before the mono indicator lights
+pub const MONO_DETECT_TIME: Duration = Duration::from_secs(1);
+
+/// How long the channels must differ before the mono indicator goes out again
+const MONO_RELEASE_TIME: Duration = Duration::from_millis(300);
+
+/// One-pole smoothing coefficient for a time constant, given the time since the last step
+/// A time of zero (or less) means no smoothing
+pub fn calculate_smoothing_alpha(time_ms: f32, elapsed_seconds: f32) -> f32 {
+    if time_ms <= 0.0 {
+        return 1.0;
+    }
+    1.0 - (-elapsed_seconds / (time_ms / 1000.0)).exp()
+}
+
+/// Attack/release coefficients for one meter update
+/// Derived from millisecond times and the measured update interval, so the
+/// meter falls at the same speed at any display refresh rate
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct MeterBallistics {
+    pub attack_alpha: f32,
+    pub release_alpha: f32,
+}
+
+impl MeterBallistics {
+    pub fn from_times(attack_ms: f32, release_ms: f32, elapsed_seconds: f32) -> Self {
+        Self {
+            attack_alpha: calculate_smoothing_alpha(attack_ms, elapsed_seconds),
+            release_alpha: calculate_smoothing_alpha(release_ms, elapsed_seconds),
+        }
+    }
+
+    /// Move a displayed level towards `target_db` with these ballistics
+    /// Shared so other level readouts respond the same way as the meter bars
+    pub fn smooth(&self, current_db: f32, target_db: f32) -> f32 {
+        let coefficient = if target_db > current_db {
+            self.attack_alpha // Attack: fast response to signal increases
+        } else {
+            self.release_alpha // Release: slow decay (prevents meter flickering)
+        };
+        target_db * coefficient + current_db * (1.0 - coefficient)
+    }
+}
+
 /// Peak levels for stereo audio
 #[derive(Debug, Clone, Copy, PartialEq)]
 pub struct PeakLevels {
@@ -74,6 +135,30 @@ impl<'a> TryFrom<&'a Buffer<'a>> for PeakLevels {
     }
 }
 
+/// Highest peak across every channel in the buffer (dB)
+///
+/// Unlike `PeakLevels`, which only looks at the first two channels, this covers
+/// the whole buffer so an over on any channel of a multichannel bus shows up.
+pub fn calculate_aggregate_peak(buffer: &Buffer) -> f32 {
+    let peak_gain = buffer
+        .as_slice_immutable()
+        .iter()
+        .flat_map(|channel| channel.iter())
+        .fold(0.0_f32, |peak, &sample| peak.max(sample.abs()));
+
+    util::gain_to_db(peak_gain)
+}
+
+/// RMS level of a block of samples (dB)
+pub fn calculate_rms_db(samples: &[f32]) -> f32 {
+    if samples.is_empty() {
+        return util::MINUS_INFINITY_DB;
+    }
+    let mean_square =
+        samples.iter().map(|&sample| sample * sample).sum::<f32>() / samples.len() as f32;
+    util::gain_to_db(mean_square.sqrt())
+}
+
 /// Meter data sent from audio thread to UI thread
 #[derive(Clone)]
 pub struct MeterProducer {
@@ -81,12 +166,33 @@ pub struct MeterProducer {
     /// Audio thread writes to these, UI thread reads from them
     pub peak_left: Arc<AtomicF32>,
     pub peak_right: Arc<AtomicF32>,
+    /// Peak over all channels, for the aggregate meter mode
+    pub peak_all: Arc<AtomicF32>,
+
+    /// Block RMS levels, drawn as the meter fill beneath the peak marker
+    pub rms_left: Arc<AtomicF32>,
+    pub rms_right: Arc<AtomicF32>,
+    pub rms_all: Arc<AtomicF32>,
+
+    /// Long-window mean of the raw samples per channel (linear, 1.0 = full scale)
+    pub dc_left: Arc<AtomicF32>,
+    pub dc_right: Arc<AtomicF32>,
+    /// Set by the UI to restart the DC measurement on the next block
+    pub dc_reset: Arc<AtomicBool>,
+
+    /// Correlation of the left and right channels over the last block (-1 to 1)
+    pub correlation: Arc<AtomicF32>,
+
+    /// Running sums behind the DC means (audio thread only)
+    dc_sum_left: f64,
+    dc_sum_right: f64,
+    dc_count: u64,
 }
 
 impl MeterProducer {
     /// Update peak levels from audio buffer (called from audio thread)
     /// Must be real-time safe - no allocations or locks
-    pub fn update_peaks(&self, buffer: &Buffer) {
+    pub fn update_peaks(&mut self, buffer: &Buffer) {
         // Use TryFrom to get peak levels, falling back to silence on error
         let peaks = PeakLevels::try_from(buffer).unwrap_or(PeakLevels {
             left_db: util::MINUS_INFINITY_DB,
@@ -96,6 +202,109 @@ impl MeterProducer {
         // Update atomic values (lock-free communication to UI thread)
         self.peak_left.store(peaks.left_db, Ordering::Relaxed);
         self.peak_right.store(peaks.right_db, Ordering::Relaxed);
+        self.peak_all
+            .store(calculate_aggregate_peak(buffer), Ordering::Relaxed);
+
+        self.update_rms(buffer);
+        self.update_dc_offset(buffer);
+        self.update_correlation(buffer);
+    }
+
+    /// Publish the normalised L/R cross-correlation of the block
+    /// A mono buffer correlates perfectly; a block where either side is silent keeps
+    /// the previous value, since correlation is undefined there
+    fn update_correlation(&self, buffer: &Buffer) {
+        let channel_slices = buffer.as_slice_immutable();
+        let Some(left) = channel_slices.first() else {
+            return;
+        };
+        let Some(right) = channel_slices.get(1) else {
+            self.correlation.store(1.0, Ordering::Relaxed);
+            return;
+        };
+
+        let (mut cross, mut left_power, mut right_power) = (0.0_f64, 0.0_f64, 0.0_f64);
+        for (&l, &r) in left.iter().zip(right.iter()) {
+            cross += l as f64 * r as f64;
+            left_power += l as f64 * l as f64;
+            right_power += r as f64 * r as f64;
+        }
+        let norm = (left_power * right_power).sqrt();
+        if norm > 0.0 {
+            self.correlation
+                .store((cross / norm) as f32, Ordering::Relaxed);
+        }
+    }
+
+    /// Publish per-channel and all-channel RMS of the block
+    fn update_rms(&self, buffer: &Buffer) {
+        let channel_slices = buffer.as_slice_immutable();
+        let Some(left) = channel_slices.first() else {
+            return;
+        };
+        let left_db = calculate_rms_db(left);
+        // Mono: use left channel for both
+        let right_db = channel_slices
+            .get(1)
+            .map_or(left_db, |right| calculate_rms_db(right));
+
+        // Mean of the per-channel powers, so every channel contributes equally
+        let mean_power = channel_slices
+            .iter()
+            .map(|channel| util::db_to_gain(calculate_rms_db(channel)).powi(2))
+            .sum::<f32>()
+            / channel_slices.len() as f32;
+
+        self.rms_left.store(left_db, Ordering::Relaxed);
+        self.rms_right.store(right_db, Ordering::Relaxed);
+        self.rms_all
+            .store(util::gain_to_db(mean_power.sqrt()), Ordering::Relaxed);
+    }
+
+    /// Accumulate the per-channel sample mean and publish it
+    fn update_dc_offset(&mut self, buffer: &Buffer) {
+        if self.dc_reset.swap(false, Ordering::Relaxed) {
+            self.reset_dc_offset();
+        }
+
+        let channel_slices = buffer.as_slice_immutable();
+        let Some(left) = channel_slices.first() else {
+            return;
+        };
+        // Mono: measure the single channel for both sides
+        let right = channel_slices.get(1).unwrap_or(left);
+
+        self.dc_sum_left += left.iter().map(|&sample| sample as f64).sum::<f64>();
+        self.dc_sum_right += right.iter().map(|&sample| sample as f64).sum::<f64>();
+        self.dc_count += left.len() as u64;
+        if self.dc_count == 0 {
+            return;
+        }
+
+        self.dc_left.store(
+            (self.dc_sum_left / self.dc_count as f64) as f32,
+            Ordering::Relaxed,
+        );
+        self.dc_right.store(
+            (self.dc_sum_right / self.dc_count as f64) as f32,
+            Ordering::Relaxed,
+        );
+
+        // Halve the accumulated history so the mean keeps following slow changes
+        if self.dc_count >= DC_OFFSET_WINDOW_SAMPLES {
+            self.dc_sum_left *= 0.5;
+            self.dc_sum_right *= 0.5;
+            self.dc_count /= 2;
+        }
+    }
+
+    /// Restart the DC offset measurement (called from `reset()` and on UI request)
+    pub fn reset_dc_offset(&mut self) {
+        self.dc_sum_left = 0.0;
+        self.dc_sum_right = 0.0;
+        self.dc_count = 0;
+        self.dc_left.store(0.0, Ordering::Relaxed);
+        self.dc_right.store(0.0, Ordering::Relaxed);
     }
 
     /// Write silence to the meter (called when processing stops)
@@ -104,6 +313,14 @@ impl MeterProducer {
             .store(util::MINUS_INFINITY_DB, Ordering::Relaxed);
         self.peak_right
             .store(util::MINUS_INFINITY_DB, Ordering::Relaxed);
+        self.peak_all
+            .store(util::MINUS_INFINITY_DB, Ordering::Relaxed);
+        self.rms_left
+            .store(util::MINUS_INFINITY_DB, Ordering::Relaxed);
+        self.rms_right
+            .store(util::MINUS_INFINITY_DB, Ordering::Relaxed);
+        self.rms_all
+            .store(util::MINUS_INFINITY_DB, Ordering::Relaxed);
     }
 }
 
@@ -113,17 +330,45 @@ struct MeterState {
     /// Smoothed levels for display (internal state)
     smoothed_left: f32,
     smoothed_right: f32,
+    smoothed_all: f32,
+
+    /// Smoothed RMS levels for the meter fill
+    smoothed_rms_left: f32,
+    smoothed_rms_right: f32,
+    smoothed_rms_all: f32,
 
     /// Peak hold values for visual feedback
     peak_hold_left: f32,
     peak_hold_right: f32,
     peak_hold_value: f32, // Maximum of both channels
 
-    /// Peak hold timer
-    peak_hold_counter: u32,
+    /// When the held peak was last raised (`None` until the first peak)
+    last_peak_time: Option<Instant>,
+
+    /// When `update()` last did any work, for burst and decay timing
+    last_update_time: Option<Instant>,
+
+    /// Editor tick of the last `update()`, to catch a second update within one tick
+    last_tick: Option<u64>,
+
+    /// Fall rate once the hold expires (0 = drop to silence instantly)
+    peak_decay_db_per_s: f32,
+
+    /// Whether the held peak never releases (cleared only by `reset_peak_hold`)
+    infinite_hold: bool,
+
+    /// Attack/release times and the coefficients they gave on the last update
+    attack_ms: f32,
+    release_ms: f32,
+    ballistics: Option<MeterBallistics>,
+
+    /// Silence detection settings (see `MeterConsumer::set_silence_decay`)
+    silence_decay_enabled: bool,
+    silence_threshold_db: f32,
+    silence_fall_db_per_s: f32,
 
-    /// Silence detection counter
-    silence_counter: u32,
+    /// When the input last dropped below the silence threshold (`None` while not silent)
+    silent_since: Option<Instant>,
 }
 
 /// Meter processor for UI thread - handles smoothing and peak hold
@@ -142,9 +387,18 @@ impl MeterConsumer {
         let mut initial_state = MeterState::default();
         initial_state.smoothed_left = util::MINUS_INFINITY_DB;
         initial_state.smoothed_right = util::MINUS_INFINITY_DB;
+        initial_state.smoothed_all = util::MINUS_INFINITY_DB;
+        initial_state.smoothed_rms_left = util::MINUS_INFINITY_DB;
+        initial_state.smoothed_rms_right = util::MINUS_INFINITY_DB;
+        initial_state.smoothed_rms_all = util::MINUS_INFINITY_DB;
         initial_state.peak_hold_left = util::MINUS_INFINITY_DB;
         initial_state.peak_hold_right = util::MINUS_INFINITY_DB;
         initial_state.peak_hold_value = util::MINUS_INFINITY_DB;
+        initial_state.attack_ms = DEFAULT_METER_ATTACK_MS;
+        initial_state.release_ms = DEFAULT_METER_RELEASE_MS;
+        initial_state.silence_decay_enabled = true;
+        initial_state.silence_threshold_db = DEFAULT_SILENCE_THRESHOLD_DB;
+        initial_state.silence_fall_db_per_s = DEFAULT_SILENCE_FALL_DB_PER_S;
 
         Self {
             meter_input,
@@ -153,21 +407,146 @@ impl MeterConsumer {
     }
 
     /// Update smoothing and peak hold logic
-    /// Call this from UI thread before drawing meters
-    pub fn update(&self) {
+    /// Call this once per frame from the editor's tick, never from `view()` or `draw()`
+    /// `tick` counts the editor's ticks and must change between calls
+    pub fn update(&self, tick: u64) {
+        self.update_at(Instant::now(), tick);
+    }
+
+    /// `update()` with an explicit clock, so hold timing doesn't depend on call frequency
+    fn update_at(&self, now: Instant, tick: u64) {
         // Read current peak levels from audio thread (atomic, lock-free)
         let left_db = self.meter_input.peak_left.load(Ordering::Relaxed);
         let right_db = self.meter_input.peak_right.load(Ordering::Relaxed);
+        let all_db = self.meter_input.peak_all.load(Ordering::Relaxed);
 
         if let Ok(mut state) = self.state.lock() {
+            // A second call within the same tick would double the ballistics
+            // Caught in debug builds, skipped in release
+            debug_assert!(
+                state.last_tick != Some(tick),
+                "MeterConsumer::update() called twice in tick {tick}"
+            );
+            if state.last_tick == Some(tick) {
+                return;
+            }
+            state.last_tick = Some(tick);
+
+            let elapsed = match state.last_update_time {
+                Some(last_update) => {
+                    let elapsed = now.saturating_duration_since(last_update);
+                    if elapsed < MIN_UPDATE_INTERVAL {
+                        return;
+                    }
+                    elapsed
+                }
+                None => Duration::ZERO,
+            };
+            state.last_update_time = Some(now);
+
             // Apply smoothing with attack/release characteristics
-            self.update_smoothing(&mut state, left_db, right_db);
+            let ballistics = MeterBallistics::from_times(
+                state.attack_ms,
+                state.release_ms,
+                elapsed.as_secs_f32(),
+            );
+            state.ballistics = Some(ballistics);
+            self.update_smoothing(&mut state, ballistics, left_db, right_db);
+            state.smoothed_all = ballistics.smooth(state.smoothed_all, all_db);
+            state.smoothed_rms_left = ballistics.smooth(
+                state.smoothed_rms_left,
+                self.meter_input.rms_left.load(Ordering::Relaxed),
+            );
+            state.smoothed_rms_right = ballistics.smooth(
+                state.smoothed_rms_right,
+                self.meter_input.rms_right.load(Ordering::Relaxed),
+            );
+            state.smoothed_rms_all = ballistics.smooth(
+                state.smoothed_rms_all,
+                self.meter_input.rms_all.load(Ordering::Relaxed),
+            );
 
             // Update peak hold behavior
-            self.update_peak_hold(&mut state, left_db, right_db);
+            self.update_peak_hold(&mut state, now, elapsed, left_db, right_db, all_db);
 
             // Silence detection for faster decay
-            self.update_silence_detection(&mut state);
+            let input_peak_db = left_db.max(right_db).max(all_db);
+            self.update_silence_detection(&mut state, now, elapsed, input_peak_db);
+        }
+    }
+
+    /// Restart the update timing after analysis was paused
+    /// Otherwise the first update sees the whole pause as elapsed time, snapping the
+    /// ballistics and expiring the peak hold at once. A held peak gets its full hold again.
+    pub fn resume(&self) {
+        self.resume_at(Instant::now());
+    }
+
+    /// `resume()` with an explicit clock
+    fn resume_at(&self, now: Instant) {
+        if let Ok(mut state) = self.state.lock() {
+            state.last_update_time = None;
+            if state.last_peak_time.is_some() {
+                state.last_peak_time = Some(now);
+            }
+            if state.silent_since.is_some() {
+                state.silent_since = Some(now);
+            }
+        }
+    }
+
+    /// Set how fast peak hold falls after the hold time (dB/s, 0 = instant drop)
+    pub fn set_peak_decay(&self, peak_decay_db_per_s: f32) {
+        if let Ok(mut state) = self.state.lock() {
+            state.peak_decay_db_per_s = peak_decay_db_per_s;
+        }
+    }
+
+    /// Keep the held peak until `reset_peak_hold()` instead of releasing it
+    pub fn set_infinite_hold(&self, infinite_hold: bool) {
+        if let Ok(mut state) = self.state.lock() {
+            state.infinite_hold = infinite_hold;
+        }
+    }
+
+    /// Clear the held peak of both channels and the overall value together
+    pub fn reset_peak_hold(&self) {
+        if let Ok(mut state) = self.state.lock() {
+            state.peak_hold_left = util::MINUS_INFINITY_DB;
+            state.peak_hold_right = util::MINUS_INFINITY_DB;
+            state.peak_hold_value = util::MINUS_INFINITY_DB;
+            state.last_peak_time = None;
+        }
+    }
+
+    /// Set the meter attack and release times in milliseconds
+    pub fn set_ballistics(&self, attack_ms: f32, release_ms: f32) {
+        if let Ok(mut state) = self.state.lock() {
+            state.attack_ms = attack_ms;
+            state.release_ms = release_ms;
+        }
+    }
+
+    /// Coefficients used by the last update, for smoothing other readouts the same way
+    /// Before the first timed update this falls back to an immediate response
+    pub fn ballistics(&self) -> MeterBallistics {
+        self.state
+            .lock()
+            .ok()
+            .and_then(|state| state.ballistics)
+            .unwrap_or(MeterBallistics {
+                attack_alpha: 1.0,
+                release_alpha: 1.0,
+            })
+    }
+
+    /// Configure the fast decay applied once the input has gone silent
+    /// `threshold_db` is compared against the raw input peaks; `fall_db_per_s` is the linear fall rate
+    pub fn set_silence_decay(&self, enabled: bool, threshold_db: f32, fall_db_per_s: f32) {
+        if let Ok(mut state) = self.state.lock() {
+            state.silence_decay_enabled = enabled;
+            state.silence_threshold_db = threshold_db;
+            state.silence_fall_db_per_s = fall_db_per_s;
         }
     }
 
@@ -188,6 +567,76 @@ impl MeterConsumer {
             .unwrap_or((util::MINUS_INFINITY_DB, util::MINUS_INFINITY_DB))
     }
 
+    /// Get the smoothed peak over all channels
+    #[must_use = "Meter levels should be used for display"]
+    pub fn get_smoothed_aggregate_level(&self) -> MeterResult<f32> {
+        self.state
+            .lock()
+            .map(|state| state.smoothed_all)
+            .map_err(|_| MeterError::LockFailed)
+    }
+
+    /// Get the smoothed aggregate level with fallback to silence
+    #[must_use = "Meter levels should be used for display"]
+    pub fn get_smoothed_aggregate_level_or_silence(&self) -> f32 {
+        self.get_smoothed_aggregate_level()
+            .unwrap_or(util::MINUS_INFINITY_DB)
+    }
+
+    /// Latest unsmoothed block peaks straight from the audio thread (left, right)
+    #[must_use = "Meter levels should be used for display"]
+    pub fn get_peak_levels(&self) -> (f32, f32) {
+        (
+            self.meter_input.peak_left.load(Ordering::Relaxed),
+            self.meter_input.peak_right.load(Ordering::Relaxed),
+        )
+    }
+
+    /// Get smoothed RMS levels with fallback to silence (left, right)
+    #[must_use = "Meter levels should be used for display"]
+    pub fn get_smoothed_rms_levels_or_silence(&self) -> (f32, f32) {
+        self.state
+            .lock()
+            .map(|state| (state.smoothed_rms_left, state.smoothed_rms_right))
+            .unwrap_or((util::MINUS_INFINITY_DB, util::MINUS_INFINITY_DB))
+    }
+
+    /// Get the smoothed all-channel RMS level with fallback to silence
+    #[must_use = "Meter levels should be used for display"]
+    pub fn get_smoothed_aggregate_rms_or_silence(&self) -> f32 {
+        self.state
+            .lock()
+            .map(|state| state.smoothed_rms_all)
+            .unwrap_or(util::MINUS_INFINITY_DB)
+    }
+
+    /// Get per-channel peak hold values with fallback to silence (left, right)
+    #[must_use = "Peak hold value should be used for display"]
+    pub fn get_peak_hold_levels_or_silence(&self) -> (f32, f32) {
+        self.state
+            .lock()
+            .map(|state| (state.peak_hold_left, state.peak_hold_right))
+            .unwrap_or((util::MINUS_INFINITY_DB, util::MINUS_INFINITY_DB))
+    }
+
+    /// L/R correlation of the latest block (1 = identical, 0 = unrelated, -1 = inverted)
+    pub fn correlation(&self) -> f32 {
+        self.meter_input.correlation.load(Ordering::Relaxed)
+    }
+
+    /// Current DC offset per channel (left, right) as a fraction of full scale
+    pub fn dc_offsets(&self) -> (f32, f32) {
+        (
+            self.meter_input.dc_left.load(Ordering::Relaxed),
+            self.meter_input.dc_right.load(Ordering::Relaxed),
+        )
+    }
+
+    /// Ask the audio thread to restart the DC offset measurement
+    pub fn reset_dc_offset(&self) {
+        self.meter_input.dc_reset.store(true, Ordering::Relaxed);
+    }
+
     /// Get peak hold value (maximum of both channels)
     #[must_use = "Peak hold value should be used for display"]
     pub fn get_peak_hold_db(&self) -> MeterResult<f32> {
@@ -204,31 +653,46 @@ impl MeterConsumer {
         self.get_peak_hold_db().unwrap_or(util::MINUS_INFINITY_DB)
     }
 
+    /// Get the held peak of one channel, or of both combined, with fallback to silence
+    /// Average is taken on linear gain, so a silent channel pulls it down 6 dB
+    #[must_use = "Peak hold value should be used for display"]
+    pub fn get_peak_hold_db_for_or_silence(&self, channel: PeakReadoutChannel) -> f32 {
+        let (left_db, right_db) = self.get_peak_hold_levels_or_silence();
+        match channel {
+            PeakReadoutChannel::Max => self.get_peak_hold_db_or_silence(),
+            PeakReadoutChannel::Average => {
+                util::gain_to_db((util::db_to_gain(left_db) + util::db_to_gain(right_db)) / 2.0)
+            }
+            PeakReadoutChannel::Left => left_db,
+            PeakReadoutChannel::Right => right_db,
+        }
+    }
+
     /// Apply attack/release smoothing to meter levels
-    fn update_smoothing(&self, state: &mut MeterState, left_db: f32, right_db: f32) {
+    fn update_smoothing(
+        &self,
+        state: &mut MeterState,
+        ballistics: MeterBallistics,
+        left_db: f32,
+        right_db: f32,
+    ) {
         // Left channel smoothing with attack/release envelope
-        if left_db > state.smoothed_left {
-            // Attack: fast response to signal increases
-            state.smoothed_left =
-                left_db * METER_ATTACK + state.smoothed_left * (1.0 - METER_ATTACK);
-        } else {
-            // Release: slow decay (prevents meter flickering)
-            state.smoothed_left =
-                left_db * METER_RELEASE + state.smoothed_left * (1.0 - METER_RELEASE);
-        }
+        state.smoothed_left = ballistics.smooth(state.smoothed_left, left_db);
 
         // Right channel smoothing (same algorithm)
-        if right_db > state.smoothed_right {
-            state.smoothed_right =
-                right_db * METER_ATTACK + state.smoothed_right * (1.0 - METER_ATTACK);
-        } else {
-            state.smoothed_right =
-                right_db * METER_RELEASE + state.smoothed_right * (1.0 - METER_RELEASE);
-        }
+        state.smoothed_right = ballistics.smooth(state.smoothed_right, right_db);
     }
 
     /// Update peak hold indicators with timed decay behavior
-    fn update_peak_hold(&self, state: &mut MeterState, left_db: f32, right_db: f32) {
+    fn update_peak_hold(
+        &self,
+        state: &mut MeterState,
+        now: Instant,
+        elapsed: Duration,
+        left_db: f32,
+        right_db: f32,
+        all_db: f32,
+    ) {
         // Check if we have new peak values
         let mut new_peak = false;
 
@@ -242,58 +706,167 @@ impl MeterConsumer {
             new_peak = true;
         }
 
-        // Update overall peak hold value (max of both channels)
-        let current_peak = state.peak_hold_left.max(state.peak_hold_right);
+        // Update overall peak hold value (max of all channels, so overs beyond L/R count too)
+        let current_peak = state.peak_hold_left.max(state.peak_hold_right).max(all_db);
         if current_peak > state.peak_hold_value {
             state.peak_hold_value = current_peak;
             new_peak = true;
         }
 
-        // Reset or increment peak hold timer
+        // Restart the hold on a new peak, otherwise check how long it has been held
+        // Infinite hold never releases
         if new_peak {
-            state.peak_hold_counter = 0;
-        } else {
-            state.peak_hold_counter += 1;
+            state.last_peak_time = Some(now);
+        } else if !state.infinite_hold {
+            let held_for = state.last_peak_time.map_or(Duration::ZERO, |last_peak| {
+                now.saturating_duration_since(last_peak)
+            });
 
             // Release peak hold after timeout
-            if state.peak_hold_counter >= PEAK_HOLD_CYCLES {
-                state.peak_hold_left = util::MINUS_INFINITY_DB;
-                state.peak_hold_right = util::MINUS_INFINITY_DB;
-                state.peak_hold_value = util::MINUS_INFINITY_DB;
-                state.peak_hold_counter = 0;
+            if held_for >= PEAK_HOLD_DURATION {
+                if state.peak_decay_db_per_s > 0.0 {
+                    // Fall gradually like a hardware meter; a new peak restarts the hold
+                    let step_db = state.peak_decay_db_per_s * elapsed.as_secs_f32();
+                    state.peak_hold_left = decay_peak_hold(state.peak_hold_left, step_db);
+                    state.peak_hold_right = decay_peak_hold(state.peak_hold_right, step_db);
+                    state.peak_hold_value = decay_peak_hold(state.peak_hold_value, step_db);
+                } else {
+                    state.peak_hold_left = util::MINUS_INFINITY_DB;
+                    state.peak_hold_right = util::MINUS_INFINITY_DB;
+                    state.peak_hold_value = util::MINUS_INFINITY_DB;
+                    state.last_peak_time = Some(now);
+                }
             }
         }
     }
 
     /// Detect silence and apply faster decay when appropriate
-    fn update_silence_detection(&self, state: &mut MeterState) {
-        let max_level = state.smoothed_left.max(state.smoothed_right);
-
-        if max_level < SILENCE_THRESHOLD_DB {
-            state.silence_counter += 1;
-
-            // After a delay, apply faster linear decay to silence
-            if state.silence_counter > SILENCE_DECAY_DELAY_FRAMES {
-                // Use linear decay in dB space for smooth, predictable decay
-                // Apply linear decay in dB space
-                if state.smoothed_left > util::MINUS_INFINITY_DB {
-                    state.smoothed_left -= SILENCE_DECAY_RATE_DB_PER_FRAME;
-                    if state.smoothed_left < METER_FLOOR_DB {
-                        state.smoothed_left = util::MINUS_INFINITY_DB;
-                    }
-                }
+    ///
+    /// Silence is judged on the raw input peaks so it is detected as soon as playback
+    /// stops, not after the slow release has run its course. After a grace period the
+    /// smoothed levels and peak holds fall linearly until the meter is dark.
+    /// Infinite peak hold keeps the held peaks through silence.
+    fn update_silence_detection(
+        &self,
+        state: &mut MeterState,
+        now: Instant,
+        elapsed: Duration,
+        input_peak_db: f32,
+    ) {
+        if !state.silence_decay_enabled || input_peak_db >= state.silence_threshold_db {
+            state.silent_since = None;
+            return;
+        }
 
-                if state.smoothed_right > util::MINUS_INFINITY_DB {
-                    state.smoothed_right -= SILENCE_DECAY_RATE_DB_PER_FRAME;
-                    if state.smoothed_right < METER_FLOOR_DB {
-                        state.smoothed_right = util::MINUS_INFINITY_DB;
-                    }
-                }
+        let silent_since = *state.silent_since.get_or_insert(now);
+        if now.saturating_duration_since(silent_since) < SILENCE_GRACE_PERIOD {
+            return;
+        }
+
+        // Use linear decay in dB space for smooth, predictable decay
+        let step_db = state.silence_fall_db_per_s * elapsed.as_secs_f32();
+        for level in [
+            &mut state.smoothed_left,
+            &mut state.smoothed_right,
+            &mut state.smoothed_all,
+            &mut state.smoothed_rms_left,
+            &mut state.smoothed_rms_right,
+            &mut state.smoothed_rms_all,
+        ] {
+            decay_silent_level(level, step_db);
+        }
+        if !state.infinite_hold {
+            for level in [
+                &mut state.peak_hold_left,
+                &mut state.peak_hold_right,
+                &mut state.peak_hold_value,
+            ] {
+                decay_silent_level(level, step_db);
             }
+        }
+    }
+}
+
+/// Lower a level by `step_db`, snapping to silence below the meter floor
+fn decay_silent_level(level_db: &mut f32, step_db: f32) {
+    if *level_db > util::MINUS_INFINITY_DB {
+        *level_db -= step_db;
+        if *level_db < METER_FLOOR_DB {
+            *level_db = util::MINUS_INFINITY_DB;
+        }
+    }
+}
+
+/// Lower a held peak by `step_db`, dropping to silence below the meter floor
+fn decay_peak_hold(peak_db: f32, step_db: f32) -> f32 {
+    let decayed = peak_db - step_db;
+    if decayed < METER_FLOOR_DB {
+        util::MINUS_INFINITY_DB
+    } else {
+        decayed
+    }
+}
+
+/// Flags input whose left and right channels have been identical for a while
+///
+/// Needs both the per-channel peak and RMS levels to match and the channels to be
+/// correlated, since equal levels alone also fit unrelated material. Separate attack
+/// and release times keep the indicator from flickering, and silence leaves the state
+/// as it is, so pauses between phrases don't clear it.
+pub struct MonoDetector {
+    is_mono: bool,
+    /// When the current matching (or differing, while mono) run started
+    run_start: Option<Instant>,
+}
+
+impl MonoDetector {
+    pub fn new() -> Self {
+        Self {
+            is_mono: false,
+            run_start: None,
+        }
+    }
+
+    /// Feed the latest per-channel (left, right) peak and RMS levels and L/R correlation
+    /// Called once per editor tick
+    pub fn update(
+        &mut self,
+        peak_db: (f32, f32),
+        rms_db: (f32, f32),
+        correlation: f32,
+        now: Instant,
+    ) {
+        let silent = peak_db.0.max(peak_db.1) < METER_FLOOR_DB;
+        if silent {
+            self.run_start = None;
+            return;
+        }
+
+        let matching = (peak_db.0 - peak_db.1).abs() < MONO_MAX_DIFFERENCE_DB
+            && (rms_db.0 - rms_db.1).abs() < MONO_MAX_DIFFERENCE_DB
+            && correlation > MONO_MIN_CORRELATION;
+
+        // A run of evidence against the current state flips it once long enough
+        if matching == self.is_mono {
+            self.run_start = None;
+            return;
+        }
+        let run_start = *self.run_start.get_or_insert(now);
+        let required = if self.is_mono {
+            MONO_RELEASE_TIME
         } else {
-            state.silence_counter = 0;
+            MONO_DETECT_TIME
+        };
+        if now.saturating_duration_since(run_start) >= required {
+            self.is_mono = matching;
+            self.run_start = None;
         }
     }
+
+    /// Whether both channels currently carry the same signal
+    pub fn is_mono(&self) -> bool {
+        self.is_mono
+    }
 }
 
 /// Factory function to create meter communication pair
@@ -303,12 +876,420 @@ pub fn create_meter_channels() -> (MeterProducer, MeterConsumer) {
     let meter_input = MeterProducer {
         peak_left: Arc::new(AtomicF32::new(util::MINUS_INFINITY_DB)),
         peak_right: Arc::new(AtomicF32::new(util::MINUS_INFINITY_DB)),
+        peak_all: Arc::new(AtomicF32::new(util::MINUS_INFINITY_DB)),
+        rms_left: Arc::new(AtomicF32::new(util::MINUS_INFINITY_DB)),
+        rms_right: Arc::new(AtomicF32::new(util::MINUS_INFINITY_DB)),
+        rms_all: Arc::new(AtomicF32::new(util::MINUS_INFINITY_DB)),
+        dc_left: Arc::new(AtomicF32::new(0.0)),
+        dc_right: Arc::new(AtomicF32::new(0.0)),
+        dc_reset: Arc::new(AtomicBool::new(false)),
+        correlation: Arc::new(AtomicF32::new(0.0)),
+        dc_sum_left: 0.0,
+        dc_sum_right: 0.0,
+        dc_count: 0,
     };
 
-    let meter_output = MeterConsumer::new(MeterProducer {
-        peak_left: meter_input.peak_left.clone(),
-        peak_right: meter_input.peak_right.clone(),
-    });
+    // The UI side only reads the shared atomics; its copy of the sums is unused
+    let meter_output = MeterConsumer::new(meter_input.clone());
 
     (meter_input, meter_output)
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const TEST_SAMPLE_RATE: f32 = 48000.0;
+
+    /// Host block size used when feeding test signals to a producer
+    const TEST_BLOCK_SIZE: usize = 512;
+
+    /// Feed `left`/`right` to `producer` in host-sized blocks, as `process` does
+    fn process_stereo(producer: &mut MeterProducer, left: &[f32], right: &[f32]) {
+        for (left_block, right_block) in left
+            .chunks(TEST_BLOCK_SIZE)
+            .zip(right.chunks(TEST_BLOCK_SIZE))
+        {
+            let mut left_block = left_block.to_vec();
+            let mut right_block = right_block.to_vec();
+            let num_samples = left_block.len();
+
+            let mut buffer = Buffer::default();
+            unsafe {
+                buffer.set_slices(num_samples, |output_slices| {
+                    *output_slices = vec![&mut left_block, &mut right_block];
+                });
+            }
+            producer.update_peaks(&buffer);
+        }
+    }
+
+    /// `seconds` of a 1 kHz tone at 0.1 peak riding on a constant `offset`
+    fn offset_tone(offset: f32, seconds: f32) -> Vec<f32> {
+        (0..(seconds * TEST_SAMPLE_RATE) as usize)
+            .map(|n| {
+                let phase = 2.0 * std::f32::consts::PI * 1000.0 * n as f32 / TEST_SAMPLE_RATE;
+                offset + 0.1 * phase.sin()
+            })
+            .collect()
+    }
+
+    /// Publish a steady `level_db` on every channel, as the audio thread would
+    fn set_input_level(producer: &MeterProducer, level_db: f32) {
+        for level in [
+            &producer.peak_left,
+            &producer.peak_right,
+            &producer.peak_all,
+            &producer.rms_left,
+            &producer.rms_right,
+            &producer.rms_all,
+        ] {
+            level.store(level_db, Ordering::Relaxed);
+        }
+    }
+
+    /// Whether `measured` is within 1% of `expected`
+    fn within_one_percent(measured: f32, expected: f32) -> bool {
+        (measured - expected).abs() <= expected.abs() * 0.01
+    }
+
+    #[test]
+    fn dc_offset_reads_within_one_percent_after_a_second() {
+        let (mut producer, consumer) = create_meter_channels();
+        process_stereo(
+            &mut producer,
+            &offset_tone(0.01, 1.0),
+            &offset_tone(-0.01, 1.0),
+        );
+
+        let (left, right) = consumer.dc_offsets();
+        assert!(within_one_percent(left, 0.01), "left read {left}");
+        assert!(within_one_percent(right, -0.01), "right read {right}");
+    }
+
+    #[test]
+    fn dc_offset_stays_within_one_percent_across_window_halvings() {
+        let (mut producer, consumer) = create_meter_channels();
+        let seconds = 3.0 * DC_OFFSET_WINDOW_SAMPLES as f32 / TEST_SAMPLE_RATE;
+        let signal = offset_tone(0.01, seconds);
+
+        // Check the reading after every block past the first second
+        let settled = TEST_SAMPLE_RATE as usize / TEST_BLOCK_SIZE;
+        let mut halvings = 0;
+        for (index, block) in signal.chunks(TEST_BLOCK_SIZE).enumerate() {
+            let count_before = producer.dc_count;
+            process_stereo(&mut producer, block, block);
+            if producer.dc_count < count_before {
+                halvings += 1;
+            }
+
+            if index >= settled {
+                let (left, right) = consumer.dc_offsets();
+                assert!(
+                    within_one_percent(left, 0.01) && within_one_percent(right, 0.01),
+                    "block {index}: read ({left}, {right})"
+                );
+            }
+        }
+        assert!(halvings >= 2, "only {halvings} halvings in three windows");
+    }
+
+    #[test]
+    fn reset_dc_offset_restarts_the_measurement() {
+        let (mut producer, consumer) = create_meter_channels();
+        let offset = offset_tone(0.01, 1.0);
+        process_stereo(&mut producer, &offset, &offset);
+
+        // A UI reset is picked up on the next block, which then reads on its own
+        consumer.reset_dc_offset();
+        let silence = vec![0.0; TEST_BLOCK_SIZE];
+        process_stereo(&mut producer, &silence, &silence);
+        assert_eq!(consumer.dc_offsets(), (0.0, 0.0));
+
+        // The audio thread's own reset clears the readout immediately
+        process_stereo(&mut producer, &offset, &offset);
+        assert_ne!(consumer.dc_offsets(), (0.0, 0.0));
+        producer.reset_dc_offset();
+        assert_eq!(consumer.dc_offsets(), (0.0, 0.0));
+    }
+
+    #[test]
+    fn burst_ticks_under_a_millisecond_apart_are_merged() {
+        let (producer, consumer) = create_meter_channels();
+        set_input_level(&producer, -6.0);
+        let start = Instant::now();
+
+        // A host catching up can deliver two frames back to back; that must not panic
+        consumer.update_at(start, 1);
+        consumer.update_at(start + Duration::from_micros(500), 2);
+
+        // The skipped tick's time is counted by the next one
+        consumer.update_at(start + Duration::from_millis(16), 3);
+        let expected =
+            MeterBallistics::from_times(DEFAULT_METER_ATTACK_MS, DEFAULT_METER_RELEASE_MS, 0.016);
+        assert_eq!(consumer.ballistics(), expected);
+    }
+
+    #[test]
+    #[cfg(debug_assertions)]
+    #[should_panic(expected = "called twice in tick")]
+    fn a_second_update_in_one_tick_is_caught() {
+        let (_producer, consumer) = create_meter_channels();
+        let start = Instant::now();
+        consumer.update_at(start, 1);
+        consumer.update_at(start + Duration::from_millis(16), 1);
+    }
+
+    #[test]
+    fn resume_does_not_count_the_pause() {
+        let (producer, consumer) = create_meter_channels();
+        set_input_level(&producer, -6.0);
+        let start = Instant::now();
+        consumer.update_at(start, 1);
+        consumer.update_at(start + Duration::from_millis(16), 2);
+
+        // Ten seconds paused, then the first tick after resuming
+        let resumed = start + Duration::from_secs(10);
+        consumer.resume_at(resumed);
+        consumer.update_at(resumed + Duration::from_millis(16), 3);
+
+        assert_eq!(
+            consumer.ballistics(),
+            MeterBallistics::from_times(DEFAULT_METER_ATTACK_MS, DEFAULT_METER_RELEASE_MS, 0.0),
+            "the pause should not count as elapsed time"
+        );
+        assert_eq!(
+            consumer.get_peak_hold_db_or_silence(),
+            -6.0,
+            "the held peak should survive the pause"
+        );
+    }
+
+    /// Update intervals from fast displays to a sluggish host
+    const TICK_INTERVALS_MS: [u64; 5] = [5, 10, 16, 25, 125];
+
+    #[test]
+    fn peak_hold_releases_at_the_hold_duration_at_any_call_rate() {
+        for interval_ms in TICK_INTERVALS_MS {
+            let (producer, consumer) = create_meter_channels();
+            let start = Instant::now();
+            set_input_level(&producer, -6.0);
+            consumer.update_at(start, 0);
+            set_input_level(&producer, -40.0);
+
+            let interval = Duration::from_millis(interval_ms);
+            let released_at = (1..)
+                .map(|tick| {
+                    consumer.update_at(start + interval * tick, tick as u64);
+                    interval * tick
+                })
+                .find(|_| consumer.get_peak_hold_db_or_silence() != -6.0)
+                .unwrap();
+
+            // The first update at or after the hold duration releases it
+            assert!(
+                released_at >= PEAK_HOLD_DURATION && released_at < PEAK_HOLD_DURATION + interval,
+                "{interval_ms} ms updates released the hold after {released_at:?}"
+            );
+        }
+    }
+
+    #[test]
+    fn a_second_update_within_a_millisecond_does_nothing() {
+        let (producer, consumer) = create_meter_channels();
+        set_input_level(&producer, -6.0);
+        let start = Instant::now();
+        consumer.update_at(start, 1);
+        consumer.update_at(start + Duration::from_millis(16), 2);
+        let levels = consumer.get_smoothed_levels_or_silence();
+        let rms_levels = consumer.get_smoothed_rms_levels_or_silence();
+        let ballistics = consumer.ballistics();
+
+        // A louder input arriving on a tick 0.5 ms later is left for the next update
+        set_input_level(&producer, 0.0);
+        consumer.update_at(start + Duration::from_micros(16_500), 3);
+
+        assert_eq!(consumer.get_smoothed_levels_or_silence(), levels);
+        assert_eq!(consumer.get_smoothed_rms_levels_or_silence(), rms_levels);
+        assert_eq!(consumer.ballistics(), ballistics);
+        assert_eq!(consumer.get_peak_hold_db_or_silence(), -6.0);
+    }
+
+    #[test]
+    fn released_peak_hold_falls_at_the_decay_rate() {
+        let decay_db_per_s = 20.0;
+        for interval_ms in TICK_INTERVALS_MS {
+            let (producer, consumer) = create_meter_channels();
+            consumer.set_peak_decay(decay_db_per_s);
+            consumer.set_silence_decay(false, DEFAULT_SILENCE_THRESHOLD_DB, 0.0);
+            let start = Instant::now();
+            set_input_level(&producer, -6.0);
+            consumer.update_at(start, 0);
+            set_input_level(&producer, -40.0);
+
+            // Run to 1.5 s and 2 s, both well past the hold
+            let interval = Duration::from_millis(interval_ms);
+            let mut tick = 0;
+            let mut hold_at = |until: Duration| {
+                while interval * tick < until {
+                    tick += 1;
+                    consumer.update_at(start + interval * tick, tick as u64);
+                }
+                (interval * tick, consumer.get_peak_hold_db_or_silence())
+            };
+            let (first_time, first_db) = hold_at(Duration::from_millis(1500));
+            let (second_time, second_db) = hold_at(Duration::from_millis(2000));
+
+            assert!(
+                first_db < -6.0,
+                "{interval_ms} ms updates: hold still at {first_db} dB"
+            );
+            let fallen_db = decay_db_per_s * (second_time - first_time).as_secs_f32();
+            assert!(
+                (first_db - second_db - fallen_db).abs() < 1e-3,
+                "{interval_ms} ms updates fell from {first_db} to {second_db} dB, \
+                 expected {fallen_db} dB"
+            );
+        }
+    }
+
+    /// Deterministic white noise, uniform in -1..1
+    fn white_noise(count: usize, seed: u64) -> Vec<f32> {
+        let mut state = seed;
+        (0..count)
+            .map(|_| {
+                state = state
+                    .wrapping_mul(6364136223846793005)
+                    .wrapping_add(1442695040888963407);
+                (state >> 40) as f32 / (1u64 << 23) as f32 - 1.0
+            })
+            .collect()
+    }
+
+    #[test]
+    fn correlation_tells_identical_inverted_and_unrelated_channels() {
+        let (mut producer, consumer) = create_meter_channels();
+        let left = white_noise(TEST_BLOCK_SIZE, 1);
+        let inverted: Vec<f32> = left.iter().map(|sample| -sample).collect();
+        let unrelated = white_noise(TEST_BLOCK_SIZE, 2);
+
+        process_stereo(&mut producer, &left, &left);
+        assert!(consumer.correlation() > MONO_MIN_CORRELATION);
+
+        process_stereo(&mut producer, &left, &inverted);
+        assert!(consumer.correlation() < -MONO_MIN_CORRELATION);
+
+        process_stereo(&mut producer, &left, &unrelated);
+        let unrelated_correlation = consumer.correlation();
+        assert!(
+            unrelated_correlation.abs() < 0.2,
+            "independent noise correlated at {unrelated_correlation}"
+        );
+
+        // A silent side leaves the last reading alone
+        process_stereo(&mut producer, &left, &[0.0; TEST_BLOCK_SIZE]);
+        assert_eq!(consumer.correlation(), unrelated_correlation);
+    }
+
+    #[test]
+    fn mono_detector_needs_correlated_channels() {
+        let start = Instant::now();
+        let level_db = (-6.0, -6.0);
+        let mono_after = |correlation: f32| {
+            let mut detector = MonoDetector::new();
+            (0..=200)
+                .map(|tick| start + Duration::from_millis(10 * tick))
+                .find(|&now| {
+                    detector.update(level_db, level_db, correlation, now);
+                    detector.is_mono()
+                })
+                .map(|now| now - start)
+        };
+
+        // Matching levels light the badge after the detect time only when correlated
+        let lit_after = mono_after(1.0).expect("identical channels should read as mono");
+        assert!(lit_after >= MONO_DETECT_TIME, "lit after {lit_after:?}");
+        assert_eq!(
+            mono_after(0.0),
+            None,
+            "equal-level unrelated channels read as mono"
+        );
+        assert_eq!(
+            mono_after(0.99),
+            None,
+            "loosely correlated channels read as mono"
+        );
+    }
+
+    #[test]
+    fn a_poisoned_meter_state_reads_as_silence() {
+        let (producer, consumer) = create_meter_channels();
+        set_input_level(&producer, -6.0);
+        let start = Instant::now();
+        consumer.update_at(start, 1);
+        consumer.update_at(start + Duration::from_millis(16), 2);
+
+        // A UI thread panicking mid-update leaves the mutex poisoned
+        let state = Arc::clone(&consumer.state);
+        let panicked = std::thread::spawn(move || {
+            let _guard = state.lock().unwrap();
+            panic!("poisoning the meter state");
+        })
+        .join();
+        assert!(panicked.is_err() && consumer.state.is_poisoned());
+
+        // Fallible getters report it, the rest fall back to silence, and nothing panics
+        let silence = util::MINUS_INFINITY_DB;
+        assert!(matches!(
+            consumer.get_smoothed_levels(),
+            Err(MeterError::LockFailed)
+        ));
+        assert!(matches!(
+            consumer.get_smoothed_aggregate_level(),
+            Err(MeterError::LockFailed)
+        ));
+        assert!(matches!(
+            consumer.get_peak_hold_db(),
+            Err(MeterError::LockFailed)
+        ));
+        assert_eq!(
+            consumer.get_smoothed_levels_or_silence(),
+            (silence, silence)
+        );
+        assert_eq!(consumer.get_smoothed_aggregate_level_or_silence(), silence);
+        assert_eq!(
+            consumer.get_smoothed_rms_levels_or_silence(),
+            (silence, silence)
+        );
+        assert_eq!(consumer.get_smoothed_aggregate_rms_or_silence(), silence);
+        assert_eq!(
+            consumer.get_peak_hold_levels_or_silence(),
+            (silence, silence)
+        );
+        assert_eq!(consumer.get_peak_hold_db_or_silence(), silence);
+        for channel in [
+            PeakReadoutChannel::Max,
+            PeakReadoutChannel::Average,
+            PeakReadoutChannel::Left,
+            PeakReadoutChannel::Right,
+        ] {
+            assert_eq!(consumer.get_peak_hold_db_for_or_silence(channel), silence);
+        }
+
+        // Updates and settings are skipped rather than panicking the editor
+        consumer.update_at(start + Duration::from_millis(32), 3);
+        consumer.set_peak_decay(20.0);
+        consumer.reset_peak_hold();
+        consumer.resume();
+        assert_eq!(
+            consumer.ballistics(),
+            MeterBallistics {
+                attack_alpha: 1.0,
+                release_alpha: 1.0,
+            }
+        );
+
+        // The raw atomics don't go through the lock
+        assert_eq!(consumer.get_peak_levels(), (-6.0, -6.0));
+    }
+}
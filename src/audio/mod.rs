@@ -1,5 +1,6 @@
 pub mod constants;
 pub mod errors;
+pub mod fixed_spectrum;
 pub mod meter;
 pub mod spectrum;
 pub mod window_functions;
@@ -1,27 +1,41 @@
+use atomic_float::AtomicF32;
 use nih_plug::prelude::*;
-use realfft::{num_complex::Complex32, RealFftPlanner, RealToComplex};
+use realfft::{num_complex::Complex, num_complex::Complex32, RealFftPlanner, RealToComplex};
+#[cfg(feature = "serialize")]
+use serde::{Deserialize, Serialize};
 use std::num::NonZeroUsize;
+use std::sync::atomic::AtomicBool;
 use std::sync::*;
 use triple_buffer::TripleBuffer;
 
 use super::errors::{SpectrumError, SpectrumResult};
-use super::window_functions::WindowType;
+use super::meter::MeterBallistics;
+use super::window_functions::{is_cola, Sample, WindowType};
 use crate::{ResolutionLevel, TiltLevel};
 
 /// Maximum FFT size we support (for buffer allocation)
-pub const MAX_FFT_SIZE: NonZeroUsize = unsafe { NonZeroUsize::new_unchecked(4096) };
+pub const MAX_FFT_SIZE: NonZeroUsize = unsafe { NonZeroUsize::new_unchecked(16384) };
 
 /// Maximum FFT size as usize for convenience
 pub const MAX_FFT_SIZE_USIZE: usize = MAX_FFT_SIZE.get();
 
+/// Smallest FFT size the producer accepts
+pub const MIN_FFT_SIZE: usize = 256;
+
+/// FFT size the producer starts with, until [`SpectrumProducer::set_fft_size`] changes it
+pub const DEFAULT_FFT_SIZE: usize = 4096;
+
 /// Maximum number of frequency bins (for maximum FFT size)
 pub const MAX_SPECTRUM_BINS: usize = MAX_FFT_SIZE_USIZE / 2 + 1;
 
 /// Spectrum analyser floor prevents log(0) in FFT calculations
-const SPECTRUM_FLOOR_DB: f32 = -140.0;
+pub const SPECTRUM_FLOOR_DB: f32 = -140.0;
 
-/// FFT overlap factor (50% overlap between consecutive FFT windows)
-const FFT_OVERLAP_FACTOR: f32 = 0.5;
+/// A full-resolution spectrum of silence, published without allocating
+static SILENT_SPECTRUM: [f32; MAX_SPECTRUM_BINS] = [SPECTRUM_FLOOR_DB; MAX_SPECTRUM_BINS];
+
+/// Sample rate assumed before the host reports one
+const DEFAULT_SAMPLE_RATE: f32 = 44100.0;
 
 /// Ring buffer size multiplier to accommodate overlap
 const RING_BUFFER_SIZE_MULTIPLIER: usize = 2;
@@ -35,28 +49,275 @@ const TILT_REFERENCE_FREQ_HZ: f32 = 1000.0;
 /// Minimum frequency threshold to avoid log(0) in tilt calculation
 const MIN_FREQ_THRESHOLD: f32 = 0.001;
 
+/// Nominal frequency of the internal calibration tone
+const CALIBRATION_TONE_HZ: f32 = 1000.0;
+
+/// Level a full-scale calibration sine should read, in dBFS
+/// See [`calibration_level_db`] for how the native RMS reading is brought onto this scale
+pub const CALIBRATION_EXPECTED_DB: f32 = 0.0;
+
+/// Allowed deviation from the expected calibration level
+pub const CALIBRATION_TOLERANCE_DB: f32 = 0.2;
+
 /// The spectrum analyser's frequency data - vector of magnitude values in dB
 /// Variable size based on resolution setting
 pub type SpectrumData = Vec<f32>;
 
+/// A published spectrum together with the settings the producer used to make it
+/// The UI reads these values instead of assuming them, so window size, sample rate
+/// and floor can change without separately shared state
+#[derive(Debug, Clone)]
+#[cfg_attr(feature = "serialize", derive(Serialize, Deserialize))]
+// Fields missing from an older snapshot load as a silent frame's
+#[cfg_attr(feature = "serialize", serde(default))]
+pub struct SpectrumFrame {
+    /// Magnitude values in dB, evenly spaced from DC to Nyquist
+    pub data: SpectrumData,
+    /// Sample rate the frame was analysed at
+    pub sample_rate: f32,
+    /// FFT window size in samples
+    pub window_size: usize,
+    /// Level used for silent or out-of-range bins
+    pub floor_db: f32,
+    /// Increments with every published frame (0 = no analysis yet)
+    pub frame_index: u64,
+    /// Per-bin minimum since the last envelope reset (empty when disabled)
+    pub envelope_min: SpectrumData,
+    /// Per-bin maximum since the last envelope reset (empty when disabled)
+    pub envelope_max: SpectrumData,
+}
+
+impl SpectrumFrame {
+    /// A silent frame with the given number of bins
+    pub fn silence(bin_count: usize, sample_rate: f32) -> Self {
+        Self {
+            data: vec![SPECTRUM_FLOOR_DB; bin_count],
+            sample_rate,
+            window_size: DEFAULT_FFT_SIZE,
+            floor_db: SPECTRUM_FLOOR_DB,
+            frame_index: 0,
+            envelope_min: SpectrumData::new(),
+            envelope_max: SpectrumData::new(),
+        }
+    }
+}
+
+impl Default for SpectrumFrame {
+    fn default() -> Self {
+        Self::silence(0, DEFAULT_SAMPLE_RATE)
+    }
+}
+
+/// Version written into each [`SpectrumSnapshot`]
+/// Bump it when a field changes meaning; added fields load with defaults instead
+#[cfg(feature = "serialize")]
+pub const SNAPSHOT_VERSION: u32 = 1;
+
+/// A spectrum frame saved for later, e.g. as JSON for golden files or external tools
+/// Serializes flat, as `{"version": 1, "data": [...], "sample_rate": ...}`
+#[cfg(feature = "serialize")]
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct SpectrumSnapshot {
+    pub version: u32,
+    #[serde(flatten)]
+    pub frame: SpectrumFrame,
+}
+
+#[cfg(feature = "serialize")]
+impl SpectrumSnapshot {
+    pub fn new(frame: SpectrumFrame) -> Self {
+        Self {
+            version: SNAPSHOT_VERSION,
+            frame,
+        }
+    }
+}
+
+/// Strongest spectral peak with sub-bin frequency and level estimates
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct SpectrumPeak {
+    pub frequency_hz: f32,
+    pub level_db: f32,
+}
+
+/// Most peaks tracked for the peak marker overlay
+pub const MAX_PEAK_MARKERS: usize = 5;
+
+/// Octave bands used for the spectral slope fit, as octaves from 1kHz (31 Hz to 16 kHz)
+const SLOPE_LOWEST_OCTAVE: i32 = -5;
+const SLOPE_HIGHEST_OCTAVE: i32 = 4;
+const SLOPE_BAND_COUNT: usize = (SLOPE_HIGHEST_OCTAVE - SLOPE_LOWEST_OCTAVE + 1) as usize;
+
+/// Octave bands quieter than this are left out of the slope fit
+const SLOPE_MIN_BAND_DB: f32 = -100.0;
+
+/// Fewest usable octave bands for a meaningful slope
+const SLOPE_MIN_BANDS: usize = 3;
+
+/// Most frames a Welch periodogram can average
+pub const MAX_WELCH_AVERAGES: i32 = 64;
+
+/// Hann window equivalent noise bandwidth in bins
+/// Summing per-bin power over-counts broadband energy by this factor
+const HANN_ENBW_BINS: f32 = 1.5;
+
+/// RMS level in the low, mid and high bands of the full-resolution spectrum
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct BandLevels {
+    pub low_db: f32,
+    pub mid_db: f32,
+    pub high_db: f32,
+}
+
+impl BandLevels {
+    /// All bands at the spectrum floor
+    pub fn silence() -> Self {
+        Self {
+            low_db: SPECTRUM_FLOOR_DB,
+            mid_db: SPECTRUM_FLOOR_DB,
+            high_db: SPECTRUM_FLOOR_DB,
+        }
+    }
+
+    /// Move each band towards `target` with the level meter's ballistics
+    #[must_use]
+    pub fn smoothed_towards(self, target: BandLevels, ballistics: MeterBallistics) -> Self {
+        Self {
+            low_db: ballistics.smooth(self.low_db, target.low_db),
+            mid_db: ballistics.smooth(self.mid_db, target.mid_db),
+            high_db: ballistics.smooth(self.high_db, target.high_db),
+        }
+    }
+}
+
+/// Band levels shared between audio and UI thread (lock-free)
+struct SharedBandLevels {
+    low_db: AtomicF32,
+    mid_db: AtomicF32,
+    high_db: AtomicF32,
+    /// Level in the user-defined alarm band
+    alarm_db: AtomicF32,
+    /// Spectral slope in dB/octave, NaN when too quiet to measure
+    slope_db_per_octave: AtomicF32,
+}
+
+/// Peak estimate shared between audio and UI thread (lock-free)
+struct SharedPeak {
+    frequency_hz: AtomicF32,
+    level_db: AtomicF32,
+}
+
 /// Cloneable wrapper for spectrum output channel (UI thread reads from this)
 /// Uses Arc<Mutex<>> wrapper to allow cloning for editor initialization
 #[derive(Clone)]
 pub struct SpectrumConsumer {
-    output: Arc<Mutex<triple_buffer::Output<SpectrumData>>>,
+    output: Arc<Mutex<triple_buffer::Output<SpectrumFrame>>>,
+    /// Interpolated peak of the full-resolution spectrum
+    peak: Arc<SharedPeak>,
+    /// Loudest local maxima of the full-resolution spectrum, loudest first
+    top_peaks: Arc<[SharedPeak; MAX_PEAK_MARKERS]>,
+    /// Unsmoothed low/mid/high band levels of the latest frame
+    band_levels: Arc<SharedBandLevels>,
+    /// Set by the UI to restart the min/max envelope on the next frame
+    envelope_reset: Arc<AtomicBool>,
 }
 
 impl SpectrumConsumer {
-    fn new(output: triple_buffer::Output<SpectrumData>) -> Self {
+    fn new(
+        output: triple_buffer::Output<SpectrumFrame>,
+        peak: Arc<SharedPeak>,
+        top_peaks: Arc<[SharedPeak; MAX_PEAK_MARKERS]>,
+        band_levels: Arc<SharedBandLevels>,
+        envelope_reset: Arc<AtomicBool>,
+    ) -> Self {
         Self {
             output: Arc::new(Mutex::new(output)),
+            peak,
+            top_peaks,
+            band_levels,
+            envelope_reset,
+        }
+    }
+
+    /// Ask the audio thread to restart the min/max envelope from the next frame
+    pub fn reset_envelope(&self) {
+        self.envelope_reset
+            .store(true, std::sync::atomic::Ordering::Relaxed);
+    }
+
+    /// Low/mid/high RMS levels of the latest frame, measured before decimation and tilt
+    /// Unsmoothed - apply [`BandLevels::smoothed_towards`] for display
+    #[must_use]
+    pub fn band_levels(&self) -> BandLevels {
+        BandLevels {
+            low_db: self
+                .band_levels
+                .low_db
+                .load(std::sync::atomic::Ordering::Relaxed),
+            mid_db: self
+                .band_levels
+                .mid_db
+                .load(std::sync::atomic::Ordering::Relaxed),
+            high_db: self
+                .band_levels
+                .high_db
+                .load(std::sync::atomic::Ordering::Relaxed),
+        }
+    }
+
+    /// RMS level in the band alarm's frequency range for the latest frame
+    /// Measured like [`Self::band_levels`], unsmoothed
+    #[must_use]
+    pub fn alarm_band_level(&self) -> f32 {
+        self.band_levels
+            .alarm_db
+            .load(std::sync::atomic::Ordering::Relaxed)
+    }
+
+    /// Spectral slope of the latest frame in dB/octave, measured like [`Self::band_levels`]
+    /// None while too few octave bands carry signal, e.g. during silence
+    #[must_use]
+    pub fn spectral_slope(&self) -> Option<f32> {
+        let slope = self
+            .band_levels
+            .slope_db_per_octave
+            .load(std::sync::atomic::Ordering::Relaxed);
+        (!slope.is_nan()).then_some(slope)
+    }
+
+    /// Strongest peak of the latest frame, measured before decimation and tilt
+    #[must_use]
+    pub fn peak(&self) -> SpectrumPeak {
+        SpectrumPeak {
+            frequency_hz: self
+                .peak
+                .frequency_hz
+                .load(std::sync::atomic::Ordering::Relaxed),
+            level_db: self
+                .peak
+                .level_db
+                .load(std::sync::atomic::Ordering::Relaxed),
         }
     }
 
-    /// Read latest spectrum data for UI display
+    /// Loudest peaks of the latest frame, loudest first, measured before decimation and tilt
+    /// Unused slots hold a peak at the spectrum floor
+    #[must_use]
+    pub fn top_peaks(&self) -> [SpectrumPeak; MAX_PEAK_MARKERS] {
+        std::array::from_fn(|rank| SpectrumPeak {
+            frequency_hz: self.top_peaks[rank]
+                .frequency_hz
+                .load(std::sync::atomic::Ordering::Relaxed),
+            level_db: self.top_peaks[rank]
+                .level_db
+                .load(std::sync::atomic::Ordering::Relaxed),
+        })
+    }
+
+    /// Read latest spectrum frame for UI display
     /// Called from UI thread only
     #[must_use]
-    pub fn read(&self) -> SpectrumResult<SpectrumData> {
+    pub fn read(&self) -> SpectrumResult<SpectrumFrame> {
         self.output
             .try_lock()
             .map(|mut output| output.read().clone())
@@ -65,16 +326,38 @@ impl SpectrumConsumer {
             })
     }
 
-    /// Read latest spectrum data with fallback to silence
+    /// Read the latest frame only if it is newer than `last_frame_index`
+    /// Returns `Ok(None)` when the caller already has this frame, avoiding the copy
+    #[must_use]
+    pub fn read_if_newer(&self, last_frame_index: u64) -> SpectrumResult<Option<SpectrumFrame>> {
+        self.output
+            .try_lock()
+            .map(|mut output| {
+                let frame = output.read();
+                if frame.frame_index != last_frame_index {
+                    Some(frame.clone())
+                } else {
+                    None
+                }
+            })
+            .map_err(|_| SpectrumError::LockFailed {
+                resource: "spectrum output".to_string(),
+            })
+    }
+
+    /// Read latest spectrum frame with fallback to silence
     /// Convenience method for when you want to always get data
     #[must_use]
-    pub fn read_or_silence(&self) -> SpectrumData {
-        self.read().unwrap_or_else(|_| vec![SPECTRUM_FLOOR_DB; 256]) // Default fallback size
+    pub fn read_or_silence(&self) -> SpectrumFrame {
+        // Default fallback size
+        self.read()
+            .unwrap_or_else(|_| SpectrumFrame::silence(256, DEFAULT_SAMPLE_RATE))
     }
 }
 
 /// Spectrum analyser speed presets for temporal envelope (attack/release)
 #[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, nih_plug::prelude::Enum)]
+#[cfg_attr(feature = "serialize", derive(Serialize, Deserialize))]
 #[allow(dead_code)]
 pub enum SpectrumSpeed {
     #[id = "very_slow"]
@@ -94,9 +377,104 @@ pub enum SpectrumSpeed {
     VeryFast,
 }
 
+/// Overlap between consecutive FFT frames
+/// Both settings are constant-overlap-add (COLA) for the Hann window, so every input
+/// sample is weighted equally across frames; 75% gives finer time resolution
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, nih_plug::prelude::Enum)]
+pub enum FftOverlap {
+    #[id = "50"]
+    #[name = "50%"]
+    Half,
+    #[id = "75"]
+    #[name = "75%"]
+    ThreeQuarters,
+}
+
+impl FftOverlap {
+    /// Samples between the starts of consecutive FFT frames
+    pub fn hop_size(&self, fft_size: usize) -> usize {
+        match self {
+            Self::Half => fft_size / 2,
+            Self::ThreeQuarters => fft_size / 4,
+        }
+    }
+}
+
+/// Rate at which finished frames are published to the UI
+/// Analysis (and temporal smoothing) still runs on every FFT frame
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, nih_plug::prelude::Enum)]
+pub enum PublishRate {
+    #[id = "every_frame"]
+    #[name = "Every Frame"]
+    EveryFrame,
+    #[id = "30hz"]
+    #[name = "30 Hz"]
+    Hz30,
+    #[id = "20hz"]
+    #[name = "20 Hz"]
+    Hz20,
+    #[id = "15hz"]
+    #[name = "15 Hz"]
+    Hz15,
+}
+
+impl PublishRate {
+    /// Target publish rate in Hz, `None` to publish every frame
+    fn target_hz(&self) -> Option<f32> {
+        match self {
+            Self::EveryFrame => None,
+            Self::Hz30 => Some(30.0),
+            Self::Hz20 => Some(20.0),
+            Self::Hz15 => Some(15.0),
+        }
+    }
+
+    /// Number of FFT frames per publish at the given sample rate and hop size
+    fn frame_interval(&self, sample_rate: f32, hop_size: usize) -> usize {
+        let Some(target_hz) = self.target_hz() else {
+            return 1;
+        };
+        let fft_frames_per_second = sample_rate / hop_size as f32;
+        ((fft_frames_per_second / target_hz).round() as usize).max(1)
+    }
+}
+
+/// Level convention used to label the spectrum, for cross-checking with other analysers
+///
+/// The magnitudes are the same either way; only the dB offset differs.
+/// - `SineRms`: a full-scale sine reads its RMS level, -3.01 dB (this analyser's native scale)
+/// - `SinePeak`: a full-scale sine reads 0 dB, the convention most analysers default to
+/// - `NoisePower`: corrects for the Hann window's 1.5-bin noise bandwidth (-1.76 dB), so
+///   broadband noise such as pink noise reads its true per-bin power rather than the
+///   sine-calibrated level
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, nih_plug::prelude::Enum)]
+pub enum ReferenceNormalization {
+    #[id = "sine_rms"]
+    #[name = "Sine RMS (-3 dB)"]
+    SineRms,
+    #[id = "sine_peak"]
+    #[name = "Sine Peak (0 dB)"]
+    SinePeak,
+    #[id = "noise_power"]
+    #[name = "Noise Power (ENBW)"]
+    NoisePower,
+}
+
+impl ReferenceNormalization {
+    /// Offset added to the native (sine RMS) levels
+    pub fn offset_db(&self) -> f32 {
+        match self {
+            Self::SineRms => 0.0,
+            Self::SinePeak => 20.0 * std::f32::consts::SQRT_2.log10(),
+            Self::NoisePower => -10.0 * HANN_ENBW_BINS.log10(),
+        }
+    }
+}
+
 impl SpectrumSpeed {
     /// Get response time constant in milliseconds for temporal envelope
-    fn response_time_ms(&self) -> f32 {
+    /// This is the release; rises are shown immediately
+    pub fn response_time_ms(&self) -> f32 {
         match self {
             Self::VerySlow => 5000.0,
             Self::Slow => 1500.0,
@@ -107,11 +485,67 @@ impl SpectrumSpeed {
     }
 }
 
+/// Time resolution and smoothing of the analysis, as chosen by window, overlap and speed
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct AnalysisTiming {
+    /// Length of audio in each FFT frame
+    pub window_ms: f32,
+    /// Time between the starts of consecutive FFT frames
+    pub hop_ms: f32,
+    /// Release time constant of the temporal envelope
+    pub release_ms: f32,
+}
+
+impl AnalysisTiming {
+    /// Timing of the analysis at `sample_rate` with `fft_size`-sample frames
+    /// The frame length is the FFT size, whatever the display resolution
+    pub fn new(
+        sample_rate: f32,
+        fft_size: usize,
+        overlap: FftOverlap,
+        speed: SpectrumSpeed,
+    ) -> Self {
+        let samples_to_ms = |samples: usize| samples as f32 / sample_rate * 1000.0;
+        Self {
+            window_ms: samples_to_ms(fft_size),
+            hop_ms: samples_to_ms(overlap.hop_size(fft_size)),
+            release_ms: speed.response_time_ms(),
+        }
+    }
+}
+
+/// Snapshot of the producer's analysis settings, for logging and reproducing a measurement
+/// Speed and tilt are the values passed to the most recent `process` call
+#[derive(Debug, Clone, Copy, PartialEq)]
+#[allow(dead_code)] // Introspection API for hosts and test harnesses
+pub struct SpectrumConfig {
+    /// Sample rate of the most recent block (Hz)
+    pub sample_rate: f32,
+    /// Samples in each FFT frame
+    pub window_size: usize,
+    /// Window applied to each frame
+    pub window_type: WindowType,
+    /// Overlap between consecutive FFT frames
+    pub overlap: FftOverlap,
+    /// Bins in each published frame
+    pub bin_count: usize,
+    /// Temporal envelope release preset
+    pub speed: SpectrumSpeed,
+    /// Display tilt around 1 kHz (dB/octave)
+    pub tilt_db_per_octave: f32,
+    /// Level convention of the published magnitudes
+    pub reference_normalization: ReferenceNormalization,
+}
+
 /// Continuously computes frequency spectrum and sends to [`SpectrumConsumer`] (audio thread writes to this)
 pub struct SpectrumProducer {
     /// FFT processing engine for frequency domain transformation
     fft_processor: Arc<dyn RealToComplex<f32>>,
-    /// Pre-computed Hann window for spectrum analysis
+    /// Samples in each FFT frame
+    fft_size: usize,
+    /// Window applied to each frame before the FFT
+    window_type: WindowType,
+    /// Pre-computed coefficients of `window_type`
     window_coefficients: Vec<f32>,
     /// Ring buffer for accumulating samples across multiple process calls
     ring_buffer: Vec<f32>,
@@ -129,8 +563,61 @@ pub struct SpectrumProducer {
     previous_spectrum: SpectrumData,
     /// Current resolution level that determines buffer sizes
     current_resolution: ResolutionLevel,
+    /// Whether analysis is paused (no samples consumed, nothing published)
+    paused: bool,
+    /// How often finished frames are sent to the UI
+    publish_rate: PublishRate,
+    /// Overlap between FFT frames, only ever a COLA-compliant one for the window
+    overlap: FftOverlap,
+    /// Speed passed to the most recent `process` call
+    speed: SpectrumSpeed,
+    /// Tilt passed to the most recent `process` call (dB/octave)
+    tilt_db_per_octave: f32,
+    /// FFT frames computed since the last publish
+    frames_since_publish: usize,
+    /// Whether the internal calibration tone replaces the input
+    calibration_enabled: bool,
+    /// Phase of the calibration tone oscillator (0.0 to 1.0)
+    calibration_phase: f32,
+    /// Linear analysis trims for the left and right channels (applied before the mono sum)
+    channel_trims: [f32; 2],
+    /// Level convention applied to the displayed spectrum and peaks
+    reference_normalization: ReferenceNormalization,
+    /// Whether the DC and Nyquist bins are replaced by their neighbours
+    hide_edge_bins: bool,
+    /// Largest change any bin may make per FFT frame (dB), 0 = unlimited
+    slew_limit_db: f32,
+    /// Frames averaged per Welch periodogram, 1 = off
+    welch_averages: usize,
+    /// Linear power summed per full-resolution bin over the current Welch block
+    welch_power_sum: Vec<f32>,
+    /// Frames summed into `welch_power_sum` so far
+    welch_frame_count: usize,
+    /// Interpolated peak of the latest frame, shared with the consumer
+    peak: Arc<SharedPeak>,
+    /// Loudest local maxima of the latest frame, shared with the consumer
+    top_peaks: Arc<[SharedPeak; MAX_PEAK_MARKERS]>,
+    /// Scratch list of local maxima (preallocated for the full spectrum)
+    peak_candidates: Vec<SpectrumPeak>,
+    /// Low/mid and mid/high band edges in Hz
+    band_edges_hz: (f32, f32),
+    /// Band levels of the latest frame, shared with the consumer
+    band_levels: Arc<SharedBandLevels>,
+    /// Lower and upper edge of the band alarm range in Hz
+    alarm_band_hz: (f32, f32),
+    /// Whether the long-term min/max envelope is tracked and published
+    envelope_enabled: bool,
+    /// Per-bin running minimum and maximum of `spectrum_result`
+    envelope_min: SpectrumData,
+    envelope_max: SpectrumData,
+    /// Reset request from the UI thread
+    envelope_reset: Arc<AtomicBool>,
+    /// Sample rate of the most recent process call (stamped on published frames)
+    sample_rate: f32,
+    /// Index of the last published frame
+    frame_index: u64,
     /// Triple buffer producer for lock-free communication to UI
-    spectrum_producer: triple_buffer::Input<SpectrumData>,
+    spectrum_producer: triple_buffer::Input<SpectrumFrame>,
     /// Count of FFT failures (for debugging without impacting performance)
     fft_failure_count: std::sync::atomic::AtomicU32,
 }
@@ -140,40 +627,302 @@ impl SpectrumProducer {
     #[must_use = "SpectrumProducer and consumer must be used"]
     pub fn new() -> (SpectrumProducer, SpectrumConsumer) {
         // Create lock-free communication channel initialized with maximum possible size
-        let (spectrum_producer, spectrum_consumer) =
-            TripleBuffer::new(&vec![SPECTRUM_FLOOR_DB; MAX_SPECTRUM_BINS]).split();
+        let (mut spectrum_producer, mut spectrum_consumer) = TripleBuffer::new(
+            &SpectrumFrame::silence(MAX_SPECTRUM_BINS, DEFAULT_SAMPLE_RATE),
+        )
+        .split();
+
+        // Cloning the initial frame doesn't carry over spare capacity, so reserve every
+        // buffer's bins here; each publish/update pair rotates the next buffer into the
+        // input slot, and `publish` never has to allocate on the audio thread
+        for _ in 0..3 {
+            let frame = spectrum_producer.input_buffer_mut();
+            for bins in [
+                &mut frame.data,
+                &mut frame.envelope_min,
+                &mut frame.envelope_max,
+            ] {
+                bins.reserve(MAX_SPECTRUM_BINS - bins.len());
+            }
+            spectrum_producer.publish();
+            spectrum_consumer.update();
+        }
 
-        // Initialize FFT processor with configured size
+        // Initialize FFT processor with the default size
+        let fft_size = DEFAULT_FFT_SIZE;
         let mut fft_planner = RealFftPlanner::<f32>::new();
-        let fft_processor = fft_planner.plan_fft_forward(MAX_FFT_SIZE_USIZE);
+        let fft_processor = fft_planner.plan_fft_forward(fft_size);
 
-        // Generate Hann window for maximum size
-        let window_coefficients = WindowType::Hann.generate(MAX_FFT_SIZE_USIZE);
+        // Generate Hann window for the default size
+        let window_type = WindowType::Hann;
+        let window_coefficients = window_type.generate(fft_size);
+
+        let peak = Arc::new(SharedPeak {
+            frequency_hz: AtomicF32::new(0.0),
+            level_db: AtomicF32::new(SPECTRUM_FLOOR_DB),
+        });
+        let top_peaks = Arc::new(std::array::from_fn(|_| SharedPeak {
+            frequency_hz: AtomicF32::new(0.0),
+            level_db: AtomicF32::new(SPECTRUM_FLOOR_DB),
+        }));
+        let envelope_reset = Arc::new(AtomicBool::new(false));
+        let band_levels = Arc::new(SharedBandLevels {
+            low_db: AtomicF32::new(SPECTRUM_FLOOR_DB),
+            mid_db: AtomicF32::new(SPECTRUM_FLOOR_DB),
+            high_db: AtomicF32::new(SPECTRUM_FLOOR_DB),
+            alarm_db: AtomicF32::new(SPECTRUM_FLOOR_DB),
+            slope_db_per_octave: AtomicF32::new(f32::NAN),
+        });
 
         let analyser = SpectrumProducer {
             fft_processor,
+            fft_size,
+            window_type,
             window_coefficients,
-            ring_buffer: vec![0.0; MAX_FFT_SIZE_USIZE * RING_BUFFER_SIZE_MULTIPLIER],
+            ring_buffer: vec![0.0; fft_size * RING_BUFFER_SIZE_MULTIPLIER],
             ring_buffer_pos: 0,
             samples_since_fft: 0,
-            time_domain_buffer: vec![0.0; MAX_FFT_SIZE_USIZE],
-            frequency_domain_buffer: vec![Complex32::new(0.0, 0.0); MAX_SPECTRUM_BINS],
-            spectrum_result: vec![SPECTRUM_FLOOR_DB; ResolutionLevel::Medium.to_bin_count()],
-            previous_spectrum: vec![SPECTRUM_FLOOR_DB; ResolutionLevel::Medium.to_bin_count()],
+            time_domain_buffer: vec![0.0; fft_size],
+            frequency_domain_buffer: vec![Complex32::new(0.0, 0.0); fft_size / 2 + 1],
+            // Full capacity up front so a resolution change resizes without allocating
+            spectrum_result: preallocated_bins(ResolutionLevel::Medium.to_bin_count()),
+            previous_spectrum: preallocated_bins(ResolutionLevel::Medium.to_bin_count()),
             current_resolution: ResolutionLevel::Medium,
+            paused: false,
+            publish_rate: PublishRate::EveryFrame,
+            overlap: FftOverlap::Half,
+            speed: SpectrumSpeed::Medium,
+            tilt_db_per_octave: 0.0,
+            frames_since_publish: 0,
+            calibration_enabled: false,
+            calibration_phase: 0.0,
+            channel_trims: [1.0, 1.0],
+            reference_normalization: ReferenceNormalization::SineRms,
+            hide_edge_bins: false,
+            slew_limit_db: 0.0,
+            welch_averages: 1,
+            welch_power_sum: vec![0.0; fft_size / 2 + 1],
+            welch_frame_count: 0,
+            peak: peak.clone(),
+            top_peaks: top_peaks.clone(),
+            peak_candidates: Vec::with_capacity(fft_size / 2 + 1),
+            band_edges_hz: (120.0, 2000.0),
+            band_levels: band_levels.clone(),
+            alarm_band_hz: (200.0, 500.0),
+            envelope_enabled: false,
+            envelope_min: SpectrumData::with_capacity(MAX_SPECTRUM_BINS),
+            envelope_max: SpectrumData::with_capacity(MAX_SPECTRUM_BINS),
+            envelope_reset: envelope_reset.clone(),
+            sample_rate: DEFAULT_SAMPLE_RATE,
+            frame_index: 0,
             spectrum_producer,
             fft_failure_count: std::sync::atomic::AtomicU32::new(0),
         };
 
-        (analyser, SpectrumConsumer::new(spectrum_consumer))
+        (
+            analyser,
+            SpectrumConsumer::new(
+                spectrum_consumer,
+                peak,
+                top_peaks,
+                band_levels,
+                envelope_reset,
+            ),
+        )
     }
 
     /// Write silence to the spectrum buffer (used when plugin is deactivated)
     /// This ensures the UI gets actual silence instead of stale audio data
     pub fn write_silence(&mut self) {
         // Use current spectrum_result size to maintain resolution
-        let silence = vec![SPECTRUM_FLOOR_DB; self.spectrum_result.len()];
-        self.spectrum_producer.write(silence);
+        self.publish(&SILENT_SPECTRUM[..self.spectrum_result.len()]);
+    }
+
+    /// Send a precomputed spectrum to the UI in place of the built-in analysis
+    /// For headless tests and hosts driving the display from their own analysis; `data`
+    /// is in dB, evenly spaced from DC to Nyquist, at any length (more than
+    /// `MAX_SPECTRUM_BINS` bins makes the published frame reallocate)
+    /// The frame is stamped with the sample rate of the last `process` call, or the default
+    /// before the first one
+    pub fn write(&mut self, data: &[f32]) {
+        self.publish(data);
+    }
+
+    /// Stamp spectrum data with the current analysis settings and send it to the UI
+    /// Fills the triple buffer's preallocated input frame in place, so this is safe to call
+    /// on the audio thread
+    fn publish(&mut self, data: &[f32]) {
+        self.frame_index += 1;
+        let frame = self.spectrum_producer.input_buffer_mut();
+        copy_bins(&mut frame.data, data);
+        frame.sample_rate = self.sample_rate;
+        frame.window_size = self.fft_size;
+        frame.floor_db = SPECTRUM_FLOOR_DB;
+        frame.frame_index = self.frame_index;
+        copy_bins(&mut frame.envelope_min, &self.envelope_min);
+        copy_bins(&mut frame.envelope_max, &self.envelope_max);
+        self.spectrum_producer.publish();
+    }
+
+    /// Pause or resume analysis
+    /// While paused `process` is never called, so on resume the ring buffer is
+    /// re-seeded (and any Welch block dropped) to avoid mixing pre-pause audio into the
+    /// first new frames
+    pub fn set_paused(&mut self, paused: bool) {
+        if self.paused && !paused {
+            self.reseed_ring_buffer();
+            self.welch_power_sum.fill(0.0);
+            self.welch_frame_count = 0;
+        }
+        self.paused = paused;
+    }
+
+    /// Set how often finished frames are published to the UI
+    pub fn set_publish_rate(&mut self, publish_rate: PublishRate) {
+        self.publish_rate = publish_rate;
+    }
+
+    /// Set the overlap between FFT frames
+    /// An overlap the window isn't COLA-compliant for is ignored and the current one kept,
+    /// so frame energy is never weighted unevenly
+    pub fn set_overlap(&mut self, overlap: FftOverlap) {
+        if overlap == self.overlap {
+            return;
+        }
+        if is_cola(&self.window_coefficients, overlap.hop_size(self.fft_size)) {
+            self.overlap = overlap;
+        }
+    }
+
+    /// Set the number of samples in each FFT frame
+    /// Re-plans the FFT and resizes the analysis buffers, so this allocates: call it from
+    /// `initialize` or another non-realtime context. The ring buffer is re-seeded and any
+    /// Welch block dropped, so no frame mixes samples analysed at both sizes.
+    ///
+    /// # Errors
+    /// `InvalidBuffer` if `fft_size` isn't a power of two from `MIN_FFT_SIZE` to
+    /// `MAX_FFT_SIZE`; the current size is kept
+    pub fn set_fft_size(&mut self, fft_size: usize) -> SpectrumResult<()> {
+        if !fft_size.is_power_of_two() || !(MIN_FFT_SIZE..=MAX_FFT_SIZE_USIZE).contains(&fft_size) {
+            return Err(SpectrumError::InvalidBuffer {
+                reason: format!(
+                    "FFT size {fft_size} is not a power of two from {MIN_FFT_SIZE} to \
+                     {MAX_FFT_SIZE_USIZE}"
+                ),
+            });
+        }
+        if fft_size == self.fft_size {
+            return Ok(());
+        }
+
+        let bin_count = fft_size / 2 + 1;
+        self.fft_size = fft_size;
+        self.fft_processor = RealFftPlanner::<f32>::new().plan_fft_forward(fft_size);
+        self.window_coefficients = self.window_type.generate(fft_size);
+        self.ring_buffer = vec![0.0; fft_size * RING_BUFFER_SIZE_MULTIPLIER];
+        self.time_domain_buffer = vec![0.0; fft_size];
+        self.frequency_domain_buffer = vec![Complex32::new(0.0, 0.0); bin_count];
+        self.welch_power_sum = vec![0.0; bin_count];
+        self.welch_frame_count = 0;
+        self.peak_candidates = Vec::with_capacity(bin_count);
+        self.reseed_ring_buffer();
+        Ok(())
+    }
+
+    /// Set the low/mid and mid/high band edges for the band level readouts
+    pub fn set_band_edges(&mut self, low_mid_hz: f32, mid_high_hz: f32) {
+        self.band_edges_hz = (low_mid_hz, mid_high_hz);
+    }
+
+    /// Set the frequency range measured for the band energy alarm
+    pub fn set_alarm_band(&mut self, low_hz: f32, high_hz: f32) {
+        self.alarm_band_hz = (low_hz.min(high_hz), low_hz.max(high_hz));
+    }
+
+    /// Enable or disable the long-term min/max envelope
+    /// Disabling drops the accumulated envelope so re-enabling starts fresh
+    pub fn set_envelope_enabled(&mut self, enabled: bool) {
+        if !enabled {
+            self.envelope_min.clear();
+            self.envelope_max.clear();
+        }
+        self.envelope_enabled = enabled;
+    }
+
+    /// Fold the current frame into the min/max envelope
+    /// Restarts from the current frame on a reset request or resolution change
+    fn update_envelope(&mut self) {
+        let reset_requested = self
+            .envelope_reset
+            .swap(false, std::sync::atomic::Ordering::Relaxed);
+        if reset_requested || self.envelope_min.len() != self.spectrum_result.len() {
+            copy_bins(&mut self.envelope_min, &self.spectrum_result);
+            copy_bins(&mut self.envelope_max, &self.spectrum_result);
+            return;
+        }
+
+        for ((min_db, max_db), &db) in self
+            .envelope_min
+            .iter_mut()
+            .zip(self.envelope_max.iter_mut())
+            .zip(&self.spectrum_result)
+        {
+            *min_db = min_db.min(db);
+            *max_db = max_db.max(db);
+        }
+    }
+
+    /// Set the left/right analysis trims in dB
+    /// Only changes what is analysed, never the audio passed through
+    pub fn set_channel_trims(&mut self, left_trim_db: f32, right_trim_db: f32) {
+        self.channel_trims = [
+            util::db_to_gain(left_trim_db),
+            util::db_to_gain(right_trim_db),
+        ];
+    }
+
+    /// Set the level convention for the displayed spectrum and peaks
+    /// Band levels are true RMS band power and are left unchanged
+    pub fn set_reference_normalization(&mut self, normalization: ReferenceNormalization) {
+        self.reference_normalization = normalization;
+    }
+
+    /// Hide or show the DC and Nyquist bins
+    pub fn set_hide_edge_bins(&mut self, hide: bool) {
+        self.hide_edge_bins = hide;
+    }
+
+    /// Set how many frames each Welch periodogram averages (1 disables averaging)
+    /// A changed count starts a fresh block so frames from both settings never mix
+    pub fn set_welch_averages(&mut self, averages: usize) {
+        let averages = averages.max(1);
+        if averages != self.welch_averages {
+            self.welch_averages = averages;
+            self.welch_power_sum.fill(0.0);
+            self.welch_frame_count = 0;
+        }
+    }
+
+    /// Set the per-frame slew limit in dB (0 disables it)
+    pub fn set_slew_limit(&mut self, slew_limit_db: f32) {
+        self.slew_limit_db = slew_limit_db;
+    }
+
+    /// Enable or disable the calibration self-test
+    /// When enabled a full-scale sine near 1 kHz is analysed instead of the input
+    pub fn set_calibration(&mut self, enabled: bool) {
+        if enabled != self.calibration_enabled {
+            self.reseed_ring_buffer();
+            self.calibration_phase = 0.0;
+            self.calibration_enabled = enabled;
+        }
+    }
+
+    /// Clear accumulated samples so the next FFT frame only contains fresh audio
+    fn reseed_ring_buffer(&mut self) {
+        self.ring_buffer.fill(0.0);
+        self.ring_buffer_pos = 0;
+        self.samples_since_fft = 0;
     }
 
     /// Get the count of FFT failures (for debugging)
@@ -184,6 +933,21 @@ impl SpectrumProducer {
             .load(std::sync::atomic::Ordering::Relaxed)
     }
 
+    /// Current analysis settings in one place, for logging alongside a measurement
+    #[allow(dead_code)] // Introspection API for hosts and test harnesses
+    pub fn config(&self) -> SpectrumConfig {
+        SpectrumConfig {
+            sample_rate: self.sample_rate,
+            window_size: self.fft_size,
+            window_type: self.window_type,
+            overlap: self.overlap,
+            bin_count: self.current_resolution.to_bin_count(),
+            speed: self.speed,
+            tilt_db_per_octave: self.tilt_db_per_octave,
+            reference_normalization: self.reference_normalization,
+        }
+    }
+
     /// Compute spectrum from audio buffer and send to UI thread
     /// Called from audio thread - must be real-time safe (no allocations)
     pub fn process(
@@ -194,11 +958,24 @@ impl SpectrumProducer {
         speed: SpectrumSpeed,
         resolution: ResolutionLevel,
     ) {
-        // Add incoming samples to ring buffer
-        self.add_samples_to_ring_buffer(buffer);
+        if self.paused {
+            return;
+        }
+
+        self.sample_rate = sample_rate;
+        self.speed = speed;
+        self.tilt_db_per_octave = tilt.to_db_per_octave();
+
+        // Add incoming samples (or the calibration tone) to ring buffer
+        if self.calibration_enabled {
+            self.add_calibration_tone_to_ring_buffer(buffer.samples(), sample_rate);
+        } else {
+            self.add_samples_to_ring_buffer(buffer);
+        }
 
         // Check if enough samples have been accumulated for next FFT
-        if self.samples_since_fft >= (MAX_FFT_SIZE_USIZE as f32 * FFT_OVERLAP_FACTOR) as usize {
+        let hop_size = self.overlap.hop_size(self.fft_size);
+        if self.samples_since_fft >= hop_size {
             self.samples_since_fft = 0;
 
             // Copy from ring buffer to FFT buffer
@@ -224,20 +1001,49 @@ impl SpectrumProducer {
             }
 
             // Convert complex FFT output to magnitude spectrum and sample to target resolution
-            self.compute_magnitude_spectrum(resolution);
+            // With Welch averaging, nothing more happens until a block is complete
+            if !self.compute_magnitude_spectrum(resolution, sample_rate) {
+                return;
+            }
 
             // Apply temporal envelope (Speed parameter - attack/release dynamics)
-            self.apply_temporal_envelope(sample_rate, speed);
+            // A Welch average is already equally weighted, so it bypasses the envelope
+            if self.welch_averages > 1 {
+                self.previous_spectrum
+                    .copy_from_slice(&self.spectrum_result);
+            } else {
+                self.apply_temporal_envelope(sample_rate, speed);
+            }
 
             // Apply tilt compensation as visual adjustment
-            self.apply_tilt_compensation(sample_rate, tilt);
+            // Skipped in calibration mode so the measured level is exact
+            if !self.calibration_enabled {
+                self.apply_tilt_compensation(sample_rate, tilt);
+            }
+
+            // Track the long-term envelope on every frame, not just published ones
+            if self.envelope_enabled {
+                self.update_envelope();
+            }
 
-            // Send result to UI thread (lock-free)
-            self.spectrum_producer.write(self.spectrum_result.clone());
+            // Send result to UI thread (lock-free), decimated to the publish rate
+            self.frames_since_publish += 1;
+            let frame_interval = self.publish_rate.frame_interval(sample_rate, hop_size);
+            if self.frames_since_publish >= frame_interval {
+                self.frames_since_publish = 0;
+                // Taken out for the call so it can be borrowed alongside `self`
+                let spectrum_result = std::mem::take(&mut self.spectrum_result);
+                self.publish(&spectrum_result);
+                self.spectrum_result = spectrum_result;
+            }
         }
     }
 
     /// Add samples from audio buffer to ring buffer
+    ///
+    /// Hosts may call `process` with tiny buffers many times per FFT frame, so the mono
+    /// mix works a channel at a time over contiguous ring runs: the wrap check happens
+    /// once per run instead of per sample, and the channel average is a single multiply.
     fn add_samples_to_ring_buffer(&mut self, buffer: &Buffer) {
         let num_channels = buffer.channels();
         let num_samples = buffer.samples();
@@ -247,22 +1053,55 @@ impl SpectrumProducer {
         }
 
         let channel_slices = buffer.as_slice_immutable();
+        let channel_gain = 1.0 / num_channels as f32;
+        let ring_len = self.ring_buffer.len();
+
+        // Trim L/R (channels beyond the first two are untrimmed), then average to mono
+        let trims = self.channel_trims;
+        let gain_for =
+            |channel_idx: usize| channel_gain * trims.get(channel_idx).copied().unwrap_or(1.0);
+
+        let mut sample_idx = 0;
+        while sample_idx < num_samples {
+            // Longest run that fits before the ring buffer wraps
+            let run_len = (num_samples - sample_idx).min(ring_len - self.ring_buffer_pos);
+            let source_range = sample_idx..sample_idx + run_len;
+            let ring_run =
+                &mut self.ring_buffer[self.ring_buffer_pos..self.ring_buffer_pos + run_len];
+
+            // First channel initialises the run, remaining channels accumulate into it
+            let first_gain = gain_for(0);
+            ring_run
+                .iter_mut()
+                .zip(&channel_slices[0][source_range.clone()])
+                .for_each(|(mixed, &sample)| *mixed = sample * first_gain);
+            for (channel_idx, channel) in channel_slices.iter().enumerate().skip(1) {
+                let gain = gain_for(channel_idx);
+                ring_run
+                    .iter_mut()
+                    .zip(&channel[source_range.clone()])
+                    .for_each(|(mixed, &sample)| *mixed += sample * gain);
+            }
+
+            self.ring_buffer_pos = (self.ring_buffer_pos + run_len) % ring_len;
+            sample_idx += run_len;
+        }
+
+        self.samples_since_fft += num_samples;
+    }
 
-        (0..num_samples).for_each(|sample_idx| {
-            // Sum all channels for mono mix using iterator
-            let mono_sample = channel_slices
-                .iter()
-                .map(|channel| channel[sample_idx])
-                .sum::<f32>()
-                / num_channels as f32;
+    /// Add a full-scale calibration sine to the ring buffer in place of the input
+    fn add_calibration_tone_to_ring_buffer(&mut self, num_samples: usize, sample_rate: f32) {
+        let phase_increment = calibration_frequency_hz(sample_rate, self.fft_size) / sample_rate;
 
-            // Add to ring buffer
-            self.ring_buffer[self.ring_buffer_pos] = mono_sample;
+        for _ in 0..num_samples {
+            self.ring_buffer[self.ring_buffer_pos] =
+                (2.0 * std::f32::consts::PI * self.calibration_phase).sin();
+            self.calibration_phase = (self.calibration_phase + phase_increment).fract();
 
-            // Advance ring buffer position (wrap around)
             self.ring_buffer_pos = (self.ring_buffer_pos + 1) % self.ring_buffer.len();
             self.samples_since_fft += 1;
-        });
+        }
     }
 
     /// Copy most recent samples from ring buffer to FFT buffer
@@ -270,10 +1109,10 @@ impl SpectrumProducer {
         let ring_len = self.ring_buffer.len();
 
         // Start position: current pos minus window size
-        let start_pos = if self.ring_buffer_pos >= MAX_FFT_SIZE_USIZE {
-            self.ring_buffer_pos - MAX_FFT_SIZE_USIZE
+        let start_pos = if self.ring_buffer_pos >= self.fft_size {
+            self.ring_buffer_pos - self.fft_size
         } else {
-            ring_len - (MAX_FFT_SIZE_USIZE - self.ring_buffer_pos)
+            ring_len - (self.fft_size - self.ring_buffer_pos)
         };
 
         // Copy samples (handle wrap-around) using iterators
@@ -288,14 +1127,8 @@ impl SpectrumProducer {
 
     /// Apply windowing in-place to time domain buffer
     fn apply_window(&mut self) {
-        // Apply Hann window to reduce spectral leakage
-        for (sample, &coeff) in self
-            .time_domain_buffer
-            .iter_mut()
-            .zip(self.window_coefficients.iter())
-        {
-            *sample *= coeff;
-        }
+        // Apply the window to reduce spectral leakage
+        apply_window(&mut self.time_domain_buffer, &self.window_coefficients);
     }
 
     /// Resize buffers when resolution changes
@@ -313,22 +1146,108 @@ impl SpectrumProducer {
     }
 
     /// Convert complex FFT output to magnitude spectrum and sample to target resolution
-    fn compute_magnitude_spectrum(&mut self, resolution: ResolutionLevel) {
+    ///
+    /// # Returns
+    /// False while a Welch block is still accumulating, when there is no new spectrum
+    fn compute_magnitude_spectrum(
+        &mut self,
+        resolution: ResolutionLevel,
+        sample_rate: f32,
+    ) -> bool {
         // Get full magnitude spectrum from FFT
-        let full_magnitude_spectrum =
-            compute_magnitude_spectrum(&self.frequency_domain_buffer, MAX_FFT_SIZE_USIZE);
+        let mut full_magnitude_spectrum =
+            compute_magnitude_spectrum(&self.frequency_domain_buffer, self.fft_size);
+        if self.hide_edge_bins {
+            hide_edge_bins(&mut full_magnitude_spectrum);
+        }
+        if self.welch_averages > 1 && !self.accumulate_welch(&mut full_magnitude_spectrum) {
+            return false;
+        }
+        let reference_offset_db = self.reference_normalization.offset_db();
+
+        // Publish the peak before any decimation or smoothing blurs it
+        let bin_width_hz = sample_rate / self.fft_size as f32;
+        let peak = find_interpolated_peak(&full_magnitude_spectrum, bin_width_hz);
+        self.peak
+            .frequency_hz
+            .store(peak.frequency_hz, std::sync::atomic::Ordering::Relaxed);
+        self.peak.level_db.store(
+            peak.level_db + reference_offset_db,
+            std::sync::atomic::Ordering::Relaxed,
+        );
+
+        // Loudest few local maxima for the peak marker overlay
+        find_top_peaks(
+            &full_magnitude_spectrum,
+            bin_width_hz,
+            MAX_PEAK_MARKERS,
+            &mut self.peak_candidates,
+        );
+        for (rank, shared) in self.top_peaks.iter().enumerate() {
+            let peak = self
+                .peak_candidates
+                .get(rank)
+                .copied()
+                .unwrap_or(SpectrumPeak {
+                    frequency_hz: 0.0,
+                    level_db: SPECTRUM_FLOOR_DB,
+                });
+            shared
+                .frequency_hz
+                .store(peak.frequency_hz, std::sync::atomic::Ordering::Relaxed);
+            shared.level_db.store(
+                peak.level_db + reference_offset_db,
+                std::sync::atomic::Ordering::Relaxed,
+            );
+        }
+
+        // Band levels come from the same untilted, undecimated spectrum
+        let (low_mid_hz, mid_high_hz) = self.band_edges_hz;
+        let bands = compute_band_levels(
+            &full_magnitude_spectrum,
+            bin_width_hz,
+            low_mid_hz,
+            mid_high_hz,
+        );
+        self.band_levels
+            .low_db
+            .store(bands.low_db, std::sync::atomic::Ordering::Relaxed);
+        self.band_levels
+            .mid_db
+            .store(bands.mid_db, std::sync::atomic::Ordering::Relaxed);
+        self.band_levels
+            .high_db
+            .store(bands.high_db, std::sync::atomic::Ordering::Relaxed);
+
+        let (alarm_low_hz, alarm_high_hz) = self.alarm_band_hz;
+        let alarm_db = compute_band_level(
+            &full_magnitude_spectrum,
+            bin_width_hz,
+            alarm_low_hz,
+            alarm_high_hz,
+        );
+        self.band_levels
+            .alarm_db
+            .store(alarm_db, std::sync::atomic::Ordering::Relaxed);
+
+        let slope = compute_spectral_slope(&full_magnitude_spectrum, bin_width_hz);
+        self.band_levels.slope_db_per_octave.store(
+            slope.unwrap_or(f32::NAN),
+            std::sync::atomic::Ordering::Relaxed,
+        );
 
         // Sample to target resolution using interpolation for better quality
+        let source_bin_count = full_magnitude_spectrum.len();
         let target_bin_count = resolution.to_bin_count();
         for i in 0..target_bin_count {
             // Map target bin to source bin with fractional indexing
             let source_pos =
-                (i as f32 * (MAX_SPECTRUM_BINS - 1) as f32) / (target_bin_count - 1) as f32;
+                (i as f32 * (source_bin_count - 1) as f32) / (target_bin_count - 1) as f32;
             let source_idx = source_pos.floor() as usize;
             let fraction = source_pos.fract();
 
             // Linear interpolation between adjacent bins
-            let value = if source_idx + 1 < MAX_SPECTRUM_BINS {
+            let value = if source_idx + 1 < source_bin_count {
                 let current = full_magnitude_spectrum[source_idx];
                 let next = full_magnitude_spectrum[source_idx + 1];
                 current + (next - current) * fraction
@@ -336,12 +1255,43 @@ impl SpectrumProducer {
                 full_magnitude_spectrum[source_idx]
             };
 
-            self.spectrum_result[i] = value;
+            self.spectrum_result[i] = value + reference_offset_db;
+        }
+
+        true
+    }
+
+    /// Add a frame's power to the Welch block, replacing it with the block average
+    /// once `welch_averages` frames are in
+    ///
+    /// Unlike the exponential envelope every frame carries equal weight, and power is
+    /// averaged linearly, so the result is an unbiased low-variance noise spectrum.
+    ///
+    /// # Returns
+    /// True when `magnitudes_db` holds a completed average
+    fn accumulate_welch(&mut self, magnitudes_db: &mut [f32]) -> bool {
+        for (sum, &db) in self.welch_power_sum.iter_mut().zip(magnitudes_db.iter()) {
+            *sum += 10.0_f32.powf(db / 10.0);
+        }
+        self.welch_frame_count += 1;
+        if self.welch_frame_count < self.welch_averages {
+            return false;
+        }
+
+        let frame_count = self.welch_frame_count as f32;
+        for (db, sum) in magnitudes_db
+            .iter_mut()
+            .zip(self.welch_power_sum.iter_mut())
+        {
+            *db = power_to_db(*sum / frame_count);
+            *sum = 0.0;
         }
+        self.welch_frame_count = 0;
+        true
     }
 
-    /// Apply tilt compensation as final visual adjustment
-    /// Tilts the spectrum around 1kHz for perceptually flat response
+    /// Apply pink compensation as final visual adjustment
+    /// Tilts the spectrum around 1kHz; see [`TiltLevel`] for the settings
     fn apply_tilt_compensation(&mut self, sample_rate: f32, tilt: TiltLevel) {
         let tilt_db_per_oct = tilt.to_db_per_octave();
 
@@ -350,15 +1300,16 @@ impl SpectrumProducer {
             return;
         }
 
+        let source_bin_count = self.fft_size / 2 + 1;
         let target_bin_count = self.spectrum_result.len();
         for (bin_idx, db_value) in self.spectrum_result.iter_mut().enumerate() {
             // Only apply tilt to signals above noise floor
             if *db_value > SPECTRUM_FLOOR_DB + 10.0 {
                 // Calculate frequency for this bin based on actual resolution
                 // Map from decimated bin index back to frequency
-                let source_pos = (bin_idx as f32 * (MAX_SPECTRUM_BINS - 1) as f32)
+                let source_pos = (bin_idx as f32 * (source_bin_count - 1) as f32)
                     / (target_bin_count - 1) as f32;
-                let freq_hz = (source_pos * sample_rate) / MAX_FFT_SIZE_USIZE as f32;
+                let freq_hz = (source_pos * sample_rate) / self.fft_size as f32;
 
                 // Apply tilt compensation
                 *db_value = apply_tilt_compensation(*db_value, freq_hz, tilt_db_per_oct);
@@ -373,10 +1324,22 @@ impl SpectrumProducer {
             &self.previous_spectrum,
             speed,
             sample_rate,
-            MAX_FFT_SIZE_USIZE,
+            self.overlap.hop_size(self.fft_size),
         );
         self.spectrum_result.copy_from_slice(&envelope_spectrum);
-        self.previous_spectrum.copy_from_slice(&updated_previous);
+
+        // Rate-limit on top of the envelope; the limited curve becomes the new reference
+        if self.slew_limit_db > 0.0 {
+            apply_slew_limit(
+                &mut self.spectrum_result,
+                &self.previous_spectrum,
+                self.slew_limit_db,
+            );
+            self.previous_spectrum
+                .copy_from_slice(&self.spectrum_result);
+        } else {
+            self.previous_spectrum.copy_from_slice(&updated_previous);
+        }
     }
 }
 
@@ -415,52 +1378,363 @@ impl SpectrumProducer {
 /// - "Spectral Audio Signal Processing" by Julius O. Smith III
 /// - AES17-2015 "AES standard method for digital audio engineering"
 /// - https://ccrma.stanford.edu/~jos/sasp/Spectrum_Analysis_Windows.html
-pub fn compute_magnitude_spectrum(frequency_bins: &[Complex32], window_size: usize) -> Vec<f32> {
-    let window_coherent_gain = 0.5; // Hann window ACF (amplitude correction factor)
-    let spectrum: Vec<f32> = frequency_bins
-        .iter()
-        .enumerate()
-        .map(|(bin_idx, &complex_bin)| {
-            // Calculate magnitude (not power)
-            let magnitude = complex_bin.norm();
-
-            // Correct scaling for magnitude spectrum with window compensation
-            let nyquist_bin = window_size / 2;
-            let scaling = if bin_idx == 0 || bin_idx == nyquist_bin {
-                // DC and Nyquist: already single-sided, no factor of 2, no RMS conversion
-                1.0 / (window_size as f32 * window_coherent_gain)
-            } else {
-                // AC bins: factor of 2 for single-sided, convert peak to RMS, compensate for window
-                (2.0 / (2.0_f32).sqrt()) / (window_size as f32 * window_coherent_gain)
-            };
+pub fn compute_magnitude_spectrum<T: Sample>(
+    frequency_bins: &[Complex<T>],
+    window_size: usize,
+) -> Vec<T> {
+    let mut spectrum = vec![T::zero(); frequency_bins.len()];
+    compute_magnitude_spectrum_into(frequency_bins, window_size, &mut spectrum);
+    spectrum
+}
 
-            let normalized_magnitude = magnitude * scaling;
+/// `compute_magnitude_spectrum` writing into a caller-owned buffer, one dB value per bin
+///
+/// Shared by the dynamic path and the fixed-size `FixedSpectrum`, which can't allocate.
+/// Only `output.len().min(frequency_bins.len())` bins are written.
+pub fn compute_magnitude_spectrum_into<T: Sample>(
+    frequency_bins: &[Complex<T>],
+    window_size: usize,
+    output: &mut [T],
+) {
+    let window_coherent_gain = T::constant(0.5); // Hann window ACF (amplitude correction factor)
+    let window_size_t = T::constant(window_size as f64);
+    let floor_db = T::constant(SPECTRUM_FLOOR_DB as f64);
+    let nyquist_bin = window_size / 2;
+    for (bin_idx, (&complex_bin, db_out)) in frequency_bins.iter().zip(output).enumerate() {
+        // Calculate magnitude (not power)
+        let magnitude = complex_bin.norm();
+
+        // Correct scaling for magnitude spectrum with window compensation
+        let scaling = if bin_idx == 0 || bin_idx == nyquist_bin {
+            // DC and Nyquist: already single-sided, no factor of 2, no RMS conversion
+            T::one() / (window_size_t * window_coherent_gain)
+        } else {
+            // AC bins: factor of 2 for single-sided, convert peak to RMS, compensate for window
+            T::SQRT_2() / (window_size_t * window_coherent_gain)
+        };
 
-            // Convert to dBFS using 20*log10 for magnitude (not power)
-            let db_value = if normalized_magnitude > MIN_AMPLITUDE_THRESHOLD {
-                20.0 * normalized_magnitude.log10()
-            } else {
-                SPECTRUM_FLOOR_DB
-            };
+        let normalized_magnitude = magnitude * scaling;
 
-            // Apply floor clamping
-            db_value.max(SPECTRUM_FLOOR_DB)
-        })
-        .collect();
+        // Convert to dBFS using 20*log10 for magnitude (not power)
+        let db_value = if normalized_magnitude > T::constant(MIN_AMPLITUDE_THRESHOLD as f64) {
+            T::constant(20.0) * normalized_magnitude.log10()
+        } else {
+            floor_db
+        };
 
-    spectrum
+        // Apply floor clamping
+        *db_out = db_value.max(floor_db);
+    }
 }
 
-/// Applies frequency-dependent tilt compensation for visual adjustment
+/// Multiply each sample by its window coefficient, in place
+pub fn apply_window<T: Sample>(samples: &mut [T], window: &[T]) {
+    for (sample, &coefficient) in samples.iter_mut().zip(window) {
+        *sample = *sample * coefficient;
+    }
+}
+
+/// Sums bin power into low, mid and high band RMS levels
 ///
-/// Tilts the spectrum display around 1kHz to provide perceptually flat response.
-/// This is a visual-only adjustment applied as the final step in the processing chain.
-/// Common values: 3dB/oct (pink noise flat), 4.5dB/oct (natural perception)
+/// Each bin's dB value is the RMS level of a sinusoid at that bin, so adding the
+/// powers and dividing by the Hann ENBW gives the band's total RMS for broadband
+/// signals. The DC bin is skipped.
 ///
 /// # Parameters
-/// * `magnitude_db` - Original magnitude in dB
-/// * `freq_hz` - Frequency of this bin in Hz
-/// * `tilt_db_per_oct` - Tilt amount in dB per octave (typically 3-6)
+/// * `magnitudes_db` - Full-resolution magnitude spectrum in dB (DC at index 0)
+/// * `bin_width_hz` - Frequency spacing between bins (sample_rate / fft_size)
+/// * `low_mid_hz` - Edge between the low and mid band
+/// * `mid_high_hz` - Edge between the mid and high band
+pub fn compute_band_levels(
+    magnitudes_db: &[f32],
+    bin_width_hz: f32,
+    low_mid_hz: f32,
+    mid_high_hz: f32,
+) -> BandLevels {
+    let nyquist_hz = magnitudes_db.len() as f32 * bin_width_hz;
+    BandLevels {
+        low_db: compute_band_level(magnitudes_db, bin_width_hz, 0.0, low_mid_hz),
+        mid_db: compute_band_level(magnitudes_db, bin_width_hz, low_mid_hz, mid_high_hz),
+        high_db: compute_band_level(magnitudes_db, bin_width_hz, mid_high_hz, nyquist_hz),
+    }
+}
+
+/// RMS level of the bins in `low_hz..high_hz`, measured like [`compute_band_levels`]
+///
+/// # Parameters
+/// * `magnitudes_db` - Full-resolution magnitude spectrum in dB (DC at index 0)
+/// * `bin_width_hz` - Frequency spacing between bins (sample_rate / fft_size)
+/// * `low_hz` - Lower band edge (inclusive)
+/// * `high_hz` - Upper band edge (exclusive)
+pub fn compute_band_level(
+    magnitudes_db: &[f32],
+    bin_width_hz: f32,
+    low_hz: f32,
+    high_hz: f32,
+) -> f32 {
+    // Bins whose centre lies in `low_hz..high_hz`, skipping DC
+    let first_bin = ((low_hz / bin_width_hz).ceil() as usize).clamp(1, magnitudes_db.len());
+    let end_bin = ((high_hz / bin_width_hz).ceil() as usize).clamp(first_bin, magnitudes_db.len());
+
+    let summed_db = sum_bins_power_to_db(&magnitudes_db[first_bin..end_bin]);
+    (summed_db - 10.0 * HANN_ENBW_BINS.log10()).max(SPECTRUM_FLOOR_DB)
+}
+
+/// `bin_count` bins of silence with room for a full-resolution spectrum
+fn preallocated_bins(bin_count: usize) -> SpectrumData {
+    let mut bins = SpectrumData::with_capacity(MAX_SPECTRUM_BINS);
+    bins.resize(bin_count, SPECTRUM_FLOOR_DB);
+    bins
+}
+
+/// Overwrite `destination` with `source`, resizing it within its existing allocation
+/// Every published and envelope buffer reserves `MAX_SPECTRUM_BINS` up front, so this
+/// never allocates for a spectrum the producer computes
+fn copy_bins(destination: &mut SpectrumData, source: &[f32]) {
+    destination.resize(source.len(), SPECTRUM_FLOOR_DB);
+    destination.copy_from_slice(source);
+}
+
+/// Total level of a group of bins, summing their linear power
+///
+/// Averaging the dB values instead reads low whenever the bins differ, and misses the
+/// energy gained by combining bins: two equal bins sum to +3 dB, not +0 dB. Any band
+/// grouping or band-energy readout should go through here.
+///
+/// # Returns
+/// The summed level in dB, `SPECTRUM_FLOOR_DB` for an empty or silent group
+pub fn sum_bins_power_to_db(bins_db: &[f32]) -> f32 {
+    let power: f32 = bins_db.iter().map(|&db| 10.0_f32.powf(db / 10.0)).sum();
+    power_to_db(power)
+}
+
+/// Least-squares slope of the spectrum in dB/octave
+///
+/// Averages bin power within each octave band, so the result is the slope of the
+/// trace as drawn: pink noise reads about -3 dB/oct, white noise about 0 dB/oct.
+/// Bands below `SLOPE_MIN_BAND_DB` or past Nyquist are left out of the fit.
+///
+/// # Parameters
+/// * `magnitudes_db` - Full-resolution magnitude spectrum in dB (DC at index 0)
+/// * `bin_width_hz` - Frequency spacing between bins (sample_rate / fft_size)
+///
+/// # Returns
+/// The slope, or None if fewer than `SLOPE_MIN_BANDS` bands carry signal
+pub fn compute_spectral_slope(magnitudes_db: &[f32], bin_width_hz: f32) -> Option<f32> {
+    let mut points = [(0.0_f32, 0.0_f32); SLOPE_BAND_COUNT];
+    let mut count = 0;
+
+    for octave in SLOPE_LOWEST_OCTAVE..=SLOPE_HIGHEST_OCTAVE {
+        let center_hz = TILT_REFERENCE_FREQ_HZ * 2.0_f32.powi(octave);
+        let low_hz = center_hz / std::f32::consts::SQRT_2;
+        let high_hz = center_hz * std::f32::consts::SQRT_2;
+
+        let (power, bins) = magnitudes_db
+            .iter()
+            .enumerate()
+            .skip(1)
+            .filter(|&(bin, _)| (low_hz..high_hz).contains(&(bin as f32 * bin_width_hz)))
+            .fold((0.0_f32, 0_usize), |(power, bins), (_, &db)| {
+                (power + 10.0_f32.powf(db / 10.0), bins + 1)
+            });
+        if bins == 0 {
+            continue;
+        }
+
+        let level_db = power_to_db(power / (bins as f32 * HANN_ENBW_BINS));
+        if level_db > SLOPE_MIN_BAND_DB {
+            points[count] = (octave as f32, level_db);
+            count += 1;
+        }
+    }
+
+    if count < SLOPE_MIN_BANDS {
+        return None;
+    }
+
+    let points = &points[..count];
+    let n = count as f32;
+    let mean_x = points.iter().map(|&(x, _)| x).sum::<f32>() / n;
+    let mean_y = points.iter().map(|&(_, y)| y).sum::<f32>() / n;
+    let (covariance, variance) = points.iter().fold((0.0, 0.0), |(cov, var), &(x, y)| {
+        (
+            cov + (x - mean_x) * (y - mean_y),
+            var + (x - mean_x) * (x - mean_x),
+        )
+    });
+
+    Some(covariance / variance)
+}
+
+/// Converts linear power to dB, clamped to the spectrum floor
+fn power_to_db(power: f32) -> f32 {
+    if power > MIN_AMPLITUDE_THRESHOLD {
+        (10.0 * power.log10()).max(SPECTRUM_FLOOR_DB)
+    } else {
+        SPECTRUM_FLOOR_DB
+    }
+}
+
+/// Finds the strongest spectral peak and refines it with parabolic interpolation
+///
+/// A tone between two bins reads low (Hann scalloping loss, up to ~1.4 dB) and its
+/// frequency snaps to the nearest bin. Fitting a parabola through the peak bin and its
+/// neighbours on the log-magnitude values recovers the true frequency; the level is then
+/// corrected by the Hann window's known response at that offset. (The parabola's own
+/// vertex overshoots by up to ~0.3 dB for a Hann window, so it isn't used for level.)
+///
+/// # Parameters
+/// * `magnitudes_db` - Full-resolution magnitude spectrum in dB (DC at index 0)
+/// * `bin_width_hz` - Frequency spacing between bins (sample_rate / fft_size)
+///
+/// # Mathematical Background
+/// For neighbouring levels α, β, γ around the peak bin k:
+/// - Offset: p = 0.5 * (α - γ) / (α - 2β + γ), in the range [-0.5, 0.5]
+/// - Frequency: (k + p) * bin_width
+/// - Level: β - 20*log10(W(p)), where W(p) = sinc(p) / (1 - p²) is the Hann window's
+///   normalised response p bins off-centre
+///
+/// # References
+/// - "Spectral Audio Signal Processing" by Julius O. Smith III, Quadratic Interpolation
+pub fn find_interpolated_peak(magnitudes_db: &[f32], bin_width_hz: f32) -> SpectrumPeak {
+    // Skip the DC bin so an offset never masks the real peak
+    let peak_bin = magnitudes_db.iter().enumerate().skip(1).fold(
+        None,
+        |best: Option<(usize, f32)>, (bin, &db)| match best {
+            Some((_, best_db)) if best_db >= db => best,
+            _ => Some((bin, db)),
+        },
+    );
+
+    let Some((bin, level_db)) = peak_bin else {
+        return SpectrumPeak {
+            frequency_hz: 0.0,
+            level_db: SPECTRUM_FLOOR_DB,
+        };
+    };
+
+    interpolate_peak(magnitudes_db, bin, level_db, bin_width_hz)
+}
+
+/// Replace the DC and Nyquist bins with their inner neighbours
+///
+/// Both are single-sided, so DC offset or a signal near Nyquist shows up as a lone
+/// spike at the very edge of the display. Copying the neighbour keeps the curve
+/// continuous instead of dropping it to the floor.
+pub fn hide_edge_bins(magnitudes_db: &mut [f32]) {
+    let len = magnitudes_db.len();
+    if len < 3 {
+        return;
+    }
+    magnitudes_db[0] = magnitudes_db[1];
+    magnitudes_db[len - 1] = magnitudes_db[len - 2];
+}
+
+/// Refine a peak at `bin` with parabolic interpolation over its neighbours
+fn interpolate_peak(
+    magnitudes_db: &[f32],
+    bin: usize,
+    level_db: f32,
+    bin_width_hz: f32,
+) -> SpectrumPeak {
+    // Edge bins have no neighbour on one side - report them as-is
+    if bin == 0 || bin + 1 >= magnitudes_db.len() {
+        return SpectrumPeak {
+            frequency_hz: bin as f32 * bin_width_hz,
+            level_db,
+        };
+    }
+
+    let alpha = magnitudes_db[bin - 1];
+    let beta = magnitudes_db[bin];
+    let gamma = magnitudes_db[bin + 1];
+    let denominator = alpha - 2.0 * beta + gamma;
+
+    let offset = if denominator.abs() > f32::EPSILON {
+        (0.5 * (alpha - gamma) / denominator).clamp(-0.5, 0.5)
+    } else {
+        0.0
+    };
+
+    SpectrumPeak {
+        frequency_hz: (bin as f32 + offset) * bin_width_hz,
+        level_db: beta - hann_response_db(offset),
+    }
+}
+
+/// Level of the Hann window's main lobe `offset` bins from its centre, relative to the
+/// centre (dB, 0 or below)
+/// This is the scalloping loss of a tone that far from the nearest bin
+fn hann_response_db(offset: f32) -> f32 {
+    if offset.abs() < f32::EPSILON {
+        return 0.0;
+    }
+    let x = std::f32::consts::PI * offset;
+    let response = x.sin() / x / (1.0 - offset * offset);
+    20.0 * response.log10()
+}
+
+/// Find the `count` loudest local maxima, loudest first, written into `output`
+///
+/// A local maximum is a bin above its lower neighbour and not below its upper one,
+/// so a flat-topped peak is reported once. The DC bin is skipped.
+pub fn find_top_peaks(
+    magnitudes_db: &[f32],
+    bin_width_hz: f32,
+    count: usize,
+    output: &mut Vec<SpectrumPeak>,
+) {
+    output.clear();
+    if magnitudes_db.len() < 3 || count == 0 {
+        return;
+    }
+
+    for bin in 1..magnitudes_db.len() - 1 {
+        let level_db = magnitudes_db[bin];
+        if level_db > SPECTRUM_FLOOR_DB
+            && level_db > magnitudes_db[bin - 1]
+            && level_db >= magnitudes_db[bin + 1]
+        {
+            output.push(interpolate_peak(magnitudes_db, bin, level_db, bin_width_hz));
+        }
+    }
+
+    let loudest_first = |a: &SpectrumPeak, b: &SpectrumPeak| b.level_db.total_cmp(&a.level_db);
+    if output.len() > count {
+        output.select_nth_unstable_by(count - 1, loudest_first);
+        output.truncate(count);
+    }
+    output.sort_unstable_by(loudest_first);
+}
+
+/// Calibration reading in dBFS for a native (sine RMS) peak level
+///
+/// Magnitudes are reported as RMS, so a full-scale sine natively reads -3.01 dB. The
+/// self-test is specified against 0 dBFS, so the sine's peak-to-RMS ratio is added back
+/// whatever level convention the display uses.
+pub fn calibration_level_db(native_level_db: f32) -> f32 {
+    native_level_db + ReferenceNormalization::SinePeak.offset_db()
+}
+
+/// Frequency of the calibration tone, snapped to the `fft_size`-point FFT bin nearest 1 kHz
+///
+/// A bin-centred tone avoids the window's scalloping loss, so the measured level
+/// checks the scaling chain itself rather than where the tone falls between bins.
+pub fn calibration_frequency_hz(sample_rate: f32, fft_size: usize) -> f32 {
+    let bin_width_hz = sample_rate / fft_size as f32;
+    (CALIBRATION_TONE_HZ / bin_width_hz).round() * bin_width_hz
+}
+
+/// Applies frequency-dependent tilt compensation for visual adjustment
+///
+/// Tilts the spectrum display around 1kHz to provide perceptually flat response.
+/// This is a visual-only adjustment applied as the final step in the processing chain.
+/// Common values: 3dB/oct (pink noise flat), 4.5dB/oct (natural perception)
+///
+/// # Parameters
+/// * `magnitude_db` - Original magnitude in dB
+/// * `freq_hz` - Frequency of this bin in Hz
+/// * `tilt_db_per_oct` - Tilt amount in dB per octave (typically 3-6)
 ///
 /// # Returns
 /// Magnitude with tilt compensation applied
@@ -468,21 +1742,34 @@ pub fn compute_magnitude_spectrum(frequency_bins: &[Complex32], window_size: usi
 /// # Mathematical Background
 /// Octaves from reference: log2(freq/ref_freq)
 /// Tilt boost: tilt_per_octave * octaves_from_reference
-fn apply_tilt_compensation(magnitude_db: f32, freq_hz: f32, tilt_db_per_oct: f32) -> f32 {
+pub fn apply_tilt_compensation<T: Sample>(magnitude_db: T, freq_hz: T, tilt_db_per_oct: T) -> T {
     // Avoid log(0) for DC bin
-    if freq_hz < MIN_FREQ_THRESHOLD {
+    if freq_hz < T::constant(MIN_FREQ_THRESHOLD as f64) {
         return magnitude_db;
     }
 
     // Calculate octaves from reference frequency
     // log2(2000/1000) = 1 octave up
     // log2(500/1000) = -1 octave down
-    let octaves_from_reference = libm::log2f(freq_hz / TILT_REFERENCE_FREQ_HZ);
+    let octaves_from_reference = (freq_hz / T::constant(TILT_REFERENCE_FREQ_HZ as f64)).log2();
 
     // Apply tilt: positive above 1kHz, negative below
     magnitude_db + (tilt_db_per_oct * octaves_from_reference)
 }
 
+/// Clamp each bin to within `max_step_db` of its previous value
+///
+/// Unlike the attack/release envelope this is a rate limit: a noisy bin can only
+/// wobble by the step per frame, while a sustained change still arrives in full
+/// after a few frames.
+pub fn apply_slew_limit<T: Sample>(spectrum: &mut [T], previous_spectrum: &[T], max_step_db: T) {
+    for (current_db, &previous_db) in spectrum.iter_mut().zip(previous_spectrum) {
+        *current_db = current_db
+            .max(previous_db - max_step_db)
+            .min(previous_db + max_step_db);
+    }
+}
+
 /// Apply temporal envelope with attack/release dynamics (Speed parameter)
 ///
 /// Implements fast attack and slow release for musical response:
@@ -494,32 +1781,31 @@ fn apply_tilt_compensation(magnitude_db: f32, freq_hz: f32, tilt_db_per_oct: f32
 /// * `previous_spectrum` - Spectrum from previous frame with temporal envelope applied
 /// * `speed` - Controls response time for decay characteristics
 /// * `sample_rate` - Sample rate for timing calculations
-/// * `fft_size` - FFT size for calculating frame rate
+/// * `hop_size` - Samples between FFT frames, for calculating frame rate
 ///
 /// # Returns
 /// Tuple of (envelope_applied_spectrum, updated_previous) for next iteration
-pub fn apply_temporal_envelope_sized(
-    current_spectrum: &[f32],
-    previous_spectrum: &[f32],
+pub fn apply_temporal_envelope_sized<T: Sample>(
+    current_spectrum: &[T],
+    previous_spectrum: &[T],
     speed: SpectrumSpeed,
     sample_rate: f32,
-    fft_size: usize,
-) -> (Vec<f32>, Vec<f32>) {
+    hop_size: usize,
+) -> (Vec<T>, Vec<T>) {
     // Calculate envelope factor based on response time
     // The release factor determines how much of the previous value to keep
     let response_time_ms = speed.response_time_ms();
 
     // Calculate how many FFT frames occur per second
-    let fft_hop_size = fft_size as f32 * (1.0 - FFT_OVERLAP_FACTOR);
-    let fft_frames_per_second = sample_rate / fft_hop_size;
+    let fft_frames_per_second = sample_rate / hop_size as f32;
 
     // Calculate release factor: higher value = slower decay
     // Using exponential decay: factor = exp(-dt/tau) where tau is the time constant
     let time_constant_seconds = response_time_ms / 1000.0;
     let dt = 1.0 / fft_frames_per_second; // Time between FFT frames
-    let release_factor = (-dt / time_constant_seconds).exp();
+    let release_factor = T::constant((-dt / time_constant_seconds).exp() as f64);
 
-    let envelope_applied: Vec<f32> = current_spectrum
+    let envelope_applied: Vec<T> = current_spectrum
         .iter()
         .zip(previous_spectrum.iter())
         .map(|(&current_db, &previous_db)| {
@@ -528,10 +1814,1138 @@ pub fn apply_temporal_envelope_sized(
                 current_db
             } else {
                 // Falling signal - gradual decay (slow release)
-                previous_db * release_factor + current_db * (1.0 - release_factor)
+                previous_db * release_factor + current_db * (T::one() - release_factor)
             }
         })
         .collect();
 
     (envelope_applied.clone(), envelope_applied)
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// Host block size used when feeding test signals through a producer
+    const TEST_BLOCK_SIZE: usize = 512;
+
+    /// Full-resolution bins of the producer's default FFT size
+    const DEFAULT_BINS: usize = DEFAULT_FFT_SIZE / 2 + 1;
+
+    /// Feed `left`/`right` through `producer` in host-sized blocks, as `process` sees them
+    fn process_stereo(
+        producer: &mut SpectrumProducer,
+        left: &[f32],
+        right: &[f32],
+        sample_rate: f32,
+        tilt: TiltLevel,
+    ) {
+        for (left_block, right_block) in left
+            .chunks(TEST_BLOCK_SIZE)
+            .zip(right.chunks(TEST_BLOCK_SIZE))
+        {
+            let mut left_block = left_block.to_vec();
+            let mut right_block = right_block.to_vec();
+            let num_samples = left_block.len();
+
+            let mut buffer = Buffer::default();
+            unsafe {
+                buffer.set_slices(num_samples, |output_slices| {
+                    *output_slices = vec![&mut left_block, &mut right_block];
+                });
+            }
+            producer.process(
+                &buffer,
+                sample_rate,
+                tilt,
+                SpectrumSpeed::Medium,
+                ResolutionLevel::Medium,
+            );
+        }
+    }
+
+    /// Native (sine RMS) level of a sine with peak amplitude 1.0
+    const UNIT_SINE_RMS_DB: f32 = -3.0103;
+
+    /// Full-resolution magnitude spectrum of `amplitude * sin(2π f t)`, analysed like the
+    /// producer does: one Hann-windowed `DEFAULT_FFT_SIZE` frame
+    fn sine_spectrum(frequency_hz: f32, amplitude: f32, sample_rate: f32) -> Vec<f32> {
+        let mut samples = sine(frequency_hz, amplitude, sample_rate, 0, DEFAULT_FFT_SIZE);
+        magnitude_spectrum_of(&mut samples)
+    }
+
+    /// Window, transform and scale one frame of samples
+    fn magnitude_spectrum_of(samples: &mut [f32]) -> Vec<f32> {
+        let window = WindowType::Hann.generate(samples.len());
+        apply_window(samples, &window);
+
+        let fft = RealFftPlanner::<f32>::new().plan_fft_forward(samples.len());
+        let mut bins = fft.make_output_vec();
+        fft.process(samples, &mut bins).unwrap();
+        compute_magnitude_spectrum(&bins, samples.len())
+    }
+
+    #[test]
+    fn calibration_tone_reads_full_scale() {
+        for sample_rate in [44100.0, 48000.0, 96000.0] {
+            let (mut producer, consumer) = SpectrumProducer::new();
+            producer.set_calibration(true);
+
+            // The tone replaces the input; two windows make sure the last frame is all tone
+            let silence = vec![0.0; DEFAULT_FFT_SIZE * 2];
+            process_stereo(
+                &mut producer,
+                &silence,
+                &silence,
+                sample_rate,
+                TiltLevel::Strong,
+            );
+
+            let peak = consumer.peak();
+            let reading_db = calibration_level_db(peak.level_db);
+            assert!(
+                (reading_db - CALIBRATION_EXPECTED_DB).abs() <= CALIBRATION_TOLERANCE_DB,
+                "{sample_rate} Hz: calibration read {reading_db} dB"
+            );
+            assert!(
+                (peak.frequency_hz - calibration_frequency_hz(sample_rate, DEFAULT_FFT_SIZE)).abs()
+                    < 0.01,
+                "{sample_rate} Hz: peak at {} Hz",
+                peak.frequency_hz
+            );
+        }
+    }
+
+    #[test]
+    fn producer_analyses_at_each_fft_size() {
+        let sample_rate = 48000.0;
+        let amplitude = 0.5;
+        for fft_size in [1024, DEFAULT_FFT_SIZE, 8192] {
+            let (mut producer, consumer) = SpectrumProducer::new();
+            producer.set_fft_size(fft_size).unwrap();
+            assert_eq!(producer.config().window_size, fft_size);
+
+            // Bin-centred at this size, so the level carries no scalloping loss
+            let tone_hz = calibration_frequency_hz(sample_rate, fft_size);
+            let signal = sine(tone_hz, amplitude, sample_rate, 0, fft_size * 8);
+            process_stereo(
+                &mut producer,
+                &signal,
+                &signal,
+                sample_rate,
+                TiltLevel::None,
+            );
+
+            // Half overlap gives two frames per window of input, whatever the size
+            let frame = consumer.read().unwrap();
+            assert_eq!(frame.window_size, fft_size);
+            assert_eq!(frame.frame_index, 16, "{fft_size}-point frames published");
+            assert_eq!(frame.data.len(), ResolutionLevel::Medium.to_bin_count());
+
+            let bin_width_hz = sample_rate / fft_size as f32;
+            let peak = consumer.peak();
+            assert!(
+                (peak.frequency_hz - tone_hz).abs() < bin_width_hz * 0.01,
+                "{fft_size}-point: peak at {} Hz, tone at {tone_hz} Hz",
+                peak.frequency_hz
+            );
+            let expected_db = UNIT_SINE_RMS_DB + 20.0 * amplitude.log10();
+            assert!(
+                (peak.level_db - expected_db).abs() < 0.05,
+                "{fft_size}-point: tone read {} dB instead of {expected_db} dB",
+                peak.level_db
+            );
+        }
+    }
+
+    #[test]
+    fn fft_size_change_reseeds_the_analysis() {
+        let sample_rate = 48000.0;
+        let (mut producer, consumer) = SpectrumProducer::new();
+        let loud = sine(1000.0, 1.0, sample_rate, 0, DEFAULT_FFT_SIZE * 2);
+        process_stereo(&mut producer, &loud, &loud, sample_rate, TiltLevel::None);
+
+        // Nothing analysed at the old size may leak into the first frame at the new one
+        producer.set_fft_size(8192).unwrap();
+        let silence = vec![0.0; 8192 / 2];
+        process_stereo(
+            &mut producer,
+            &silence,
+            &silence,
+            sample_rate,
+            TiltLevel::None,
+        );
+        assert_eq!(consumer.read().unwrap().window_size, 8192);
+        assert_eq!(consumer.peak().level_db, SPECTRUM_FLOOR_DB);
+    }
+
+    #[test]
+    fn unsupported_fft_sizes_are_rejected() {
+        let (mut producer, _consumer) = SpectrumProducer::new();
+        for fft_size in [0, 100, 1000, MIN_FFT_SIZE / 2, MAX_FFT_SIZE_USIZE * 2] {
+            assert!(
+                producer.set_fft_size(fft_size).is_err(),
+                "{fft_size} should be rejected"
+            );
+            assert_eq!(producer.config().window_size, DEFAULT_FFT_SIZE);
+        }
+        for fft_size in [MIN_FFT_SIZE, MAX_FFT_SIZE_USIZE] {
+            producer.set_fft_size(fft_size).unwrap();
+            assert_eq!(producer.config().window_size, fft_size);
+        }
+    }
+
+    /// Write `samples` into the producer's ring buffer as a single-channel host block
+    fn add_mono_block(producer: &mut SpectrumProducer, samples: &[f32]) {
+        let mut block = samples.to_vec();
+        let mut buffer = Buffer::default();
+        unsafe {
+            buffer.set_slices(block.len(), |output_slices| {
+                *output_slices = vec![&mut block];
+            });
+        }
+        producer.add_samples_to_ring_buffer(&buffer);
+    }
+
+    #[test]
+    fn ring_buffer_window_is_the_latest_samples() {
+        let (mut producer, _consumer) = SpectrumProducer::new();
+        let ring_len = producer.ring_buffer.len();
+        let window_len = producer.time_domain_buffer.len();
+
+        // Everything written so far, after the silence the ring buffer starts with
+        let mut written = vec![0.0; ring_len];
+        let mut state: u64 = 0x5eed;
+        for block_index in 0..200 {
+            state = state
+                .wrapping_mul(6364136223846793005)
+                .wrapping_add(1442695040888963407);
+            // Mostly host-sized blocks, with some longer than the whole ring buffer
+            let block_size = if block_index % 10 == 9 {
+                ring_len + (state >> 33) as usize % ring_len
+            } else {
+                1 + (state >> 33) as usize % 2048
+            };
+            let block = white_noise(block_size, state);
+            add_mono_block(&mut producer, &block);
+            written.extend_from_slice(&block);
+
+            producer.copy_from_ring_buffer();
+            let expected = &written[written.len() - window_len..];
+            assert!(
+                producer.time_domain_buffer == expected,
+                "block {block_index} of {block_size} samples, ring position {}: window is \
+                 not the last {window_len} samples",
+                producer.ring_buffer_pos
+            );
+        }
+    }
+
+    #[test]
+    fn interpolated_peak_tracks_a_sine_between_bins() {
+        let sample_rate = 48000.0;
+        let bin_width_hz = sample_rate / DEFAULT_FFT_SIZE as f32;
+
+        // Sweep a full bin spacing above bin 85 (~1 kHz) in tenths of a bin
+        for step in 0..=10 {
+            let frequency_hz = (85.0 + step as f32 / 10.0) * bin_width_hz;
+            let spectrum = sine_spectrum(frequency_hz, 1.0, sample_rate);
+            let peak = find_interpolated_peak(&spectrum, bin_width_hz);
+
+            assert!(
+                (peak.level_db - UNIT_SINE_RMS_DB).abs() <= 0.2,
+                "{frequency_hz} Hz read {} dB",
+                peak.level_db
+            );
+            assert!(
+                (peak.frequency_hz - frequency_hz).abs() <= frequency_hz * 0.01,
+                "{frequency_hz} Hz read at {} Hz",
+                peak.frequency_hz
+            );
+        }
+    }
+
+    /// Deterministic white noise, uniform in -1..1 (RMS 1/sqrt(3), about -4.77 dB)
+    fn white_noise(count: usize, seed: u64) -> Vec<f32> {
+        let mut state = seed;
+        (0..count)
+            .map(|_| {
+                state = state
+                    .wrapping_mul(6364136223846793005)
+                    .wrapping_add(1442695040888963407);
+                (state >> 40) as f32 / (1u64 << 23) as f32 - 1.0
+            })
+            .collect()
+    }
+
+    /// RMS level of `samples` in dB
+    fn rms_db(samples: &[f32]) -> f32 {
+        let mean_square: f64 =
+            samples.iter().map(|&s| (s as f64).powi(2)).sum::<f64>() / samples.len() as f64;
+        (10.0 * mean_square.log10()) as f32
+    }
+
+    #[test]
+    fn band_levels_read_a_sine_in_its_band() {
+        let sample_rate = 48000.0;
+        let bin_width_hz = sample_rate / DEFAULT_FFT_SIZE as f32;
+
+        for (frequency_hz, band) in [(100.0, 0), (1000.0, 1), (8000.0, 2)] {
+            let spectrum = sine_spectrum(frequency_hz, 0.5, sample_rate);
+            let levels = compute_band_levels(&spectrum, bin_width_hz, 250.0, 4000.0);
+            let levels = [levels.low_db, levels.mid_db, levels.high_db];
+
+            // A 0.5 peak sine has an RMS of -9.03 dB
+            let expected_db = UNIT_SINE_RMS_DB + 20.0 * 0.5_f32.log10();
+            assert!(
+                (levels[band] - expected_db).abs() <= 0.1,
+                "{frequency_hz} Hz read {} dB in its own band",
+                levels[band]
+            );
+            for (other, level_db) in levels.iter().enumerate().filter(|&(b, _)| b != band) {
+                assert!(
+                    *level_db < expected_db - 50.0,
+                    "{frequency_hz} Hz leaked {level_db} dB into band {other}"
+                );
+            }
+        }
+    }
+
+    #[test]
+    fn band_level_reads_broadband_rms() {
+        let sample_rate = 48000.0;
+        let bin_width_hz = sample_rate / DEFAULT_FFT_SIZE as f32;
+        let mut noise = white_noise(DEFAULT_FFT_SIZE, 1);
+        let expected_db = rms_db(&noise);
+
+        let spectrum = magnitude_spectrum_of(&mut noise);
+        let full_band_db = compute_band_level(&spectrum, bin_width_hz, 0.0, sample_rate / 2.0);
+        assert!(
+            (full_band_db - expected_db).abs() <= 0.5,
+            "noise at {expected_db} dB RMS read {full_band_db} dB"
+        );
+    }
+
+    #[test]
+    fn band_levels_partition_the_spectrum() {
+        let sample_rate = 48000.0;
+        let bin_width_hz = sample_rate / DEFAULT_FFT_SIZE as f32;
+        let spectrum = magnitude_spectrum_of(&mut white_noise(DEFAULT_FFT_SIZE, 2));
+
+        // Every non-DC bin lands in exactly one band, so the bands sum to the whole
+        let levels = compute_band_levels(&spectrum, bin_width_hz, 250.0, 4000.0);
+        let summed_db = sum_bins_power_to_db(&[levels.low_db, levels.mid_db, levels.high_db]);
+        let full_band_db = compute_band_level(&spectrum, bin_width_hz, 0.0, sample_rate / 2.0);
+        assert!(
+            (summed_db - full_band_db).abs() <= 0.01,
+            "bands summed to {summed_db} dB, full band read {full_band_db} dB"
+        );
+    }
+
+    #[test]
+    fn two_equal_tones_in_a_band_read_three_db_above_one() {
+        let sample_rate = 48000.0;
+        let bin_width_hz = sample_rate / DEFAULT_FFT_SIZE as f32;
+        // Bin-centred and far apart, so neither tone's leakage reaches the other
+        let (first_hz, second_hz) = (40.0 * bin_width_hz, 120.0 * bin_width_hz);
+
+        let one_tone = sine(first_hz, 0.25, sample_rate, 0, DEFAULT_FFT_SIZE);
+        let mut two_tones = sine(second_hz, 0.25, sample_rate, 0, DEFAULT_FFT_SIZE);
+        for (sample, first) in two_tones.iter_mut().zip(&one_tone) {
+            *sample += first;
+        }
+
+        let one_db = compute_band_level(
+            &magnitude_spectrum_of(&mut one_tone.clone()),
+            bin_width_hz,
+            250.0,
+            4000.0,
+        );
+        let two_db = compute_band_level(
+            &magnitude_spectrum_of(&mut two_tones),
+            bin_width_hz,
+            250.0,
+            4000.0,
+        );
+        assert!(
+            (two_db - one_db - 3.0103).abs() <= 0.05,
+            "one tone read {one_db} dB, two read {two_db} dB"
+        );
+    }
+
+    #[test]
+    fn bin_power_sums_in_the_power_domain() {
+        assert!((sum_bins_power_to_db(&[-20.0, -20.0]) - (-20.0 + 3.0103)).abs() < 1e-3);
+        assert!(sum_bins_power_to_db(&[-6.0206; 4]).abs() < 1e-3);
+        assert!(sum_bins_power_to_db(&[0.0, SPECTRUM_FLOOR_DB]).abs() < 1e-3);
+        assert_eq!(sum_bins_power_to_db(&[]), SPECTRUM_FLOOR_DB);
+    }
+
+    /// Samples of `amplitude * sin(2π f t)` starting at sample `start`
+    fn sine(
+        frequency_hz: f32,
+        amplitude: f32,
+        sample_rate: f32,
+        start: usize,
+        count: usize,
+    ) -> Vec<f32> {
+        (start..start + count)
+            .map(|n| {
+                let phase = 2.0 * std::f64::consts::PI * frequency_hz as f64 * n as f64;
+                amplitude * (phase / sample_rate as f64).sin() as f32
+            })
+            .collect()
+    }
+
+    #[test]
+    fn envelope_reset_restarts_min_and_max() {
+        let sample_rate = 48000.0;
+        let second = sample_rate as usize;
+        let (mut producer, consumer) = SpectrumProducer::new();
+        producer.set_envelope_enabled(true);
+
+        // Put the tone exactly on a published bin, so that bin sits on the main lobe
+        // instead of a sidelobe whose level wanders with the tone's phase
+        let published_bins = ResolutionLevel::Medium.to_bin_count();
+        let tone_bin = 11;
+        let tone_hz = tone_bin as f32 * (DEFAULT_BINS - 1) as f32 / (published_bins - 1) as f32
+            * (sample_rate / DEFAULT_FFT_SIZE as f32);
+
+        // Silence then a steady tone: the minimum holds the silence, the maximum the tone
+        let silence = vec![0.0; second / 2];
+        process_stereo(
+            &mut producer,
+            &silence,
+            &silence,
+            sample_rate,
+            TiltLevel::None,
+        );
+        let tone = sine(tone_hz, 0.5, sample_rate, 0, second);
+        process_stereo(&mut producer, &tone, &tone, sample_rate, TiltLevel::None);
+
+        let frame = consumer.read().unwrap();
+        let tone_db = frame.data[tone_bin];
+        assert!(
+            frame.envelope_min[tone_bin] < tone_db - 60.0,
+            "minimum should still hold the silence before a reset"
+        );
+
+        consumer.reset_envelope();
+        let tone = sine(tone_hz, 0.5, sample_rate, second, second / 2);
+        process_stereo(&mut producer, &tone, &tone, sample_rate, TiltLevel::None);
+
+        let frame = consumer.read().unwrap();
+        assert_eq!(frame.envelope_min.len(), frame.data.len());
+        assert_eq!(frame.envelope_max.len(), frame.data.len());
+        assert!(
+            (frame.envelope_min[tone_bin] - tone_db).abs() < 1.0,
+            "minimum {} dB should restart at the tone's {tone_db} dB",
+            frame.envelope_min[tone_bin]
+        );
+        assert!(
+            (frame.envelope_max[tone_bin] - tone_db).abs() < 1.0,
+            "maximum {} dB should restart at the tone's {tone_db} dB",
+            frame.envelope_max[tone_bin]
+        );
+
+        // Disabling drops the envelope from published frames
+        producer.set_envelope_enabled(false);
+        producer.write_silence();
+        let frame = consumer.read().unwrap();
+        assert!(frame.envelope_min.is_empty() && frame.envelope_max.is_empty());
+    }
+
+    #[test]
+    fn top_peaks_are_the_loudest_tones_loudest_first() {
+        let sample_rate = 48000.0;
+        let bin_width_hz = sample_rate / DEFAULT_FFT_SIZE as f32;
+        let tones = [(5000.0, 0.1), (200.0, 0.5), (1000.0, 0.25)];
+
+        let mut samples = vec![0.0; DEFAULT_FFT_SIZE];
+        for (frequency_hz, amplitude) in tones {
+            let tone = sine(frequency_hz, amplitude, sample_rate, 0, DEFAULT_FFT_SIZE);
+            for (sample, tone_sample) in samples.iter_mut().zip(tone) {
+                *sample += tone_sample;
+            }
+        }
+        let spectrum = magnitude_spectrum_of(&mut samples);
+
+        let mut peaks = Vec::new();
+        find_top_peaks(&spectrum, bin_width_hz, 3, &mut peaks);
+        assert_eq!(peaks.len(), 3);
+        for (peak, (frequency_hz, amplitude)) in peaks.iter().zip([tones[1], tones[2], tones[0]]) {
+            let level_db = UNIT_SINE_RMS_DB + 20.0 * f32::log10(amplitude);
+            assert!(
+                (peak.frequency_hz - frequency_hz).abs() <= frequency_hz * 0.01,
+                "{frequency_hz} Hz tone found at {} Hz",
+                peak.frequency_hz
+            );
+            assert!(
+                (peak.level_db - level_db).abs() <= 0.2,
+                "{frequency_hz} Hz tone read {} dB, expected {level_db} dB",
+                peak.level_db
+            );
+        }
+
+        // Asking for fewer keeps the loudest
+        let mut fewer = Vec::new();
+        find_top_peaks(&spectrum, bin_width_hz, 2, &mut fewer);
+        assert_eq!(fewer, peaks[..2]);
+    }
+
+    #[test]
+    fn top_peaks_skip_dc_and_report_flat_tops_once() {
+        let floor = SPECTRUM_FLOOR_DB;
+        let spectrum = [0.0, -10.0, -20.0, -6.0, -6.0, -30.0, floor, -40.0, floor];
+        let mut peaks = Vec::new();
+        find_top_peaks(&spectrum, 1.0, 8, &mut peaks);
+
+        // Bin 0 is louder than everything but is DC; the flat top at bins 3-4 counts once
+        let levels: Vec<f32> = peaks.iter().map(|peak| peak.level_db).collect();
+        assert_eq!(peaks.len(), 2, "found {levels:?}");
+        assert!(peaks[0].frequency_hz > 3.0 && peaks[0].frequency_hz < 4.0);
+        assert!((peaks[1].frequency_hz - 7.0).abs() < 0.5);
+        assert!(peaks[0].level_db > peaks[1].level_db);
+    }
+
+    #[test]
+    fn top_peaks_of_silence_or_nothing_are_empty() {
+        let mut peaks = vec![SpectrumPeak {
+            frequency_hz: 1.0,
+            level_db: 0.0,
+        }];
+
+        find_top_peaks(&[SPECTRUM_FLOOR_DB; 64], 1.0, 4, &mut peaks);
+        assert!(peaks.is_empty(), "silence has no peaks");
+
+        find_top_peaks(&[-10.0, 0.0, -10.0], 1.0, 0, &mut peaks);
+        assert!(peaks.is_empty(), "no peaks were asked for");
+
+        find_top_peaks(&[-10.0, 0.0], 1.0, 4, &mut peaks);
+        assert!(peaks.is_empty(), "two bins have no interior maximum");
+    }
+
+    #[test]
+    fn edge_bins_are_hidden_only_when_enabled() {
+        let sample_rate = 48000.0;
+        // Steady DC plus a tone at Nyquist, so both edge bins carry a lone spike
+        let signal: Vec<f32> = (0..sample_rate as usize)
+            .map(|n| 0.25 + if n % 2 == 0 { 0.25 } else { -0.25 })
+            .collect();
+        let raw = magnitude_spectrum_of(&mut signal[..DEFAULT_FFT_SIZE].to_vec());
+        let last = raw.len() - 1;
+
+        let [shown, hidden] = [false, true].map(|hide| {
+            let (mut producer, consumer) = SpectrumProducer::new();
+            producer.set_hide_edge_bins(hide);
+            process_stereo(
+                &mut producer,
+                &signal,
+                &signal,
+                sample_rate,
+                TiltLevel::None,
+            );
+            consumer.read().unwrap().data.clone()
+        });
+        let published_last = shown.len() - 1;
+
+        // Off: the edges are the analysed levels
+        assert!((shown[0] - raw[0]).abs() < 0.1, "DC read {} dB", shown[0]);
+        assert!(
+            (shown[published_last] - raw[last]).abs() < 0.1,
+            "Nyquist read {} dB",
+            shown[published_last]
+        );
+
+        // On: each edge takes its inner neighbour's level
+        assert!(
+            (hidden[0] - raw[1]).abs() < 0.1,
+            "hidden DC read {} dB",
+            hidden[0]
+        );
+        assert!(
+            (hidden[published_last] - raw[last - 1]).abs() < 0.1,
+            "hidden Nyquist read {} dB",
+            hidden[published_last]
+        );
+        // The neighbours only hold the Hann window's leakage, 3 dB down once scaled to RMS
+        assert!(hidden[0] < shown[0] - 2.9);
+        assert!(hidden[published_last] < shown[published_last] - 2.9);
+
+        // Everything between the edges is the same either way
+        assert_eq!(hidden[1..published_last], shown[1..published_last]);
+    }
+
+    #[test]
+    fn every_overlap_setting_is_cola_for_the_analysis_window() {
+        let window = WindowType::Hann.generate::<f32>(DEFAULT_FFT_SIZE);
+        for overlap in [FftOverlap::Half, FftOverlap::ThreeQuarters] {
+            assert!(
+                is_cola(&window, overlap.hop_size(DEFAULT_FFT_SIZE)),
+                "{overlap:?} is not COLA for the Hann window"
+            );
+
+            // So the producer accepts every setting
+            let (mut producer, _consumer) = SpectrumProducer::new();
+            producer.set_overlap(overlap);
+            assert_eq!(producer.overlap, overlap);
+        }
+    }
+
+    const ALL_SPEEDS: [SpectrumSpeed; 5] = [
+        SpectrumSpeed::VerySlow,
+        SpectrumSpeed::Slow,
+        SpectrumSpeed::Medium,
+        SpectrumSpeed::Fast,
+        SpectrumSpeed::VeryFast,
+    ];
+
+    /// Run `frames` envelope steps of a constant `input_db` spectrum from `start_db`
+    fn envelope_after(
+        start_db: f32,
+        input_db: f32,
+        frames: usize,
+        speed: SpectrumSpeed,
+        hop_size: usize,
+    ) -> f32 {
+        let input = [input_db; 4];
+        let mut previous = vec![start_db; 4];
+        for _ in 0..frames {
+            previous = apply_temporal_envelope_sized(&input, &previous, speed, 48000.0, hop_size).1;
+        }
+        previous[0]
+    }
+
+    #[test]
+    fn temporal_envelope_attack_is_immediate() {
+        for speed in ALL_SPEEDS {
+            for hop_size in [256, 1024, 2048] {
+                assert_eq!(
+                    envelope_after(-100.0, -20.0, 1, speed, hop_size),
+                    -20.0,
+                    "{speed:?} at hop {hop_size}"
+                );
+            }
+        }
+    }
+
+    #[test]
+    fn temporal_envelope_release_follows_its_time_constant() {
+        let sample_rate = 48000.0;
+        for speed in ALL_SPEEDS {
+            let time_constant_s = speed.response_time_ms() / 1000.0;
+            for hop_size in [256, 1024, 2048] {
+                let frame_s = hop_size as f32 / sample_rate;
+                let frames_per_time_constant = (time_constant_s / frame_s).round() as usize;
+
+                // A 60 dB drop closes exponentially with the speed's time constant
+                for time_constants in [1, 3, 5] {
+                    let frames = frames_per_time_constant * time_constants;
+                    let level_db = envelope_after(-20.0, -80.0, frames, speed, hop_size);
+                    let expected_db =
+                        -80.0 + 60.0 * (-(frames as f32) * frame_s / time_constant_s).exp();
+                    assert!(
+                        (level_db - expected_db).abs() < 0.01,
+                        "{speed:?} at hop {hop_size}: {level_db} dB after {frames} frames, \
+                         expected {expected_db} dB"
+                    );
+                }
+
+                // Attack settles in one frame, release takes many
+                let settle = |start_db: f32, input_db: f32| {
+                    let input = [input_db];
+                    let mut previous = vec![start_db];
+                    (1..)
+                        .find(|_| {
+                            previous = apply_temporal_envelope_sized(
+                                &input,
+                                &previous,
+                                speed,
+                                sample_rate,
+                                hop_size,
+                            )
+                            .1;
+                            (previous[0] - input_db).abs() < 1.0
+                        })
+                        .unwrap()
+                };
+                assert!(settle(-80.0, -20.0) < settle(-20.0, -80.0));
+            }
+        }
+    }
+
+    #[test]
+    fn temporal_envelope_holds_a_steady_spectrum() {
+        for speed in ALL_SPEEDS {
+            for hop_size in [256, 1024, 2048] {
+                assert_eq!(envelope_after(-42.0, -42.0, 100, speed, hop_size), -42.0);
+            }
+        }
+    }
+
+    /// A tone plus low-level noise in f64, so the spectrum has a peak, skirts and a floor
+    fn tone_and_noise_f64() -> Vec<f64> {
+        let tone = sine(1000.0, 0.5, 48000.0, 0, DEFAULT_FFT_SIZE);
+        let noise = white_noise(DEFAULT_FFT_SIZE, 3);
+        tone.iter()
+            .zip(&noise)
+            .map(|(&tone, &noise)| tone as f64 + 0.001 * noise as f64)
+            .collect()
+    }
+
+    #[test]
+    fn window_agrees_between_f32_and_f64() {
+        let samples = tone_and_noise_f64();
+        for window_type in [WindowType::Hann, WindowType::Hamming, WindowType::Blackman] {
+            let mut windowed_f64 = samples.clone();
+            apply_window(
+                &mut windowed_f64,
+                &window_type.generate::<f64>(samples.len()),
+            );
+            let mut windowed_f32: Vec<f32> = samples.iter().map(|&sample| sample as f32).collect();
+            apply_window(
+                &mut windowed_f32,
+                &window_type.generate::<f32>(samples.len()),
+            );
+
+            for (index, (&wide, &narrow)) in windowed_f64.iter().zip(&windowed_f32).enumerate() {
+                assert!(
+                    (wide - narrow as f64).abs() < 1e-6,
+                    "{window_type:?} sample {index}: {wide} vs {narrow}"
+                );
+            }
+        }
+    }
+
+    #[test]
+    fn magnitude_spectrum_agrees_between_f32_and_f64() {
+        let samples = tone_and_noise_f64();
+        let size = samples.len();
+
+        let mut windowed_f64 = samples.clone();
+        apply_window(&mut windowed_f64, &WindowType::Hann.generate::<f64>(size));
+        let fft_f64 = RealFftPlanner::<f64>::new().plan_fft_forward(size);
+        let mut bins_f64 = fft_f64.make_output_vec();
+        fft_f64.process(&mut windowed_f64, &mut bins_f64).unwrap();
+        let spectrum_f64 = compute_magnitude_spectrum(&bins_f64, size);
+
+        let mut samples_f32: Vec<f32> = samples.iter().map(|&sample| sample as f32).collect();
+        let spectrum_f32 = magnitude_spectrum_of(&mut samples_f32);
+
+        // f32 rounding noise sits far below the signal; compare everything above it
+        for (bin, (&wide, &narrow)) in spectrum_f64.iter().zip(&spectrum_f32).enumerate() {
+            if wide > -100.0 {
+                assert!(
+                    (wide - narrow as f64).abs() < 0.01,
+                    "bin {bin}: {wide} dB in f64, {narrow} dB in f32"
+                );
+            }
+        }
+        let peak_bin = (1000.0 / (48000.0 / size as f64)).round() as usize;
+        assert!(
+            spectrum_f64[peak_bin] > -10.0,
+            "the tone should be in the comparison"
+        );
+    }
+
+    #[test]
+    fn tilt_compensation_agrees_between_f32_and_f64() {
+        for tilt_db_per_oct in [0.0, 3.0, 4.5, 6.0] {
+            for freq_hz in [0.0, 10.0, 20.0, 100.0, 1000.0, 5000.0, 20000.0] {
+                for magnitude_db in [-120.0, -60.0, -3.0, 0.0] {
+                    let wide =
+                        apply_tilt_compensation::<f64>(magnitude_db, freq_hz, tilt_db_per_oct);
+                    let narrow = apply_tilt_compensation::<f32>(
+                        magnitude_db as f32,
+                        freq_hz as f32,
+                        tilt_db_per_oct as f32,
+                    );
+                    assert!(
+                        (wide - narrow as f64).abs() < 1e-4,
+                        "{magnitude_db} dB at {freq_hz} Hz, {tilt_db_per_oct} dB/oct: \
+                         {wide} vs {narrow}"
+                    );
+                }
+            }
+        }
+    }
+
+    #[test]
+    fn tilt_compensation_leaves_the_1khz_pivot_unchanged() {
+        for tilt_db_per_oct in [-3.0, 3.0, 4.5, 6.0] {
+            for magnitude_db in [-90.0, -24.0, 0.0] {
+                let tilted = apply_tilt_compensation::<f32>(
+                    magnitude_db,
+                    TILT_REFERENCE_FREQ_HZ,
+                    tilt_db_per_oct,
+                );
+                assert_eq!(
+                    tilted, magnitude_db,
+                    "{tilt_db_per_oct} dB/oct moved {magnitude_db} dB at the pivot"
+                );
+            }
+        }
+    }
+
+    #[test]
+    fn tilt_compensation_adds_its_slope_per_octave() {
+        for tilt_db_per_oct in [3.0, 4.5, 6.0] {
+            for octaves in [-6, -3, -2, -1, 1, 2, 3, 4] {
+                let freq_hz = TILT_REFERENCE_FREQ_HZ * 2.0_f32.powi(octaves);
+                let tilted = apply_tilt_compensation::<f32>(-40.0, freq_hz, tilt_db_per_oct);
+                // Boosted above the pivot and cut below it, by the slope for each octave
+                let expected = -40.0 + tilt_db_per_oct * octaves as f32;
+                assert!(
+                    (tilted - expected).abs() < 1e-4,
+                    "{tilt_db_per_oct} dB/oct at {freq_hz} Hz: {tilted} vs {expected}"
+                );
+            }
+        }
+    }
+
+    #[test]
+    fn zero_tilt_is_an_identity() {
+        for freq_hz in [0.0, 20.0, 440.0, 1000.0, 12345.0, 24000.0] {
+            for magnitude_db in [SPECTRUM_FLOOR_DB, -60.0, -3.0, 0.0] {
+                assert_eq!(
+                    apply_tilt_compensation::<f32>(magnitude_db, freq_hz, 0.0),
+                    magnitude_db,
+                    "0 dB/oct changed {magnitude_db} dB at {freq_hz} Hz"
+                );
+            }
+        }
+    }
+
+    /// Standard deviation of `values` (dB)
+    fn spread_db(values: &[f32]) -> f32 {
+        let mean = values.iter().sum::<f32>() / values.len() as f32;
+        let variance =
+            values.iter().map(|&v| (v - mean).powi(2)).sum::<f32>() / values.len() as f32;
+        variance.sqrt()
+    }
+
+    #[test]
+    fn welch_block_publishes_only_when_complete() {
+        let (mut producer, _consumer) = SpectrumProducer::new();
+        producer.set_welch_averages(3);
+
+        let mut frame = vec![-20.0; DEFAULT_BINS];
+        assert!(!producer.accumulate_welch(&mut frame.clone()));
+        assert!(!producer.accumulate_welch(&mut frame.clone()));
+        assert!(producer.accumulate_welch(&mut frame));
+        assert!(
+            frame.iter().all(|&db| (db + 20.0).abs() < 1e-3),
+            "a steady level averages to itself"
+        );
+
+        // The sum is cleared for the next block
+        let mut frame = vec![-50.0; DEFAULT_BINS];
+        assert!(!producer.accumulate_welch(&mut frame.clone()));
+        assert!(!producer.accumulate_welch(&mut frame.clone()));
+        assert!(producer.accumulate_welch(&mut frame));
+        assert!((frame[100] + 50.0).abs() < 1e-3, "read {} dB", frame[100]);
+    }
+
+    #[test]
+    fn welch_averages_linear_power_not_decibels() {
+        let (mut producer, _consumer) = SpectrumProducer::new();
+        producer.set_welch_averages(2);
+
+        assert!(!producer.accumulate_welch(&mut vec![0.0; DEFAULT_BINS]));
+        let mut frame = vec![-10.0; DEFAULT_BINS];
+        assert!(producer.accumulate_welch(&mut frame));
+
+        // (1.0 + 0.1) / 2 in power, not the -5 dB a dB average would give
+        let expected = 10.0 * 0.55_f32.log10();
+        assert!(
+            (frame[1] - expected).abs() < 1e-3,
+            "read {} dB, expected {expected} dB",
+            frame[1]
+        );
+    }
+
+    #[test]
+    fn welch_changing_the_count_starts_a_fresh_block() {
+        let (mut producer, _consumer) = SpectrumProducer::new();
+        producer.set_welch_averages(2);
+        assert!(!producer.accumulate_welch(&mut vec![0.0; DEFAULT_BINS]));
+
+        // The 0 dB frame from the old block must not leak into the new one
+        producer.set_welch_averages(3);
+        let mut frame = vec![-30.0; DEFAULT_BINS];
+        assert!(!producer.accumulate_welch(&mut frame.clone()));
+        assert!(!producer.accumulate_welch(&mut frame.clone()));
+        assert!(producer.accumulate_welch(&mut frame));
+        assert!((frame[1] + 30.0).abs() < 1e-3, "read {} dB", frame[1]);
+    }
+
+    #[test]
+    fn welch_averaging_steadies_a_noise_spectrum() {
+        const AVERAGES: usize = 16;
+        let (mut producer, _consumer) = SpectrumProducer::new();
+        producer.set_welch_averages(AVERAGES);
+
+        let frames: Vec<Vec<f32>> = (0..AVERAGES as u64)
+            .map(|seed| magnitude_spectrum_of(&mut white_noise(DEFAULT_FFT_SIZE, seed + 1)))
+            .collect();
+        let mut averaged = Vec::new();
+        for frame in &frames {
+            averaged = frame.clone();
+            producer.accumulate_welch(&mut averaged);
+        }
+
+        // Away from the edges, where every bin is an independent estimate of the same level
+        let bins = 16..DEFAULT_BINS - 16;
+        let single_spread = spread_db(&frames[0][bins.clone()]);
+        let averaged_spread = spread_db(&averaged[bins]);
+
+        // One periodogram scatters ~5.6 dB; averaging N frames divides that by ~sqrt(N)
+        assert!(single_spread > 4.0, "one frame spread {single_spread} dB");
+        assert!(
+            averaged_spread < single_spread / 3.0,
+            "{AVERAGES} averages spread {averaged_spread} dB, one frame {single_spread} dB"
+        );
+    }
+
+    #[test]
+    fn welch_averaging_publishes_once_per_block() {
+        let sample_rate = 48000.0;
+        let noise = white_noise(sample_rate as usize, 7);
+        let [unaveraged, averaged] = [1, 4].map(|averages| {
+            let (mut producer, consumer) = SpectrumProducer::new();
+            producer.set_welch_averages(averages);
+            process_stereo(&mut producer, &noise, &noise, sample_rate, TiltLevel::None);
+            consumer.read().unwrap().frame_index
+        });
+
+        assert!(unaveraged > 8, "published {unaveraged} frames");
+        assert_eq!(averaged, unaveraged / 4);
+    }
+
+    /// Committed reference vectors for the golden tests, one value per line
+    const GOLDEN_DIR: &str = concat!(env!("CARGO_MANIFEST_DIR"), "/tests/golden");
+
+    /// Set to rewrite the reference vectors after an intentional change to the scaling
+    const UPDATE_GOLDENS_VAR: &str = "UPDATE_GOLDENS";
+
+    /// Largest difference from a reference value still accepted (dB)
+    const GOLDEN_TOLERANCE_DB: f32 = 1e-3;
+
+    /// Level below which values are f32 rounding in the FFT that varies with the SIMD
+    /// path, so a value there only has to stay below it (dB)
+    const GOLDEN_FLOOR_DB: f32 = -100.0;
+
+    /// Mismatched values listed in a failure message before the rest are counted
+    const GOLDEN_DIFF_LINES: usize = 20;
+
+    /// FFT size of the golden inputs, small enough to keep the reference files readable
+    const GOLDEN_FFT_SIZE: usize = 1024;
+
+    const GOLDEN_SAMPLE_RATE: f32 = 48000.0;
+
+    /// Tilt the golden spectra go through, so a change to its slope or pivot shows up
+    const GOLDEN_TILT_DB_PER_OCT: f32 = 4.5;
+
+    /// Compare `actual` against the reference file `name`, or rewrite it when
+    /// `UPDATE_GOLDENS` is set
+    fn check_golden(name: &str, actual: &[f32]) {
+        let path = format!("{GOLDEN_DIR}/{name}.txt");
+        if std::env::var_os(UPDATE_GOLDENS_VAR).is_some() {
+            let text: String = actual.iter().map(|value| format!("{value:.6}\n")).collect();
+            std::fs::create_dir_all(GOLDEN_DIR).unwrap();
+            std::fs::write(&path, text).unwrap();
+            return;
+        }
+
+        let expected: Vec<f32> = std::fs::read_to_string(&path)
+            .unwrap_or_else(|err| {
+                panic!("{path}: {err}; run with {UPDATE_GOLDENS_VAR}=1 to create it")
+            })
+            .lines()
+            .map(|line| line.parse().unwrap())
+            .collect();
+        assert_eq!(
+            actual.len(),
+            expected.len(),
+            "{name}: {} values against {} in the reference",
+            actual.len(),
+            expected.len()
+        );
+
+        let mismatches: Vec<String> = expected
+            .iter()
+            .zip(actual)
+            .enumerate()
+            .filter(|(_, (&expected, &actual))| {
+                let both_below_floor = expected < GOLDEN_FLOOR_DB && actual < GOLDEN_FLOOR_DB;
+                (actual - expected).abs() > GOLDEN_TOLERANCE_DB && !both_below_floor
+            })
+            .map(|(bin, (&expected, &actual))| {
+                format!(
+                    "  bin {bin}: expected {expected:.4}, got {actual:.4} ({:+.4})",
+                    actual - expected
+                )
+            })
+            .collect();
+        assert!(
+            mismatches.is_empty(),
+            "{name}: {} of {} values above {GOLDEN_FLOOR_DB} dB differ by more than \
+             {GOLDEN_TOLERANCE_DB}\n{}{}\n\
+             Rerun with {UPDATE_GOLDENS_VAR}=1 if the change is intended",
+            mismatches.len(),
+            expected.len(),
+            mismatches[..mismatches.len().min(GOLDEN_DIFF_LINES)].join("\n"),
+            if mismatches.len() > GOLDEN_DIFF_LINES {
+                format!("\n  ... and {} more", mismatches.len() - GOLDEN_DIFF_LINES)
+            } else {
+                String::new()
+            }
+        );
+    }
+
+    /// One golden frame through the scaling chain: Hann window, FFT, dB scaling, tilt
+    fn golden_spectrum(mut samples: Vec<f32>) -> Vec<f32> {
+        assert_eq!(samples.len(), GOLDEN_FFT_SIZE);
+        let bin_width_hz = GOLDEN_SAMPLE_RATE / GOLDEN_FFT_SIZE as f32;
+        magnitude_spectrum_of(&mut samples)
+            .into_iter()
+            .enumerate()
+            .map(|(bin, db)| {
+                apply_tilt_compensation(db, bin as f32 * bin_width_hz, GOLDEN_TILT_DB_PER_OCT)
+            })
+            .collect()
+    }
+
+    /// A -12 dBFS sine `bins` bin widths up, over one golden frame
+    fn golden_sine(bins: f32) -> Vec<f32> {
+        let frequency_hz = bins * GOLDEN_SAMPLE_RATE / GOLDEN_FFT_SIZE as f32;
+        let amplitude = 10.0_f32.powf(-12.0 / 20.0);
+        sine(
+            frequency_hz,
+            amplitude,
+            GOLDEN_SAMPLE_RATE,
+            0,
+            GOLDEN_FFT_SIZE,
+        )
+    }
+
+    #[test]
+    fn golden_sine_at_a_bin_centre() {
+        // Bin 21, 984.375 Hz: all of the tone lands on three bins
+        check_golden("sine_bin_centre", &golden_spectrum(golden_sine(21.0)));
+    }
+
+    #[test]
+    fn golden_sine_between_bins() {
+        // Halfway between bins 21 and 22, where the Hann scalloping loss is largest
+        check_golden("sine_between_bins", &golden_spectrum(golden_sine(21.5)));
+    }
+
+    #[test]
+    fn golden_impulse() {
+        let mut impulse = vec![0.0; GOLDEN_FFT_SIZE];
+        impulse[GOLDEN_FFT_SIZE / 2] = 1.0;
+        check_golden("impulse", &golden_spectrum(impulse));
+    }
+
+    #[test]
+    fn golden_smoothed_white_noise() {
+        // Four noise frames through the Medium envelope, as the producer would at 50% overlap
+        let hop_size = GOLDEN_FFT_SIZE / 2;
+        let mut previous = golden_spectrum(white_noise(GOLDEN_FFT_SIZE, 1));
+        for seed in 2..=4 {
+            let current = golden_spectrum(white_noise(GOLDEN_FFT_SIZE, seed));
+            previous = apply_temporal_envelope_sized(
+                &current,
+                &previous,
+                SpectrumSpeed::Medium,
+                GOLDEN_SAMPLE_RATE,
+                hop_size,
+            )
+            .1;
+        }
+        check_golden("smoothed_white_noise", &previous);
+    }
+
+    #[test]
+    fn golden_hann_window_sum() {
+        // The sum sets the coherent gain the dB scaling divides out
+        let window = WindowType::Hann.generate::<f32>(GOLDEN_FFT_SIZE);
+        check_golden("hann_window_sum", &[window.iter().sum()]);
+    }
+
+    /// A frame with every field set away from its default
+    #[cfg(feature = "serialize")]
+    fn populated_frame() -> SpectrumFrame {
+        SpectrumFrame {
+            data: vec![-3.0, -42.5, SPECTRUM_FLOOR_DB, 0.125],
+            sample_rate: 96000.0,
+            window_size: 2048,
+            floor_db: -120.0,
+            frame_index: 12345,
+            envelope_min: vec![-60.0, -61.5],
+            envelope_max: vec![-1.0, -2.25],
+        }
+    }
+
+    #[cfg(feature = "serialize")]
+    fn assert_frames_equal(actual: &SpectrumFrame, expected: &SpectrumFrame) {
+        assert_eq!(actual.data, expected.data);
+        assert_eq!(actual.sample_rate, expected.sample_rate);
+        assert_eq!(actual.window_size, expected.window_size);
+        assert_eq!(actual.floor_db, expected.floor_db);
+        assert_eq!(actual.frame_index, expected.frame_index);
+        assert_eq!(actual.envelope_min, expected.envelope_min);
+        assert_eq!(actual.envelope_max, expected.envelope_max);
+    }
+
+    #[test]
+    #[cfg(feature = "serialize")]
+    fn spectrum_frame_round_trips_through_json() {
+        let frame = populated_frame();
+        let json = serde_json::to_string(&frame).unwrap();
+        let loaded: SpectrumFrame = serde_json::from_str(&json).unwrap();
+        assert_frames_equal(&loaded, &frame);
+    }
+
+    #[test]
+    #[cfg(feature = "serialize")]
+    fn snapshot_is_a_flat_versioned_frame() {
+        let snapshot = SpectrumSnapshot::new(populated_frame());
+        let value = serde_json::to_value(&snapshot).unwrap();
+        assert_eq!(value["version"], SNAPSHOT_VERSION);
+        assert_eq!(value["frame_index"], 12345);
+        assert!(
+            value.get("frame").is_none(),
+            "frame fields sit beside the version"
+        );
+
+        let loaded: SpectrumSnapshot = serde_json::from_value(value).unwrap();
+        assert_eq!(loaded.version, SNAPSHOT_VERSION);
+        assert_frames_equal(&loaded.frame, &snapshot.frame);
+    }
+
+    #[test]
+    #[cfg(feature = "serialize")]
+    fn snapshot_missing_newer_fields_still_loads() {
+        // A file from before the envelope fields were added
+        let json = r#"{"version": 1, "data": [-6.0, -12.0], "sample_rate": 44100.0}"#;
+        let loaded: SpectrumSnapshot = serde_json::from_str(json).unwrap();
+        let silent = SpectrumFrame::default();
+
+        assert_eq!(loaded.frame.data, [-6.0, -12.0]);
+        assert_eq!(loaded.frame.sample_rate, 44100.0);
+        assert_eq!(loaded.frame.window_size, silent.window_size);
+        assert_eq!(loaded.frame.floor_db, silent.floor_db);
+        assert!(loaded.frame.envelope_min.is_empty() && loaded.frame.envelope_max.is_empty());
+    }
+
+    #[test]
+    #[cfg(feature = "serialize")]
+    fn spectrum_speed_round_trips_through_json() {
+        for speed in ALL_SPEEDS {
+            let json = serde_json::to_string(&speed).unwrap();
+            assert_eq!(serde_json::from_str::<SpectrumSpeed>(&json).unwrap(), speed);
+        }
+    }
+}
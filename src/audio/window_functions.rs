@@ -2,8 +2,29 @@
 ///
 /// This module provides various window functions and adaptive windowing
 /// strategies for optimizing spectrum analysis at different frequency ranges.
-use core::f32::consts::PI;
-use libm::cosf;
+use realfft::num_traits::{Float, FloatConst};
+
+/// Largest relative ripple in the overlap-added window still accepted as constant
+const COLA_TOLERANCE: f64 = 1e-3;
+
+/// Floating point type the analysis core runs on
+/// The plugin uses f32; offline tooling can run the same code in f64
+pub trait Sample: Float + FloatConst {
+    /// Convert a constant, rounding it for f32
+    fn constant(value: f64) -> Self;
+}
+
+impl Sample for f32 {
+    fn constant(value: f64) -> Self {
+        value as f32
+    }
+}
+
+impl Sample for f64 {
+    fn constant(value: f64) -> Self {
+        value
+    }
+}
 
 /// Window function types for FFT analysis
 #[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
@@ -23,9 +44,9 @@ pub enum WindowType {
 
 impl WindowType {
     /// Generate window coefficients for this window type
-    pub fn generate(self, window_size: usize) -> Vec<f32> {
+    pub fn generate<T: Sample>(self, window_size: usize) -> Vec<T> {
         match self {
-            Self::Rectangular => vec![1.0; window_size],
+            Self::Rectangular => vec![T::one(); window_size],
             Self::Hann => generate_hann_window(window_size),
             Self::Hamming => generate_hamming_window(window_size),
             Self::Blackman => generate_blackman_window(window_size),
@@ -56,13 +77,14 @@ impl WindowType {
 /// - Better frequency isolation than rectangular window
 /// - Slightly wider peaks than rectangular (4 bins vs 2 bins)
 /// - Good general-purpose window for audio analysis
-pub fn generate_hann_window(window_size: usize) -> Vec<f32> {
-    let window_size_f32 = window_size as f32;
+pub fn generate_hann_window<T: Sample>(window_size: usize) -> Vec<T> {
+    let window_size_t = T::constant(window_size as f64);
+    let two_pi = T::constant(2.0) * T::PI();
 
     (0..window_size)
         .map(|i| {
-            let position = i as f32 / window_size_f32;
-            0.5 * (1.0 - cosf(2.0 * PI * position))
+            let position = T::constant(i as f64) / window_size_t;
+            T::constant(0.5) * (T::one() - (two_pi * position).cos())
         })
         .collect()
 }
@@ -83,13 +105,14 @@ pub fn generate_hann_window(window_size: usize) -> Vec<f32> {
 /// - Better for detecting weak signals near strong ones
 /// - Good for harmonic analysis where sidelobe rejection matters
 /// - Preferred when frequency accuracy more important than amplitude accuracy
-pub fn generate_hamming_window(window_size: usize) -> Vec<f32> {
-    let window_size_f32 = window_size as f32;
+pub fn generate_hamming_window<T: Sample>(window_size: usize) -> Vec<T> {
+    let window_size_t = T::constant(window_size as f64);
+    let two_pi = T::constant(2.0) * T::PI();
 
     (0..window_size)
         .map(|i| {
-            let position = i as f32 / window_size_f32;
-            0.54 - 0.46 * cosf(2.0 * PI * position)
+            let position = T::constant(i as f64) / window_size_t;
+            T::constant(0.54) - T::constant(0.46) * (two_pi * position).cos()
         })
         .collect()
 }
@@ -109,13 +132,133 @@ pub fn generate_hamming_window(window_size: usize) -> Vec<f32> {
 /// - High-frequency analysis where leakage is problematic
 /// - When you need clean spectrum display
 /// - Trade frequency resolution for cleaner appearance
-pub fn generate_blackman_window(window_size: usize) -> Vec<f32> {
-    let window_size_f32 = window_size as f32;
+pub fn generate_blackman_window<T: Sample>(window_size: usize) -> Vec<T> {
+    let window_size_t = T::constant(window_size as f64);
+    let two_pi = T::constant(2.0) * T::PI();
 
     (0..window_size)
         .map(|i| {
-            let position = i as f32 / window_size_f32;
-            0.42 - 0.5 * cosf(2.0 * PI * position) + 0.08 * cosf(4.0 * PI * position)
+            let position = T::constant(i as f64) / window_size_t;
+            T::constant(0.42) - T::constant(0.5) * (two_pi * position).cos()
+                + T::constant(0.08) * (T::constant(2.0) * two_pi * position).cos()
         })
         .collect()
 }
+
+/// Checks the constant-overlap-add (COLA) condition for a window and hop size
+///
+/// When COLA holds, the shifted copies of the window sum to a constant, so every input
+/// sample carries the same total weight across frames. Energy averaged over frames is
+/// then unbiased, with no sample counted more or less than its neighbours.
+/// Allocation-free, so it can run on the audio thread.
+///
+/// # Parameters
+/// * `window` - Window coefficients as generated by [`WindowType::generate`]
+/// * `hop_size` - Samples between the starts of consecutive frames
+///
+/// # Valid Combinations (periodic windows, as generated here)
+/// - Rectangular: hop = N (no overlap)
+/// - Hann: hop = N/2 (50%), N/4 (75%), or any N/2k
+/// - Hamming: hop = N/2 (50%), N/4 (75%)
+/// - Blackman: hop = N/3 (66.7%), N/4 (75%)
+pub fn is_cola<T: Sample>(window: &[T], hop_size: usize) -> bool {
+    if hop_size == 0 || hop_size > window.len() {
+        return false;
+    }
+
+    // Sum of every window copy covering a sample at each position within one hop
+    let overlap_sum = |phase: usize| -> T {
+        window
+            .iter()
+            .skip(phase)
+            .step_by(hop_size)
+            .fold(T::zero(), |sum, &coefficient| sum + coefficient)
+    };
+
+    let expected = overlap_sum(0);
+    if expected <= T::zero() {
+        return false;
+    }
+    let tolerance = expected * T::constant(COLA_TOLERANCE);
+    (1..hop_size).all(|phase| (overlap_sum(phase) - expected).abs() <= tolerance)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// Window length used for the COLA checks; divisible by the hops listed on `is_cola`
+    const TEST_WINDOW_SIZE: usize = 1536;
+
+    #[test]
+    fn documented_window_hops_are_cola() {
+        let n = TEST_WINDOW_SIZE;
+        let valid = [
+            (WindowType::Rectangular, n),
+            (WindowType::Hann, n / 2),
+            (WindowType::Hann, n / 4),
+            (WindowType::Hann, n / 8),
+            (WindowType::Hamming, n / 2),
+            (WindowType::Hamming, n / 4),
+            (WindowType::Blackman, n / 3),
+            (WindowType::Blackman, n / 4),
+        ];
+        for (window_type, hop_size) in valid {
+            assert!(
+                is_cola(&window_type.generate::<f64>(n), hop_size),
+                "{window_type:?} at hop {hop_size} should be COLA"
+            );
+            assert!(
+                is_cola(&window_type.generate::<f32>(n), hop_size),
+                "{window_type:?} at hop {hop_size} should be COLA in f32"
+            );
+        }
+    }
+
+    #[test]
+    fn other_window_hops_are_not_cola() {
+        let n = TEST_WINDOW_SIZE;
+        let invalid = [
+            (WindowType::Hann, n),
+            (WindowType::Hann, n / 3 * 2),
+            (WindowType::Hamming, n),
+            (WindowType::Blackman, n / 2),
+        ];
+        for (window_type, hop_size) in invalid {
+            assert!(
+                !is_cola(&window_type.generate::<f64>(n), hop_size),
+                "{window_type:?} at hop {hop_size} should not be COLA"
+            );
+        }
+
+        let hann = WindowType::Hann.generate::<f64>(n);
+        assert!(!is_cola(&hann, 0));
+        assert!(!is_cola(&hann, n + 1));
+        assert!(
+            !is_cola(&[0.0_f64; 16], 4),
+            "an all-zero window covers nothing"
+        );
+    }
+
+    #[test]
+    fn cola_hann_frames_overlap_add_to_a_constant() {
+        // Overlap-add the window itself, as a constant signal would be weighted
+        let n = TEST_WINDOW_SIZE;
+        let hop_size = n / 4;
+        let window = WindowType::Hann.generate::<f64>(n);
+        let mut sum = vec![0.0; n * 4];
+        for start in (0..=sum.len() - n).step_by(hop_size) {
+            for (total, coefficient) in sum[start..start + n].iter_mut().zip(&window) {
+                *total += coefficient;
+            }
+        }
+
+        // Away from the ends, where fewer frames overlap, every sample has the same weight
+        for (index, &total) in sum.iter().enumerate().take(sum.len() - n).skip(n) {
+            assert!(
+                (total - 2.0).abs() < 1e-9,
+                "sample {index} weighted {total}"
+            );
+        }
+    }
+}
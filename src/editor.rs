@@ -1,19 +1,39 @@
-use crate::audio::meter::MeterConsumer;
-use crate::audio::spectrum::SpectrumConsumer;
-use crate::ui::{GridOverlay, MeterDisplay, SpectrumDisplay, UITheme, GridShader};
-use crate::SAPluginParams;
+use crate::audio::errors::{ReadFailureTracker, SpectrumError};
+use crate::audio::meter::{
+    MeterConsumer, MonoDetector, DC_OFFSET_WARNING_RATIO, MONO_DETECT_TIME, MONO_MAX_DIFFERENCE_DB,
+    MONO_MIN_CORRELATION,
+};
+use crate::audio::spectrum::{
+    calibration_frequency_hz, calibration_level_db, AnalysisTiming, BandLevels, SpectrumConsumer,
+    CALIBRATION_EXPECTED_DB, CALIBRATION_TOLERANCE_DB,
+};
+use crate::ui::frequency_markers::FrequencyMarker;
+use crate::ui::label_layout::AxisGutters;
+use crate::ui::png_export::{export_directory, export_path};
+use crate::ui::spectrum_display::CursorSnap;
+use crate::ui::{
+    BassInspector, GridLabels, GridOverlay, GridShader, LevelHistoryDisplay, MeterDisplay,
+    PeakHistoryDisplay, SpectrumDisplay, TiltMeter, UITheme,
+};
+use crate::{AnalyserTask, MeterScale, SAPlugin, SAPluginParams};
 
 use atomic_float::AtomicF32;
-use nih_plug::context::gui::GuiContext;
+use nih_plug::context::gui::{AsyncExecutor, GuiContext};
 use nih_plug_iced::executor::Default;
 use nih_plug_iced::futures::Subscription;
 use nih_plug_iced::widget::canvas::Canvas;
-use nih_plug_iced::widget::{column, container, row, stack, text, shader};
+use nih_plug_iced::widget::{
+    button, column, container, mouse_area, row, shader, stack, text, text_input, tooltip,
+};
 use nih_plug_iced::widgets::ResizeHandle;
-use nih_plug_iced::{window, IcedState, Padding};
-use nih_plug_iced::{alignment::Horizontal, Element, IcedEditor, Length, Renderer, Task, Theme};
+use nih_plug_iced::{
+    alignment::Horizontal, alignment::Vertical, Element, IcedEditor, Length, Renderer, Task, Theme,
+};
+use nih_plug_iced::{event, keyboard, window, Event, IcedState, Padding};
+use std::path::Path;
 use std::sync::atomic::{AtomicBool, Ordering};
 use std::sync::Arc;
+use std::time::{Instant, SystemTime};
 
 #[derive(Debug, Clone)]
 pub enum Message {
@@ -23,6 +43,36 @@ pub enum Message {
     RequestResize(nih_plug_iced::Size),
     /// Window was actually resized (from baseview/iced event)
     WindowResized(nih_plug_iced::Size),
+    /// Pause or resume analysis (skips FFT work and freezes the display)
+    TogglePause,
+    /// Clear the level history strip
+    ClearLevelHistory,
+    /// Clear the peak history strip and its clip latches
+    ClearPeakHistory,
+    /// Restart the long-term min/max envelope
+    ResetEnvelope,
+    /// Restart the DC offset measurement
+    ResetDcOffset,
+    /// Show or hide the explanation under the MONO badge
+    ToggleMonoInfo,
+    /// Clear the held meter peak and the clip latches
+    ResetPeakHold,
+    /// Held modifier keys changed; Ctrl and Alt snap the cursor readout
+    ModifiersChanged(keyboard::Modifiers),
+    /// Show or hide the frequency marker editor
+    ToggleMarkerEditor,
+    /// Append a frequency marker at the default frequency
+    AddFrequencyMarker,
+    /// Delete the frequency marker at this index
+    RemoveFrequencyMarker(usize),
+    /// Frequency text of a marker was edited (applied once it parses)
+    FrequencyMarkerEdited(usize, String),
+    /// Label of a marker was edited
+    FrequencyMarkerLabelEdited(usize, String),
+    /// Step a marker to the next colour
+    CycleFrequencyMarkerColor(usize),
+    /// Save a PNG of the spectrum, grid and meters
+    ExportPng,
 }
 
 /// Grouped UI data structure
@@ -33,6 +83,7 @@ pub struct EditorData {
     pub plugin_params: Arc<SAPluginParams>,
     pub sample_rate: Arc<AtomicF32>,
     pub process_stopped: Arc<AtomicBool>,
+    pub analysis_paused: Arc<AtomicBool>,
 
     /// DISPLAY DATA - Separated communication channels
     pub spectrum_output: SpectrumConsumer,
@@ -44,9 +95,11 @@ pub struct EditorInitFlags {
     pub plugin_params: Arc<SAPluginParams>,
     pub sample_rate: Arc<AtomicF32>,
     pub process_stopped: Arc<AtomicBool>,
+    pub analysis_paused: Arc<AtomicBool>,
     pub spectrum_output: SpectrumConsumer,
     pub meter_output: MeterConsumer,
     pub iced_state: Arc<IcedState>,
+    pub async_executor: AsyncExecutor<SAPlugin>,
 }
 
 pub struct PluginEditor {
@@ -57,12 +110,42 @@ pub struct PluginEditor {
     spectrum_display: SpectrumDisplay,
     grid_overlay: GridOverlay,
     meter_display: MeterDisplay,
+    level_history: LevelHistoryDisplay,
+    peak_history: PeakHistoryDisplay,
+
+    /// Low/mid/high band levels smoothed with the meter ballistics
+    band_levels: BandLevels,
+
+    /// Spectral slope relative to the tilt target
+    tilt_meter: TiltMeter,
+
+    /// Watches for identical L/R channels to light the MONO badge
+    mono_detector: MonoDetector,
+    /// Whether the MONO badge's explanation is shown
+    mono_info_open: bool,
+
+    /// Last seen value of the reset peak hold parameter, to act on its rising edge
+    peak_hold_reset_param: bool,
+
+    /// Counts `Message::Tick`s, so the meter can tell a repeated update from a new frame
+    tick_count: u64,
+
+    /// Whether the frequency marker editor is shown
+    marker_editor_open: bool,
+    /// Frequency text of each marker row as typed, which may not parse yet
+    marker_frequency_inputs: Vec<String>,
+    /// Consecutive failed reads of the meter state
+    meter_read_failures: ReadFailureTracker,
 
     /// GPU SHADERS - High performance rendering
     grid_shader: GridShader,
+    /// Tick labels and axis titles drawn over the shader grid
+    grid_labels: GridLabels,
 
     /// GUI CONTEXT
     context: Arc<dyn GuiContext>,
+    /// Runs screenshot exports on the plugin's background thread
+    async_executor: AsyncExecutor<SAPlugin>,
 
     /// ICED STATE - For window resize
     iced_state: Arc<IcedState>,
@@ -77,58 +160,508 @@ pub fn create_spectrum_canvas(
         .height(Length::Fill)
 }
 
-/// Create dB value display text widget
-pub fn create_db_display(peak_hold_db: f32) -> Element<'static, Message, Theme, Renderer> {
-    text(format!("{:.1} dB", peak_hold_db))
+/// Create dB value display text widget, clicking it clears the held peak
+/// `peak_hold_db` is in dBFS and is shown on the selected meter scale
+/// Infinite hold shows two decimals, since the value is read as a song's worst case
+pub fn create_db_display(
+    peak_hold_db: f32,
+    scale: MeterScale,
+    alignment_dbfs: f32,
+    infinite_hold: bool,
+) -> Element<'static, Message, Theme, Renderer> {
+    let decimals = if infinite_hold { 2 } else { 1 };
+    mouse_area(
+        text(format!(
+            "{:.*} {}",
+            decimals,
+            scale.from_dbfs(peak_hold_db, alignment_dbfs),
+            scale.unit()
+        ))
         .size(6.0)
-        .color(UITheme::TEXT_SECONDARY)
-        .into()
+        .color(UITheme::TEXT_SECONDARY),
+    )
+    .on_press(Message::ResetPeakHold)
+    .into()
+}
+
+/// Create low/mid/high band level readouts
+pub fn create_band_readouts(levels: BandLevels) -> Element<'static, Message, Theme, Renderer> {
+    let readout = |label: &str, level_db: f32| {
+        text(format!("{} {:.0}", label, level_db))
+            .size(6.0)
+            .color(UITheme::TEXT_SECONDARY)
+    };
+
+    column![
+        readout("L", levels.low_db),
+        readout("M", levels.mid_db),
+        readout("H", levels.high_db),
+    ]
+    .align_x(Horizontal::Center)
+    .width(Length::Fill)
+    .into()
+}
+
+/// Create tilt meter canvas widget
+/// The tooltip names the target slope the bar is centred on
+pub fn create_tilt_meter(
+    tilt_meter: &TiltMeter,
+    target_db_per_octave: f32,
+) -> Element<'_, Message, Theme, Renderer> {
+    tooltip(
+        Canvas::new(tilt_meter)
+            .width(Length::Fill)
+            .height(Length::Fixed(UITheme::TILT_METER_HEIGHT)),
+        container(
+            text(format!(
+                "Slope vs {:+.1} dB/oct target\nRight: brighter, left: darker",
+                target_db_per_octave
+            ))
+            .size(10.0)
+            .color(UITheme::BADGE_TEXT),
+        )
+        .padding(Padding::default().top(2).bottom(2).left(6).right(6))
+        .style(UITheme::badge),
+        tooltip::Position::Left,
+    )
+    .into()
 }
 
 /// Create level meter canvas widget
-pub fn create_meter_canvas(meter_display: &MeterDisplay) -> Canvas<&MeterDisplay, Message> {
-    Canvas::new(meter_display)
-        .width(Length::Fixed(UITheme::METER_WIDTH))
+/// The tooltip shows the active attack/release times
+pub fn create_meter_canvas(
+    meter_display: &MeterDisplay,
+    attack_ms: f32,
+    release_ms: f32,
+) -> Element<'_, Message, Theme, Renderer> {
+    tooltip(
+        Canvas::new(meter_display)
+            .width(Length::Fixed(UITheme::METER_WIDTH))
+            .height(Length::Fill),
+        container(
+            text(format!(
+                "Attack {:.1} ms\nRelease {:.0} ms",
+                attack_ms, release_ms
+            ))
+            .size(10.0)
+            .color(UITheme::BADGE_TEXT),
+        )
+        .padding(Padding::default().top(2).bottom(2).left(6).right(6))
+        .style(UITheme::badge),
+        tooltip::Position::Left,
+    )
+    .into()
+}
+
+/// Create "MONO" badge, lit while both channels carry the same signal
+/// Clicking it shows (or hides) what the detection looks for underneath
+pub fn create_mono_badge(
+    is_mono: bool,
+    show_info: bool,
+) -> Element<'static, Message, Theme, Renderer> {
+    let color = if is_mono {
+        UITheme::BADGE_TEXT
+    } else {
+        UITheme::TEXT_SECONDARY
+    };
+
+    let badge = mouse_area(
+        container(text("MONO").size(7.0).color(color))
+            .width(Length::Fill)
+            .align_x(Horizontal::Center),
+    )
+    .on_press(Message::ToggleMonoInfo);
+    if !show_info {
+        return badge.into();
+    }
+
+    let info = container(
+        text(format!(
+            "Lit when L/R correlation is above {}\n\
+             and peak and RMS have matched\n\
+             within {:.1} dB for {:.0} s",
+            MONO_MIN_CORRELATION,
+            MONO_MAX_DIFFERENCE_DB,
+            MONO_DETECT_TIME.as_secs_f32()
+        ))
+        .size(10.0)
+        .color(UITheme::BADGE_TEXT),
+    )
+    .padding(Padding::default().top(2).bottom(2).left(6).right(6))
+    .style(UITheme::badge);
+
+    column![badge, mouse_area(info).on_press(Message::ToggleMonoInfo)]
+        .align_x(Horizontal::Center)
+        .into()
+}
+
+/// Create button that restarts the min/max envelope
+pub fn create_envelope_reset_button() -> Element<'static, Message, Theme, Renderer> {
+    button(text("RESET").size(7.0).color(UITheme::TEXT_SECONDARY))
+        .on_press(Message::ResetEnvelope)
+        .padding(2)
+        .width(Length::Fill)
+        .into()
+}
+
+/// Create level history strip, cleared by clicking it
+pub fn create_level_history_strip(
+    level_history: &LevelHistoryDisplay,
+) -> Element<'_, Message, Theme, Renderer> {
+    mouse_area(
+        Canvas::new(level_history)
+            .width(Length::Fill)
+            .height(Length::Fixed(UITheme::LEVEL_HISTORY_HEIGHT)),
+    )
+    .on_press(Message::ClearLevelHistory)
+    .into()
+}
+
+/// Create the bass inspector panel, taking a quarter of the spectrum column
+pub fn create_bass_inspector_strip(
+    spectrum_display: &SpectrumDisplay,
+) -> Element<'_, Message, Theme, Renderer> {
+    Canvas::new(BassInspector::new(spectrum_display))
+        .width(Length::Fill)
+        .height(Length::FillPortion(1))
+        .into()
+}
+
+/// Create the per-channel peak history strip; clicking clears it and the clip latches
+pub fn create_peak_history_strip(
+    peak_history: &PeakHistoryDisplay,
+) -> Element<'_, Message, Theme, Renderer> {
+    mouse_area(
+        Canvas::new(peak_history)
+            .width(Length::Fill)
+            .height(Length::Fixed(UITheme::PEAK_HISTORY_HEIGHT)),
+    )
+    .on_press(Message::ClearPeakHistory)
+    .into()
+}
+
+/// Create pause/resume toggle button
+pub fn create_pause_button(paused: bool) -> Element<'static, Message, Theme, Renderer> {
+    let label = if paused { "RUN" } else { "PAUSE" };
+    button(text(label).size(7.0).color(UITheme::TEXT_SECONDARY))
+        .on_press(Message::TogglePause)
+        .padding(2)
+        .width(Length::Fill)
+        .into()
+}
+
+/// Create "PAUSED" badge shown in the top-left corner of the spectrum
+pub fn create_paused_badge() -> Element<'static, Message, Theme, Renderer> {
+    container(
+        container(text("PAUSED").size(10.0).color(UITheme::BADGE_TEXT))
+            .padding(Padding::default().top(2).bottom(2).left(6).right(6))
+            .style(UITheme::badge),
+    )
+    .width(Length::Fill)
+    .height(Length::Fill)
+    .align_x(Horizontal::Left)
+    .align_y(Vertical::Top)
+    .padding(8)
+    .into()
+}
+
+/// Create the button that shows or hides the frequency marker editor
+pub fn create_markers_button(editor_open: bool) -> Element<'static, Message, Theme, Renderer> {
+    let label = if editor_open { "DONE" } else { "MARKERS" };
+    button(text(label).size(7.0).color(UITheme::TEXT_SECONDARY))
+        .on_press(Message::ToggleMarkerEditor)
+        .padding(2)
+        .width(Length::Fill)
+        .into()
+}
+
+/// Create the button that saves a PNG of the analyser
+/// Hovering shows where exports are saved
+pub fn create_export_button(directory: &Path) -> Element<'static, Message, Theme, Renderer> {
+    tooltip(
+        button(text("PNG").size(7.0).color(UITheme::TEXT_SECONDARY))
+            .on_press(Message::ExportPng)
+            .padding(2)
+            .width(Length::Fill),
+        container(
+            text(format!("Save a screenshot to\n{}", directory.display()))
+                .size(10.0)
+                .color(UITheme::BADGE_TEXT),
+        )
+        .padding(Padding::default().top(2).bottom(2).left(6).right(6))
+        .style(UITheme::badge),
+        tooltip::Position::Left,
+    )
+    .into()
+}
+
+/// Create the frequency marker editor in the bottom-left corner of the spectrum
+/// One row per marker: frequency, label, colour swatch (click to cycle) and delete
+pub fn create_marker_editor(
+    markers: &[FrequencyMarker],
+    frequency_inputs: &[String],
+) -> Element<'static, Message, Theme, Renderer> {
+    let mut rows = column![text("MARKERS").size(9.0).color(UITheme::BADGE_TEXT)];
+    for (index, (marker, frequency_input)) in markers.iter().zip(frequency_inputs).enumerate() {
+        rows = rows.push(
+            row![
+                text_input("Hz", frequency_input)
+                    .on_input(move |value| Message::FrequencyMarkerEdited(index, value))
+                    .size(9.0)
+                    .padding(2)
+                    .width(Length::Fixed(52.0)),
+                text_input("Label", &marker.label)
+                    .on_input(move |value| Message::FrequencyMarkerLabelEdited(index, value))
+                    .size(9.0)
+                    .padding(2)
+                    .width(Length::Fixed(90.0)),
+                button(text("■").size(9.0).color(marker.color.color()))
+                    .on_press(Message::CycleFrequencyMarkerColor(index))
+                    .padding(2),
+                button(text("×").size(9.0).color(UITheme::TEXT_SECONDARY))
+                    .on_press(Message::RemoveFrequencyMarker(index))
+                    .padding(2),
+            ]
+            .spacing(2)
+            .align_y(Vertical::Center),
+        );
+    }
+    rows = rows.push(
+        button(text("+ ADD").size(7.0).color(UITheme::TEXT_SECONDARY))
+            .on_press(Message::AddFrequencyMarker)
+            .padding(2),
+    );
+
+    container(
+        container(rows.spacing(2))
+            .padding(Padding::default().top(4).bottom(4).left(6).right(6))
+            .style(UITheme::badge),
+    )
+    .width(Length::Fill)
+    .height(Length::Fill)
+    .align_x(Horizontal::Left)
+    .align_y(Vertical::Bottom)
+    .padding(8)
+    .into()
+}
+
+/// Create read failure warning, shown under the status line while shared data keeps
+/// failing to read, so a blank display isn't mistaken for silence
+pub fn create_read_warning_badge(
+    error: &SpectrumError,
+) -> Element<'static, Message, Theme, Renderer> {
+    container(
+        text(error.to_string())
+            .size(10.0)
+            .color(UITheme::READ_WARNING),
+    )
+    .padding(Padding::default().top(2).bottom(2).left(6).right(6))
+    .style(UITheme::badge)
+    .into()
+}
+
+/// Create band energy alarm badge, shown under the status line
+pub fn create_band_alarm_badge(
+    level_db: f32,
+    threshold_db: f32,
+    low_hz: f32,
+    high_hz: f32,
+) -> Element<'static, Message, Theme, Renderer> {
+    let label = format!(
+        "BAND {:.0}-{:.0} Hz: {:.1} dB > {:.1}",
+        low_hz.min(high_hz),
+        low_hz.max(high_hz),
+        level_db,
+        threshold_db
+    );
+
+    container(text(label).size(10.0).color(UITheme::BAND_ALARM))
+        .padding(Padding::default().top(2).bottom(2).left(6).right(6))
+        .style(UITheme::badge)
+        .into()
+}
+
+/// Create calibration self-test readout with pass/fail against the expected level
+pub fn create_calibration_badge(
+    measured_db: f32,
+    tone_hz: f32,
+) -> Element<'static, Message, Theme, Renderer> {
+    let passed = (measured_db - CALIBRATION_EXPECTED_DB).abs() <= CALIBRATION_TOLERANCE_DB;
+    let (verdict, color) = if passed {
+        ("PASS", UITheme::CALIBRATION_PASS)
+    } else {
+        ("FAIL", UITheme::CALIBRATION_FAIL)
+    };
+    let label = format!(
+        "CAL {:.1} Hz: {:.2} dB (expect {:.2} ±{:.1}) {}",
+        tone_hz, measured_db, CALIBRATION_EXPECTED_DB, CALIBRATION_TOLERANCE_DB, verdict
+    );
+
+    container(
+        container(text(label).size(10.0).color(color))
+            .padding(Padding::default().top(2).bottom(2).left(6).right(6))
+            .style(UITheme::badge),
+    )
+    .width(Length::Fill)
+    .height(Length::Fill)
+    .align_x(Horizontal::Center)
+    .align_y(Vertical::Top)
+    .padding(8)
+    .into()
+}
+
+/// Summarise the settings that change how the trace reads, e.g. "+4.5 dB/oct · -90..0 dB · 512 bins"
+pub fn describe_display_settings(params: &SAPluginParams) -> String {
+    let tilt = params.tilt.value().to_db_per_octave();
+    let (min_db, max_db) = params.range.value().to_db_range();
+    let bins = params.resolution.value().to_bin_count();
+    format!(
+        "{:+.1} dB/oct · {:.0}..{:.0} dB · {} bins",
+        tilt, min_db, max_db, bins
+    )
+}
+
+/// Summarise the analysis time resolution and smoothing, e.g.
+/// "186 ms window · 93 ms hop · 500 ms release", so a delayed transient isn't a surprise
+pub fn describe_analysis_timing(
+    params: &SAPluginParams,
+    sample_rate: f32,
+    fft_size: usize,
+) -> String {
+    let timing = AnalysisTiming::new(
+        sample_rate,
+        fft_size,
+        params.fft_overlap.value(),
+        params.speed.value(),
+    );
+    format!(
+        "{:.0} ms window · {:.0} ms hop · {:.0} ms release",
+        timing.window_ms, timing.hop_ms, timing.release_ms
+    )
+}
+
+/// Create per-channel DC offset readout, e.g. "DC L: -0.3% R: 0.0%"
+/// Highlighted once either channel exceeds the warning level; click to restart the measurement
+pub fn create_dc_offset_readout(
+    dc_left: f32,
+    dc_right: f32,
+) -> Element<'static, Message, Theme, Renderer> {
+    let warning = dc_left.abs().max(dc_right.abs()) > DC_OFFSET_WARNING_RATIO;
+    let color = if warning {
+        UITheme::DC_OFFSET_WARNING
+    } else {
+        UITheme::TEXT_SECONDARY
+    };
+    let label = format!("DC L: {:.1}% R: {:.1}%", dc_left * 100.0, dc_right * 100.0);
+
+    mouse_area(
+        container(text(label).size(10.0).color(color))
+            .padding(Padding::default().top(2).bottom(2).left(6).right(6))
+            .style(UITheme::badge),
+    )
+    .on_press(Message::ResetDcOffset)
+    .into()
+}
+
+/// Create compact status line showing the active display configuration
+/// The analysis timing and DC offset readout sit directly beneath the settings summary
+pub fn create_status_line(
+    status: String,
+    timing: String,
+    dc_offset_readout: Element<'static, Message, Theme, Renderer>,
+    band_alarm: Option<Element<'static, Message, Theme, Renderer>>,
+    read_warning: Option<Element<'static, Message, Theme, Renderer>>,
+) -> Element<'static, Message, Theme, Renderer> {
+    let mut lines = column![
+        container(text(status).size(10.0).color(UITheme::BADGE_TEXT))
+            .padding(Padding::default().top(2).bottom(2).left(6).right(6))
+            .style(UITheme::badge),
+        container(text(timing).size(10.0).color(UITheme::TEXT_SECONDARY))
+            .padding(Padding::default().top(2).bottom(2).left(6).right(6))
+            .style(UITheme::badge),
+        dc_offset_readout,
+    ];
+    if let Some(band_alarm) = band_alarm {
+        lines = lines.push(band_alarm);
+    }
+    if let Some(read_warning) = read_warning {
+        lines = lines.push(read_warning);
+    }
+
+    container(lines.spacing(2).align_x(Horizontal::Right))
+        .width(Length::Fill)
         .height(Length::Fill)
+        .align_x(Horizontal::Right)
+        .align_y(Vertical::Top)
+        .padding(8)
+        .into()
 }
 
 /// Create right panel layout with knob and meter
 pub fn create_right_panel<'a>(
+    pause_button: Element<'a, Message, Theme, Renderer>,
+    envelope_reset_button: Option<Element<'a, Message, Theme, Renderer>>,
+    markers_button: Element<'a, Message, Theme, Renderer>,
+    export_button: Element<'a, Message, Theme, Renderer>,
     db_display: Element<'a, Message, Theme, Renderer>,
-    meter_canvas: Canvas<&'a MeterDisplay, Message>,
+    band_readouts: Element<'a, Message, Theme, Renderer>,
+    tilt_meter: Element<'a, Message, Theme, Renderer>,
+    mono_badge: Element<'a, Message, Theme, Renderer>,
+    meter_canvas: Element<'a, Message, Theme, Renderer>,
 ) -> Element<'a, Message, Theme, Renderer> {
-    column![
-        container(db_display)
-            .width(Length::Fill)
-            .align_x(Horizontal::Center)
-            .padding(UITheme::PADDING_SMALL),
-        container(meter_canvas)
-            .width(Length::Fill)
-            .padding(UITheme::PADDING_SMALL)
-    ]
-    .spacing(UITheme::PADDING_SMALL)
-    .into()
+    let mut panel = column![pause_button];
+    if let Some(envelope_reset_button) = envelope_reset_button {
+        panel = panel.push(envelope_reset_button);
+    }
+
+    panel
+        .push(markers_button)
+        .push(export_button)
+        .push(
+            container(db_display)
+                .width(Length::Fill)
+                .align_x(Horizontal::Center)
+                .padding(UITheme::PADDING_SMALL),
+        )
+        .push(band_readouts)
+        .push(tilt_meter)
+        .push(mono_badge)
+        .push(
+            container(meter_canvas)
+                .width(Length::Fill)
+                .padding(UITheme::PADDING_SMALL),
+        )
+        .spacing(UITheme::PADDING_SMALL)
+        .into()
 }
 
 /// Create main layout container with stacked canvases
+/// Each enabled strip sits in its own row beneath the spectrum
+/// The spectrum fills three portions, so a `FillPortion(1)` strip gets a quarter of the height
 pub fn create_main_layout_with_stack<'a>(
     layered_spectrum: nih_plug_iced::widget::Stack<'a, Message, Theme, Renderer>,
+    strips: Vec<Element<'a, Message, Theme, Renderer>>,
     right_panel: Element<'a, Message, Theme, Renderer>,
 ) -> Element<'a, Message, Theme, Renderer> {
+    let mut spectrum_column = column![
+        // Inner container for the stack without padding
+        container(layered_spectrum)
+            .width(Length::Fill)
+            .height(Length::FillPortion(3))
+            .style(UITheme::background_dark)
+    ];
+    for strip in strips {
+        spectrum_column = spectrum_column.push(strip);
+    }
+
     container(
         row![
             // Outer container with padding to shift the entire stack
-            container(
-                // Inner container for the stack without padding
-                container(layered_spectrum)
-                    .width(Length::Fill)
-                    .height(Length::Fill)
-                    .style(UITheme::background_dark)
-            )
-            .width(Length::Fill)
-            .height(Length::Fill)
-            .padding(Padding::default().top(5).left(10))
-            .style(UITheme::background_dark),
+            container(spectrum_column.spacing(UITheme::PADDING_SMALL))
+                .width(Length::Fill)
+                .height(Length::Fill)
+                .padding(Padding::default().top(5).left(10))
+                .style(UITheme::background_dark),
             container(right_panel)
                 .width(Length::Fixed(UITheme::METER_WIDTH + 15.0))
                 .height(Length::Fill)
@@ -143,6 +676,27 @@ pub fn create_main_layout_with_stack<'a>(
     .into()
 }
 
+impl PluginEditor {
+    /// Clear the held peak (both channels and the overall value), the clip latches and
+    /// the spectrum captured on peak
+    /// Runs on the UI thread in one step, so no partially cleared state is ever drawn
+    fn reset_peak_hold(&mut self) {
+        self.editor_data.meter_output.reset_peak_hold();
+        self.peak_history.clear_clip_latches();
+        self.spectrum_display.clear_captured_spectrum();
+    }
+
+    /// Change the persisted frequency markers and hand the result to the canvas grid
+    /// The spectrum reads the markers straight from the parameters when it draws
+    fn edit_frequency_markers(&mut self, edit: impl FnOnce(&mut Vec<FrequencyMarker>)) {
+        let Ok(mut markers) = self.editor_data.plugin_params.frequency_markers.write() else {
+            return;
+        };
+        edit(&mut markers);
+        self.grid_overlay.set_frequency_markers(markers.clone());
+    }
+}
+
 impl IcedEditor for PluginEditor {
     type Executor = Default;
     type Message = Message;
@@ -158,25 +712,53 @@ impl IcedEditor for PluginEditor {
             plugin_params: initialization_flags.plugin_params,
             sample_rate: initialization_flags.sample_rate,
             process_stopped: initialization_flags.process_stopped,
+            analysis_paused: initialization_flags.analysis_paused,
             spectrum_output: initialization_flags.spectrum_output,
             meter_output: initialization_flags.meter_output,
         };
 
+        let frequency_markers = editor_data
+            .plugin_params
+            .frequency_markers
+            .read()
+            .map(|markers| markers.clone())
+            .unwrap_or_default();
+        let mut grid_overlay = GridOverlay::new(editor_data.plugin_params.clone());
+        grid_overlay.set_frequency_markers(frequency_markers.clone());
+
         let editor = Self {
             // DISPLAY COMPONENTS - Pure rendering with new communication channels
             spectrum_display: SpectrumDisplay::new(
                 editor_data.spectrum_output.clone(),
-                editor_data.sample_rate.clone(),
                 editor_data.plugin_params.clone(),
             ),
-            grid_overlay: GridOverlay::new(),
-            meter_display: MeterDisplay::new(editor_data.meter_output.clone()),
+            grid_overlay,
+            meter_display: MeterDisplay::new(
+                editor_data.meter_output.clone(),
+                editor_data.plugin_params.clone(),
+            ),
+            level_history: LevelHistoryDisplay::new(),
+            peak_history: PeakHistoryDisplay::new(),
+            band_levels: BandLevels::silence(),
+            tilt_meter: TiltMeter::new(),
+            mono_detector: MonoDetector::new(),
+            mono_info_open: false,
+            peak_hold_reset_param: false,
+            tick_count: 0,
+            marker_editor_open: false,
+            meter_read_failures: ReadFailureTracker::new("meter state"),
+            marker_frequency_inputs: frequency_markers
+                .iter()
+                .map(|marker| format!("{}", marker.frequency_hz))
+                .collect(),
 
             // GPU SHADERS - High performance rendering
-            grid_shader: GridShader::new(),
+            grid_shader: GridShader::new(editor_data.plugin_params.clone()),
+            grid_labels: GridLabels::new(editor_data.plugin_params.clone()),
 
             // ICED STATE
             iced_state: initialization_flags.iced_state.clone(),
+            async_executor: initialization_flags.async_executor,
 
             // GROUPED DATA
             editor_data,
@@ -193,8 +775,86 @@ impl IcedEditor for PluginEditor {
     fn update(&mut self, message: Self::Message) -> Task<Self::Message> {
         match message {
             Message::Tick => {
-                // Request a redraw by returning none
-                // The canvas will automatically redraw with latest spectrum data
+                self.tick_count = self.tick_count.wrapping_add(1);
+
+                // Pull the latest spectrum so the canvas redraws with fresh data
+                self.spectrum_display.update();
+                let params = &self.editor_data.plugin_params;
+                self.editor_data
+                    .meter_output
+                    .set_peak_decay(params.peak_decay.value());
+                self.editor_data
+                    .meter_output
+                    .set_ballistics(params.meter_attack.value(), params.meter_release.value());
+                self.editor_data.meter_output.set_silence_decay(
+                    params.silence_decay.value(),
+                    params.silence_threshold.value(),
+                    params.silence_fall_rate.value(),
+                );
+                self.editor_data
+                    .meter_output
+                    .set_infinite_hold(params.infinite_peak_hold.value());
+
+                // The host can automate a reset by switching the parameter on
+                let reset_requested = params.reset_peak_hold.value();
+                if reset_requested && !self.peak_hold_reset_param {
+                    self.reset_peak_hold();
+                }
+                self.peak_hold_reset_param = reset_requested;
+
+                // Everything below is frozen while paused
+                if !self.editor_data.analysis_paused.load(Ordering::Relaxed) {
+                    // Advance meter smoothing and peak hold once per frame
+                    // This is the only caller of update(); view() and the meter canvas only read
+                    self.editor_data.meter_output.update(self.tick_count);
+                    self.meter_read_failures
+                        .record(&self.editor_data.meter_output.get_smoothed_levels());
+                    self.meter_display.update_trails();
+
+                    // Fade the spectrum while the input sits near the noise floor
+                    self.spectrum_display.set_meter_level(
+                        self.editor_data
+                            .meter_output
+                            .get_smoothed_aggregate_level_or_silence(),
+                    );
+
+                    // Snapshot the spectrum on each new loudest peak; R clears the capture
+                    if self.editor_data.plugin_params.peak_capture.value() {
+                        self.spectrum_display.capture_on_peak(
+                            self.editor_data.meter_output.get_peak_hold_db_or_silence(),
+                        );
+                    }
+
+                    // Sample short-term level for the history strip
+                    // Uses the smoothed meter level until a dedicated RMS/LUFS-S source exists
+                    let (left_db, right_db) = self
+                        .editor_data
+                        .meter_output
+                        .get_smoothed_levels_or_silence();
+                    self.level_history.update(left_db.max(right_db));
+
+                    let (peak_left_db, peak_right_db) =
+                        self.editor_data.meter_output.get_peak_levels();
+                    self.peak_history.update(peak_left_db, peak_right_db);
+
+                    self.mono_detector.update(
+                        self.editor_data.meter_output.get_peak_levels(),
+                        self.editor_data
+                            .meter_output
+                            .get_smoothed_rms_levels_or_silence(),
+                        self.editor_data.meter_output.correlation(),
+                        Instant::now(),
+                    );
+
+                    let target = self.editor_data.spectrum_output.band_levels();
+                    let ballistics = self.editor_data.meter_output.ballistics();
+                    self.band_levels = self.band_levels.smoothed_towards(target, ballistics);
+                    self.tilt_meter.update(
+                        self.editor_data.spectrum_output.spectral_slope(),
+                        self.editor_data.plugin_params.tilt_target.value(),
+                        ballistics,
+                    );
+                }
                 Task::none()
             }
             Message::RequestResize(size) => {
@@ -212,6 +872,116 @@ impl IcedEditor for PluginEditor {
                 // No task needed - the window is already resized
                 Task::none()
             }
+            Message::ResetEnvelope => {
+                // The audio thread restarts the envelope on its next frame
+                self.editor_data.spectrum_output.reset_envelope();
+                Task::none()
+            }
+            Message::ResetDcOffset => {
+                // The audio thread restarts the measurement on its next block
+                self.editor_data.meter_output.reset_dc_offset();
+                Task::none()
+            }
+            Message::ToggleMonoInfo => {
+                self.mono_info_open = !self.mono_info_open;
+                Task::none()
+            }
+            Message::ResetPeakHold => {
+                self.reset_peak_hold();
+                Task::none()
+            }
+            Message::ModifiersChanged(modifiers) => {
+                self.spectrum_display
+                    .set_cursor_snap(CursorSnap::from_modifiers(modifiers));
+                Task::none()
+            }
+            Message::ToggleMarkerEditor => {
+                self.marker_editor_open = !self.marker_editor_open;
+                Task::none()
+            }
+            Message::AddFrequencyMarker => {
+                let marker = FrequencyMarker::default();
+                self.marker_frequency_inputs
+                    .push(format!("{}", marker.frequency_hz));
+                self.edit_frequency_markers(|markers| markers.push(marker));
+                Task::none()
+            }
+            Message::RemoveFrequencyMarker(index) => {
+                if index < self.marker_frequency_inputs.len() {
+                    self.marker_frequency_inputs.remove(index);
+                }
+                self.edit_frequency_markers(|markers| {
+                    if index < markers.len() {
+                        markers.remove(index);
+                    }
+                });
+                Task::none()
+            }
+            Message::FrequencyMarkerEdited(index, value) => {
+                // Keep partial input such as "3." on screen, apply it once it's a frequency
+                let frequency_hz = value.trim().parse::<f32>().ok().filter(|hz| *hz > 0.0);
+                if let Some(input) = self.marker_frequency_inputs.get_mut(index) {
+                    *input = value;
+                }
+                if let Some(frequency_hz) = frequency_hz {
+                    self.edit_frequency_markers(|markers| {
+                        if let Some(marker) = markers.get_mut(index) {
+                            marker.frequency_hz = frequency_hz;
+                        }
+                    });
+                }
+                Task::none()
+            }
+            Message::FrequencyMarkerLabelEdited(index, label) => {
+                self.edit_frequency_markers(|markers| {
+                    if let Some(marker) = markers.get_mut(index) {
+                        marker.label = label;
+                    }
+                });
+                Task::none()
+            }
+            Message::CycleFrequencyMarkerColor(index) => {
+                self.edit_frequency_markers(|markers| {
+                    if let Some(marker) = markers.get_mut(index) {
+                        marker.color = marker.color.next();
+                    }
+                });
+                Task::none()
+            }
+            Message::ClearLevelHistory => {
+                self.level_history.clear();
+                Task::none()
+            }
+            Message::ClearPeakHistory => {
+                self.peak_history.clear();
+                Task::none()
+            }
+            Message::ExportPng => {
+                // Capture on the UI thread; rendering and file IO run in the background
+                let meter_levels_db = self
+                    .editor_data
+                    .meter_output
+                    .get_smoothed_levels_or_silence();
+                self.async_executor
+                    .execute_background(AnalyserTask::ExportPng {
+                        screenshot: Box::new(self.spectrum_display.screenshot(meter_levels_db)),
+                        path: export_path(&export_directory(), SystemTime::now()),
+                    });
+                Task::none()
+            }
+            Message::TogglePause => {
+                // The audio thread picks this up on its next process call
+                let paused = self.editor_data.analysis_paused.load(Ordering::Relaxed);
+                self.editor_data
+                    .analysis_paused
+                    .store(!paused, Ordering::Relaxed);
+
+                // Don't let the meter count the pause as time since its last update
+                if paused {
+                    self.editor_data.meter_output.resume();
+                }
+                Task::none()
+            }
         }
     }
 
@@ -225,22 +995,39 @@ impl IcedEditor for PluginEditor {
         // Set up a callback for window resize events
         window_subs.on_resize = Some(Arc::new(|size| Some(Message::WindowResized(size))));
 
-        // Return no additional subscriptions
-        Subscription::none()
+        // R clears the held peak, unless a widget already used the key
+        // Modifier changes are always forwarded for cursor snapping
+        event::listen_with(|event, status, _window| match event {
+            Event::Keyboard(keyboard::Event::KeyPressed {
+                key: keyboard::Key::Character(key),
+                modifiers,
+                ..
+            }) if status == event::Status::Ignored
+                && modifiers.is_empty()
+                && key.eq_ignore_ascii_case("r") =>
+            {
+                Some(Message::ResetPeakHold)
+            }
+            Event::Keyboard(keyboard::Event::ModifiersChanged(modifiers)) => {
+                Some(Message::ModifiersChanged(modifiers))
+            }
+            _ => None,
+        })
     }
 
     fn view(&self) -> Element<'_, Self::Message, Self::Theme, Renderer> {
-        // Update meter processing before reading peak hold
-        self.editor_data.meter_output.update();
+        let paused = self.editor_data.analysis_paused.load(Ordering::Relaxed);
 
         // Create widgets using pure functions
         let spectrum_canvas = create_spectrum_canvas(&self.spectrum_display);
 
-        // Wrap spectrum canvas in container with bottom padding to stop before -100 line
+        // Wrap spectrum canvas in container with bottom padding so it stops where the
+        // grid does, leaving the frequency label gutter free
+        let gutters = AxisGutters::for_params(&self.editor_data.plugin_params);
         let spectrum_container = container(spectrum_canvas)
             .width(Length::Fill)
             .height(Length::Fill)
-            .padding(Padding::default().bottom(30)); // 30px bottom padding
+            .padding(Padding::default().bottom(gutters.bottom));
 
         // Canvas-based grid (existing) - commented out for shader testing
         let _grid_canvas: Canvas<&GridOverlay, Message> = Canvas::new(&self.grid_overlay)
@@ -253,20 +1040,132 @@ impl IcedEditor for PluginEditor {
             .width(Length::FillPortion(6))
             .height(Length::Fill);
 
+        // The shader only draws lines, its labels and axis titles come from a canvas on top
+        let grid_labels_canvas = Canvas::new(&self.grid_labels)
+            .width(Length::FillPortion(6))
+            .height(Length::Fill);
+
         // Stack the canvases and shader on top of each other
         // Both grids will render - we can compare performance and visual quality
-        let layered_spectrum = stack![
+        let mut layered_spectrum = stack![
             spectrum_container,
             // grid_canvas,        // Comment out canvas grid to see shader grid
             grid_shader_widget,    // Our new GPU-accelerated grid
+            grid_labels_canvas,
         ];
 
-        let db_display =
-            create_db_display(self.editor_data.meter_output.get_peak_hold_db_or_silence());
-        let meter_canvas = create_meter_canvas(&self.meter_display);
+        // Flag the alarm band while its energy is over the threshold
+        let params = &self.editor_data.plugin_params;
+        let alarm_db = self.editor_data.spectrum_output.alarm_band_level();
+        let band_alarm = (params.band_alarm.value()
+            && alarm_db > params.band_alarm_threshold.value())
+        .then(|| {
+            create_band_alarm_badge(
+                alarm_db,
+                params.band_alarm_threshold.value(),
+                params.band_alarm_low.value(),
+                params.band_alarm_high.value(),
+            )
+        });
+
+        // Spectrum failures take precedence, the meter's only show when the spectrum is fine
+        let read_warning = self
+            .spectrum_display
+            .read_error()
+            .or_else(|| self.meter_read_failures.error())
+            .map(|error| create_read_warning_badge(&error));
+
+        // Always show how the display is configured so the trace isn't misread
+        let (dc_left, dc_right) = self.editor_data.meter_output.dc_offsets();
+        layered_spectrum = layered_spectrum.push(create_status_line(
+            describe_display_settings(&self.editor_data.plugin_params),
+            describe_analysis_timing(
+                &self.editor_data.plugin_params,
+                self.editor_data.sample_rate.load(Ordering::Relaxed),
+                self.spectrum_display.window_size(),
+            ),
+            create_dc_offset_readout(dc_left, dc_right),
+            band_alarm,
+            read_warning,
+        ));
+
+        // Show a badge so a frozen display is never mistaken for live analysis
+        if paused {
+            layered_spectrum = layered_spectrum.push(create_paused_badge());
+        }
+
+        // Calibration self-test readout
+        if self.editor_data.plugin_params.calibration.value() {
+            let tone_hz = calibration_frequency_hz(
+                self.editor_data.sample_rate.load(Ordering::Relaxed),
+                self.spectrum_display.window_size(),
+            );
+            // The self-test reads the native scale, so undo the level convention first
+            let reference_offset_db = self
+                .editor_data
+                .plugin_params
+                .reference_normalization
+                .value()
+                .offset_db();
+            layered_spectrum = layered_spectrum.push(create_calibration_badge(
+                calibration_level_db(
+                    self.editor_data.spectrum_output.peak().level_db - reference_offset_db,
+                ),
+                tone_hz,
+            ));
+        }
+
+        // Marker editor floats over the bottom-left of the spectrum while open
+        if self.marker_editor_open {
+            let frequency_markers = self
+                .editor_data
+                .plugin_params
+                .frequency_markers
+                .read()
+                .map(|markers| markers.clone())
+                .unwrap_or_default();
+            layered_spectrum = layered_spectrum.push(create_marker_editor(
+                &frequency_markers,
+                &self.marker_frequency_inputs,
+            ));
+        }
+
+        let db_display = create_db_display(
+            self.editor_data
+                .meter_output
+                .get_peak_hold_db_for_or_silence(
+                    self.editor_data.plugin_params.peak_readout_channel.value(),
+                ),
+            self.editor_data.plugin_params.meter_scale.value(),
+            self.editor_data.plugin_params.meter_alignment.value(),
+            self.editor_data.plugin_params.infinite_peak_hold.value(),
+        );
+        let meter_canvas = create_meter_canvas(
+            &self.meter_display,
+            self.editor_data.plugin_params.meter_attack.value(),
+            self.editor_data.plugin_params.meter_release.value(),
+        );
 
         // Compose layout using pure functions
-        let right_panel = create_right_panel(db_display, meter_canvas);
+        let pause_button = create_pause_button(paused);
+        let band_readouts = create_band_readouts(self.band_levels);
+        let envelope_reset_button = self
+            .editor_data
+            .plugin_params
+            .envelope
+            .value()
+            .then(create_envelope_reset_button);
+        let right_panel = create_right_panel(
+            pause_button,
+            envelope_reset_button,
+            create_markers_button(self.marker_editor_open),
+            create_export_button(&export_directory()),
+            db_display,
+            band_readouts,
+            create_tilt_meter(&self.tilt_meter, params.tilt_target.value()),
+            create_mono_badge(self.mono_detector.is_mono(), self.mono_info_open),
+            meter_canvas,
+        );
 
         // Add resize handle to the right panel at the bottom
         let (current_width, current_height) = self.iced_state.size();
@@ -284,7 +1183,34 @@ impl IcedEditor for PluginEditor {
             .align_x(Horizontal::Right)
         ];
 
-        let main_content = create_main_layout_with_stack(layered_spectrum, right_panel_with_resize.into());
+        let level_strip = self
+            .editor_data
+            .plugin_params
+            .level_history
+            .value()
+            .then(|| create_level_history_strip(&self.level_history));
+        let peak_strip = self
+            .editor_data
+            .plugin_params
+            .peak_history
+            .value()
+            .then(|| create_peak_history_strip(&self.peak_history));
+        let bass_inspector = self
+            .editor_data
+            .plugin_params
+            .bass_inspector
+            .value()
+            .then(|| create_bass_inspector_strip(&self.spectrum_display));
+
+        let main_content = create_main_layout_with_stack(
+            layered_spectrum,
+            bass_inspector
+                .into_iter()
+                .chain(level_strip)
+                .chain(peak_strip)
+                .collect(),
+            right_panel_with_resize.into(),
+        );
 
         // Apply grey overlay when processing is stopped
         if self.editor_data.process_stopped.load(Ordering::Relaxed) {
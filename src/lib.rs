@@ -1,18 +1,28 @@
 mod audio;
 mod editor;
+pub mod prelude;
 mod ui;
 
 use atomic_float::AtomicF32;
-use audio::meter::{create_meter_channels, MeterConsumer, MeterProducer};
-use audio::spectrum::{SpectrumConsumer, SpectrumProducer, SpectrumSpeed};
+use audio::meter::{
+    create_meter_channels, MeterConsumer, MeterProducer, DEFAULT_METER_ATTACK_MS,
+    DEFAULT_METER_RELEASE_MS, DEFAULT_SILENCE_FALL_DB_PER_S, DEFAULT_SILENCE_THRESHOLD_DB,
+};
+use audio::spectrum::{
+    FftOverlap, PublishRate, ReferenceNormalization, SpectrumConsumer, SpectrumProducer,
+    SpectrumSpeed, MAX_PEAK_MARKERS, MAX_WELCH_AVERAGES,
+};
 use editor::EditorInitFlags;
 use editor::PluginEditor;
 use nih_plug::prelude::*;
 use nih_plug_iced::{create_iced_editor, IcedState};
+use std::path::PathBuf;
 use std::sync::{
     atomic::{AtomicBool, Ordering},
-    Arc,
+    Arc, RwLock,
 };
+use ui::frequency_markers::FrequencyMarker;
+use ui::png_export::{self, Screenshot};
 
 #[derive(Enum, PartialEq, Clone)]
 enum AmplitudeRange {
@@ -37,8 +47,9 @@ impl AmplitudeRange {
     }
 }
 
+/// How many display bins the spectrum is reduced to
 #[derive(Enum, PartialEq, Clone, Copy)]
-enum ResolutionLevel {
+pub enum ResolutionLevel {
     #[id = "low"]
     #[name = "Low (1024)"]
     Low,
@@ -59,13 +70,301 @@ impl ResolutionLevel {
             ResolutionLevel::Low => 128,      // Smoothest - fewer bins
             ResolutionLevel::Medium => 256,   // Medium detail
             ResolutionLevel::High => 512,     // High detail
-            ResolutionLevel::Maximum => 2049, // All bins (4096 FFT / 2 + 1)
+            ResolutionLevel::Maximum => 2049, // All bins of the default 4096 FFT
+        }
+    }
+}
+
+#[derive(Enum, PartialEq, Clone, Copy)]
+enum SpectrumInterpolation {
+    #[id = "off"]
+    #[name = "Off"]
+    Off,
+    #[id = "cubic"]
+    #[name = "Cubic"]
+    Cubic,
+    #[id = "sinc"]
+    #[name = "Sinc (Lanczos)"]
+    Sinc,
+}
+
+/// How consecutive spectrum points are joined
+#[derive(Enum, PartialEq, Clone, Copy)]
+enum CurveStyle {
+    #[id = "smooth"]
+    #[name = "Smooth"]
+    Smooth,
+    #[id = "linear"]
+    #[name = "Linear"]
+    Linear,
+    #[id = "stepped"]
+    #[name = "Stepped"]
+    Stepped,
+}
+
+/// How rounded the spectrum curve is drawn, independent of analysis resolution
+#[derive(Enum, PartialEq, Clone, Copy)]
+enum CurveSmoothness {
+    #[id = "auto"]
+    #[name = "Auto (follow resolution)"]
+    Auto,
+    #[id = "tight"]
+    #[name = "Tight"]
+    Tight,
+    #[id = "balanced"]
+    #[name = "Balanced"]
+    Balanced,
+    #[id = "smooth"]
+    #[name = "Smooth"]
+    Smooth,
+    #[id = "very_smooth"]
+    #[name = "Very Smooth"]
+    VerySmooth,
+}
+
+/// Lower edge of the frequency display
+///
+/// With a 4096-point FFT at 48 kHz the bins are ~11.7 Hz apart, so below ~40 Hz the
+/// trace is interpolated between only a handful of bins. Extending the range shows
+/// where sub-bass energy sits, not fine detail within it.
+#[derive(Enum, PartialEq, Clone, Copy)]
+enum LowFrequencyLimit {
+    #[id = "20hz"]
+    #[name = "20 Hz"]
+    Hz20,
+    #[id = "10hz"]
+    #[name = "10 Hz"]
+    Hz10,
+    #[id = "5hz"]
+    #[name = "5 Hz"]
+    Hz5,
+}
+
+impl LowFrequencyLimit {
+    pub fn to_hz(&self) -> f32 {
+        match self {
+            LowFrequencyLimit::Hz20 => 20.0,
+            LowFrequencyLimit::Hz10 => 10.0,
+            LowFrequencyLimit::Hz5 => 5.0,
+        }
+    }
+}
+
+#[derive(Enum, PartialEq, Clone, Copy)]
+enum PercentileWindow {
+    #[id = "off"]
+    #[name = "Off"]
+    Off,
+    #[id = "3s"]
+    #[name = "3 s"]
+    Seconds3,
+    #[id = "10s"]
+    #[name = "10 s"]
+    Seconds10,
+    #[id = "30s"]
+    #[name = "30 s"]
+    Seconds30,
+}
+
+impl PercentileWindow {
+    pub fn to_seconds(&self) -> Option<f32> {
+        match self {
+            PercentileWindow::Off => None,
+            PercentileWindow::Seconds3 => Some(3.0),
+            PercentileWindow::Seconds10 => Some(10.0),
+            PercentileWindow::Seconds30 => Some(30.0),
+        }
+    }
+}
+
+/// What the level meter shows
+#[derive(Enum, PartialEq, Clone, Copy)]
+enum MeterMode {
+    #[id = "per_channel"]
+    #[name = "Per Channel (L/R)"]
+    PerChannel,
+    #[id = "aggregate"]
+    #[name = "Aggregate (Max)"]
+    Aggregate,
+}
+
+/// Scale the meter readouts are labelled in
+///
+/// Display-only: the meter still measures dBFS, and dBu/VU are offsets from it that
+/// put the alignment level at +4 dBu / 0 VU.
+#[derive(Enum, PartialEq, Clone, Copy)]
+enum MeterScale {
+    #[id = "dbfs"]
+    #[name = "dBFS"]
+    Dbfs,
+    #[id = "dbu"]
+    #[name = "dBu"]
+    Dbu,
+    #[id = "vu"]
+    #[name = "dBVU"]
+    Vu,
+}
+
+impl MeterScale {
+    /// Analogue level the alignment point stands for (+4 dBu = 0 VU)
+    const ALIGNMENT_DBU: f32 = 4.0;
+
+    /// Convert a dBFS level to this scale, given the dBFS level of the alignment point
+    pub fn from_dbfs(&self, level_dbfs: f32, alignment_dbfs: f32) -> f32 {
+        match self {
+            MeterScale::Dbfs => level_dbfs,
+            MeterScale::Dbu => level_dbfs - alignment_dbfs + Self::ALIGNMENT_DBU,
+            MeterScale::Vu => level_dbfs - alignment_dbfs,
+        }
+    }
+
+    pub fn unit(&self) -> &'static str {
+        match self {
+            MeterScale::Dbfs => "dB",
+            MeterScale::Dbu => "dBu",
+            MeterScale::Vu => "VU",
+        }
+    }
+}
+
+/// Which channels the headline peak-hold readout shows
+#[derive(Enum, PartialEq, Clone, Copy)]
+pub enum PeakReadoutChannel {
+    #[id = "max"]
+    #[name = "Max"]
+    Max,
+    #[id = "average"]
+    #[name = "Average"]
+    Average,
+    #[id = "left"]
+    #[name = "Left"]
+    Left,
+    #[id = "right"]
+    #[name = "Right"]
+    Right,
+}
+
+/// Which input the analysis (spectrum and meter) reads
+#[derive(Enum, PartialEq, Clone, Copy)]
+enum AnalysisSource {
+    #[id = "main"]
+    #[name = "Main In"]
+    Main,
+    #[id = "aux"]
+    #[name = "Aux In"]
+    Aux,
+}
+
+/// Root note for the musical-interval grid, or off for the decade grid
+#[derive(Enum, PartialEq, Clone, Copy)]
+enum MusicalGridRoot {
+    #[id = "off"]
+    #[name = "Off"]
+    Off,
+    #[id = "c"]
+    #[name = "C"]
+    C,
+    #[id = "c_sharp"]
+    #[name = "C#"]
+    CSharp,
+    #[id = "d"]
+    #[name = "D"]
+    D,
+    #[id = "d_sharp"]
+    #[name = "D#"]
+    DSharp,
+    #[id = "e"]
+    #[name = "E"]
+    E,
+    #[id = "f"]
+    #[name = "F"]
+    F,
+    #[id = "f_sharp"]
+    #[name = "F#"]
+    FSharp,
+    #[id = "g"]
+    #[name = "G"]
+    G,
+    #[id = "g_sharp"]
+    #[name = "G#"]
+    GSharp,
+    #[id = "a"]
+    #[name = "A"]
+    A,
+    #[id = "a_sharp"]
+    #[name = "A#"]
+    ASharp,
+    #[id = "b"]
+    #[name = "B"]
+    B,
+}
+
+impl MusicalGridRoot {
+    /// Root frequency in the lowest octave, or `None` when the decade grid is used
+    pub fn to_hz(&self) -> Option<f32> {
+        let semitone = match self {
+            MusicalGridRoot::Off => return None,
+            MusicalGridRoot::C => 0,
+            MusicalGridRoot::CSharp => 1,
+            MusicalGridRoot::D => 2,
+            MusicalGridRoot::DSharp => 3,
+            MusicalGridRoot::E => 4,
+            MusicalGridRoot::F => 5,
+            MusicalGridRoot::FSharp => 6,
+            MusicalGridRoot::G => 7,
+            MusicalGridRoot::GSharp => 8,
+            MusicalGridRoot::A => 9,
+            MusicalGridRoot::ASharp => 10,
+            MusicalGridRoot::B => 11,
+        };
+        Some(audio::constants::root_note_frequency(semitone))
+    }
+}
+
+/// How many minor grid lines are drawn between the major lines
+#[derive(Enum, Debug, PartialEq, Clone, Copy)]
+enum GridDensity {
+    #[id = "off"]
+    #[name = "Off (majors only)"]
+    Off,
+    #[id = "sparse"]
+    #[name = "Sparse"]
+    Sparse,
+    #[id = "normal"]
+    #[name = "Normal"]
+    Normal,
+    #[id = "dense"]
+    #[name = "Dense"]
+    Dense,
+}
+
+impl GridDensity {
+    /// Multiples of each decade drawn by the frequency grid, 1x is the major line
+    pub fn decade_multipliers(&self) -> &'static [f32] {
+        match self {
+            GridDensity::Off => &[1.0],
+            GridDensity::Sparse => &[1.0, 3.0],
+            GridDensity::Normal => &[1.0, 2.0, 5.0],
+            GridDensity::Dense => &[1.0, 2.0, 3.0, 4.0, 5.0, 6.0, 7.0, 8.0, 9.0],
+        }
+    }
+
+    /// Factor applied to the automatic dB step, `None` to draw only the range endpoints
+    pub fn db_step_scale(&self) -> Option<f32> {
+        match self {
+            GridDensity::Off => None,
+            GridDensity::Sparse => Some(2.0),
+            GridDensity::Normal => Some(1.0),
+            GridDensity::Dense => Some(0.5),
         }
     }
 }
 
-#[derive(Enum, PartialEq)]
-enum TiltLevel {
+/// Pink compensation: display tilt around 1 kHz, applied after all measurements
+/// Per-bin power of pink noise falls 3 dB/oct, so `Subtle` shows it flat and the
+/// steeper settings lift the highs further, towards how a mix is heard
+#[derive(Enum, PartialEq, Clone, Copy)]
+pub enum TiltLevel {
     #[id = "none"]
     #[name = "None (0 dB/oct)"]
     None,
@@ -115,6 +414,16 @@ struct SAPlugin {
 
     // PROCESSING STATE
     process_stopped: Arc<AtomicBool>,
+    analysis_paused: Arc<AtomicBool>, // Set from the UI to skip analysis entirely
+}
+
+/// Work the editor hands to the plugin's background thread
+enum AnalyserTask {
+    /// Render a captured screenshot and save it as a PNG at `path`
+    ExportPng {
+        screenshot: Box<Screenshot>,
+        path: PathBuf,
+    },
 }
 
 #[derive(Params)]
@@ -128,8 +437,202 @@ struct SAPluginParams {
     #[id = "speed"]
     pub speed: EnumParam<SpectrumSpeed>,
 
+    /// Pink compensation; the one tilt applied to the trace (the tilt meter only measures)
     #[id = "tilt"]
     pub tilt: EnumParam<TiltLevel>,
+
+    #[id = "interpolation"]
+    pub interpolation: EnumParam<SpectrumInterpolation>,
+
+    #[id = "fill_decay"]
+    pub fill_decay: FloatParam,
+
+    #[id = "calibration"]
+    pub calibration: BoolParam,
+
+    #[id = "reference_enabled"]
+    pub reference_enabled: BoolParam,
+
+    #[id = "reference_db"]
+    pub reference_db: FloatParam,
+
+    #[id = "peak_readout"]
+    pub peak_readout: BoolParam,
+
+    #[id = "publish_rate"]
+    pub publish_rate: EnumParam<PublishRate>,
+
+    #[id = "frame_blend"]
+    pub frame_blend: BoolParam,
+
+    #[id = "max_trace"]
+    pub max_trace: BoolParam,
+
+    #[id = "percentile_window"]
+    pub percentile_window: EnumParam<PercentileWindow>,
+
+    #[id = "level_history"]
+    pub level_history: BoolParam,
+
+    #[id = "low_frequency"]
+    pub low_frequency: EnumParam<LowFrequencyLimit>,
+
+    #[id = "band_low_mid"]
+    pub band_low_mid: FloatParam,
+
+    #[id = "band_mid_high"]
+    pub band_mid_high: FloatParam,
+
+    #[id = "bin_markers"]
+    pub bin_markers: BoolParam,
+
+    #[id = "peak_decay"]
+    pub peak_decay: FloatParam,
+
+    #[id = "envelope"]
+    pub envelope: BoolParam,
+
+    #[id = "curve_smoothness"]
+    pub curve_smoothness: EnumParam<CurveSmoothness>,
+
+    #[id = "meter_mode"]
+    pub meter_mode: EnumParam<MeterMode>,
+
+    #[id = "left_trim_db"]
+    pub left_trim_db: FloatParam,
+
+    #[id = "right_trim_db"]
+    pub right_trim_db: FloatParam,
+
+    #[id = "peak_markers"]
+    pub peak_markers: IntParam,
+
+    #[id = "peak_history"]
+    pub peak_history: BoolParam,
+
+    #[id = "reference_normalization"]
+    pub reference_normalization: EnumParam<ReferenceNormalization>,
+
+    #[id = "hide_edge_bins"]
+    pub hide_edge_bins: BoolParam,
+
+    #[id = "silence_decay"]
+    pub silence_decay: BoolParam,
+
+    #[id = "silence_threshold"]
+    pub silence_threshold: FloatParam,
+
+    #[id = "silence_fall_rate"]
+    pub silence_fall_rate: FloatParam,
+
+    #[id = "slew_limit"]
+    pub slew_limit: FloatParam,
+
+    #[id = "meter_attack"]
+    pub meter_attack: FloatParam,
+
+    #[id = "meter_release"]
+    pub meter_release: FloatParam,
+
+    #[id = "musical_grid"]
+    pub musical_grid: EnumParam<MusicalGridRoot>,
+
+    #[id = "fractional_leds"]
+    pub fractional_leds: BoolParam,
+
+    #[id = "analysis_source"]
+    pub analysis_source: EnumParam<AnalysisSource>,
+
+    #[id = "led_trails"]
+    pub led_trails: BoolParam,
+
+    #[id = "band_alarm"]
+    pub band_alarm: BoolParam,
+
+    #[id = "band_alarm_low"]
+    pub band_alarm_low: FloatParam,
+
+    #[id = "band_alarm_high"]
+    pub band_alarm_high: FloatParam,
+
+    #[id = "band_alarm_threshold"]
+    pub band_alarm_threshold: FloatParam,
+
+    #[id = "meter_scale"]
+    pub meter_scale: EnumParam<MeterScale>,
+
+    #[id = "meter_alignment"]
+    pub meter_alignment: FloatParam,
+
+    #[id = "curve_style"]
+    pub curve_style: EnumParam<CurveStyle>,
+
+    #[id = "infinite_peak_hold"]
+    pub infinite_peak_hold: BoolParam,
+
+    /// Clears the held peak on each off-to-on change, so hosts can automate resets
+    #[id = "reset_peak_hold"]
+    pub reset_peak_hold: BoolParam,
+
+    /// Spectral slope the tilt meter is centred on
+    #[id = "tilt_target"]
+    pub tilt_target: FloatParam,
+
+    /// Dims the spectrum while no new frames arrive (paused, stopped or starved)
+    #[id = "dim_stale"]
+    pub dim_stale: BoolParam,
+
+    #[id = "grid_density"]
+    pub grid_density: EnumParam<GridDensity>,
+
+    /// Overlap between FFT frames, restricted to COLA-compliant hops for the window
+    #[id = "fft_overlap"]
+    pub fft_overlap: EnumParam<FftOverlap>,
+
+    /// Turns the meter's peak cap and hold line red on overs
+    #[id = "meter_over_color"]
+    pub meter_over_color: BoolParam,
+
+    /// Draws the axis units ("Hz" and the level unit) in the label gutters
+    #[id = "axis_titles"]
+    pub axis_titles: BoolParam,
+
+    /// Channel (or combination) shown by the peak-hold dB readout
+    #[id = "peak_readout_channel"]
+    pub peak_readout_channel: EnumParam<PeakReadoutChannel>,
+
+    /// User-defined vertical marker lines, edited in the marker editor
+    /// Only saved with the state when `serialize` is on, as it is for the plugin build
+    #[cfg_attr(feature = "serialize", persist = "frequency-markers")]
+    pub frequency_markers: Arc<RwLock<Vec<FrequencyMarker>>>,
+
+    /// Draws the sparse bass bins with extra, cubic-interpolated display points
+    #[id = "bass_oversampling"]
+    pub bass_oversampling: BoolParam,
+
+    /// Snapshots the spectrum whenever the meter sees a new loudest peak
+    #[id = "peak_capture"]
+    pub peak_capture: BoolParam,
+
+    /// Time the spectrum takes to blend from the old curve style to the new one
+    #[id = "curve_style_crossfade"]
+    pub curve_style_crossfade: FloatParam,
+
+    /// Fades the spectrum while the meter level is below `low_level_threshold`, where
+    /// the curve is mostly the noise floor
+    #[id = "low_level_dim"]
+    pub low_level_dim: BoolParam,
+
+    #[id = "low_level_threshold"]
+    pub low_level_threshold: FloatParam,
+
+    /// Frames averaged into each Welch periodogram, 1 = off
+    #[id = "welch_averages"]
+    pub welch_averages: IntParam,
+
+    /// Show a zoomed 20-200 Hz panel beneath the spectrum
+    #[id = "bass_inspector"]
+    pub bass_inspector: BoolParam,
 }
 
 impl Default for SAPlugin {
@@ -159,6 +662,7 @@ impl Default for SAPlugin {
 
             // PROCESSING STATE
             process_stopped: Arc::new(AtomicBool::new(false)),
+            analysis_paused: Arc::new(AtomicBool::new(false)),
         }
     }
 }
@@ -170,6 +674,259 @@ impl Default for SAPluginParams {
             resolution: EnumParam::new("Resolution", ResolutionLevel::Medium),
             speed: EnumParam::new("Speed", SpectrumSpeed::Medium),
             tilt: EnumParam::new("Tilt", TiltLevel::Natural),
+            interpolation: EnumParam::new("Interpolation", SpectrumInterpolation::Off),
+            fill_decay: FloatParam::new(
+                "Fill Decay",
+                0.0,
+                FloatRange::Linear {
+                    min: 0.0,
+                    max: 3000.0,
+                },
+            )
+            .with_unit(" ms")
+            .with_step_size(10.0),
+            calibration: BoolParam::new("Calibration Test", false),
+            reference_enabled: BoolParam::new("Reference Line", false),
+            reference_db: FloatParam::new(
+                "Reference Level",
+                -18.0,
+                FloatRange::Linear {
+                    min: -60.0,
+                    max: 0.0,
+                },
+            )
+            .with_unit(" dB")
+            .with_step_size(0.5),
+            peak_readout: BoolParam::new("Peak Readout", false),
+            publish_rate: EnumParam::new("Publish Rate", PublishRate::EveryFrame),
+            frame_blend: BoolParam::new("Frame Blending", false),
+            max_trace: BoolParam::new("Max Trace", false),
+            percentile_window: EnumParam::new("95th Percentile", PercentileWindow::Off),
+            level_history: BoolParam::new("Level History", false),
+            low_frequency: EnumParam::new("Low Frequency Limit", LowFrequencyLimit::Hz20),
+            band_low_mid: FloatParam::new(
+                "Low/Mid Band Edge",
+                120.0,
+                FloatRange::Skewed {
+                    min: 40.0,
+                    max: 500.0,
+                    factor: FloatRange::skew_factor(-1.0),
+                },
+            )
+            .with_unit(" Hz")
+            .with_step_size(1.0),
+            band_mid_high: FloatParam::new(
+                "Mid/High Band Edge",
+                2000.0,
+                FloatRange::Skewed {
+                    min: 500.0,
+                    max: 10000.0,
+                    factor: FloatRange::skew_factor(-1.0),
+                },
+            )
+            .with_unit(" Hz")
+            .with_step_size(10.0),
+            bin_markers: BoolParam::new("FFT Bin Markers", false),
+            peak_decay: FloatParam::new(
+                "Peak Hold Decay",
+                0.0,
+                FloatRange::Linear {
+                    min: 0.0,
+                    max: 60.0,
+                },
+            )
+            .with_unit(" dB/s")
+            .with_step_size(1.0),
+            envelope: BoolParam::new("Min/Max Envelope", false),
+            curve_smoothness: EnumParam::new("Curve Smoothness", CurveSmoothness::Auto),
+            meter_mode: EnumParam::new("Meter Mode", MeterMode::PerChannel),
+            left_trim_db: FloatParam::new(
+                "Left Analysis Trim",
+                0.0,
+                FloatRange::Linear {
+                    min: -24.0,
+                    max: 24.0,
+                },
+            )
+            .with_unit(" dB")
+            .with_step_size(0.5),
+            right_trim_db: FloatParam::new(
+                "Right Analysis Trim",
+                0.0,
+                FloatRange::Linear {
+                    min: -24.0,
+                    max: 24.0,
+                },
+            )
+            .with_unit(" dB")
+            .with_step_size(0.5),
+            peak_markers: IntParam::new(
+                "Peak Markers",
+                0,
+                IntRange::Linear {
+                    min: 0,
+                    max: MAX_PEAK_MARKERS as i32,
+                },
+            ),
+            peak_history: BoolParam::new("Peak History", false),
+            reference_normalization: EnumParam::new(
+                "Level Convention",
+                ReferenceNormalization::SineRms,
+            ),
+            hide_edge_bins: BoolParam::new("Hide DC/Nyquist Bins", false),
+            silence_decay: BoolParam::new("Meter Silence Decay", true),
+            silence_threshold: FloatParam::new(
+                "Silence Threshold",
+                DEFAULT_SILENCE_THRESHOLD_DB,
+                FloatRange::Linear {
+                    min: -90.0,
+                    max: -30.0,
+                },
+            )
+            .with_unit(" dB")
+            .with_step_size(1.0),
+            silence_fall_rate: FloatParam::new(
+                "Silence Fall Rate",
+                DEFAULT_SILENCE_FALL_DB_PER_S,
+                FloatRange::Linear {
+                    min: 10.0,
+                    max: 120.0,
+                },
+            )
+            .with_unit(" dB/s")
+            .with_step_size(1.0),
+            slew_limit: FloatParam::new(
+                "Slew Limit",
+                0.0,
+                FloatRange::Linear {
+                    min: 0.0,
+                    max: 20.0,
+                },
+            )
+            .with_unit(" dB/frame")
+            .with_step_size(0.5),
+            meter_attack: FloatParam::new(
+                "Meter Attack",
+                DEFAULT_METER_ATTACK_MS,
+                FloatRange::Skewed {
+                    min: 0.0,
+                    max: 100.0,
+                    factor: FloatRange::skew_factor(-1.0),
+                },
+            )
+            .with_unit(" ms")
+            .with_step_size(0.5),
+            meter_release: FloatParam::new(
+                "Meter Release",
+                DEFAULT_METER_RELEASE_MS,
+                FloatRange::Skewed {
+                    min: 50.0,
+                    max: 3000.0,
+                    factor: FloatRange::skew_factor(-1.0),
+                },
+            )
+            .with_unit(" ms")
+            .with_step_size(1.0),
+            musical_grid: EnumParam::new("Musical Grid Root", MusicalGridRoot::Off),
+            fractional_leds: BoolParam::new("Fractional Meter LEDs", false),
+            analysis_source: EnumParam::new("Analysis Source", AnalysisSource::Main),
+            led_trails: BoolParam::new("Meter LED Trails", false),
+            band_alarm: BoolParam::new("Band Alarm", false),
+            band_alarm_low: FloatParam::new(
+                "Band Alarm Low Edge",
+                200.0,
+                FloatRange::Skewed {
+                    min: 20.0,
+                    max: 20000.0,
+                    factor: FloatRange::skew_factor(-2.0),
+                },
+            )
+            .with_unit(" Hz")
+            .with_step_size(1.0),
+            band_alarm_high: FloatParam::new(
+                "Band Alarm High Edge",
+                500.0,
+                FloatRange::Skewed {
+                    min: 20.0,
+                    max: 20000.0,
+                    factor: FloatRange::skew_factor(-2.0),
+                },
+            )
+            .with_unit(" Hz")
+            .with_step_size(1.0),
+            band_alarm_threshold: FloatParam::new(
+                "Band Alarm Threshold",
+                -20.0,
+                FloatRange::Linear {
+                    min: -80.0,
+                    max: 0.0,
+                },
+            )
+            .with_unit(" dB")
+            .with_step_size(0.5),
+            meter_scale: EnumParam::new("Meter Scale", MeterScale::Dbfs),
+            meter_alignment: FloatParam::new(
+                "Meter Alignment",
+                -18.0,
+                FloatRange::Linear {
+                    min: -24.0,
+                    max: -9.0,
+                },
+            )
+            .with_unit(" dBFS")
+            .with_step_size(0.5),
+            curve_style: EnumParam::new("Curve Style", CurveStyle::Smooth),
+            infinite_peak_hold: BoolParam::new("Infinite Peak Hold", false),
+            reset_peak_hold: BoolParam::new("Reset Peak Hold", false),
+            tilt_target: FloatParam::new(
+                "Tilt Target",
+                -3.0,
+                FloatRange::Linear {
+                    min: -9.0,
+                    max: 3.0,
+                },
+            )
+            .with_unit(" dB/oct")
+            .with_step_size(0.1),
+            dim_stale: BoolParam::new("Dim Stale Spectrum", false),
+            grid_density: EnumParam::new("Grid Density", GridDensity::Normal),
+            fft_overlap: EnumParam::new("FFT Overlap", FftOverlap::Half),
+            meter_over_color: BoolParam::new("Meter Over Colour", true),
+            axis_titles: BoolParam::new("Axis Titles", false),
+            peak_readout_channel: EnumParam::new("Peak Readout Channel", PeakReadoutChannel::Max),
+            frequency_markers: Arc::new(RwLock::new(Vec::new())),
+            bass_oversampling: BoolParam::new("Bass Oversampling", false),
+            peak_capture: BoolParam::new("Capture On Peak", false),
+            curve_style_crossfade: FloatParam::new(
+                "Curve Style Crossfade",
+                150.0,
+                FloatRange::Linear {
+                    min: 0.0,
+                    max: 500.0,
+                },
+            )
+            .with_unit(" ms")
+            .with_step_size(10.0),
+            low_level_dim: BoolParam::new("Dim Below Level", false),
+            low_level_threshold: FloatParam::new(
+                "Dim Below Level Threshold",
+                -60.0,
+                FloatRange::Linear {
+                    min: -120.0,
+                    max: -20.0,
+                },
+            )
+            .with_unit(" dB")
+            .with_step_size(1.0),
+            welch_averages: IntParam::new(
+                "Welch Averages",
+                1,
+                IntRange::Linear {
+                    min: 1,
+                    max: MAX_WELCH_AVERAGES,
+                },
+            ),
+            bass_inspector: BoolParam::new("Bass Inspector", false),
         }
     }
 }
@@ -188,13 +945,17 @@ impl Plugin for SAPlugin {
         main_input_channels: NonZeroU32::new(2),
         main_output_channels: NonZeroU32::new(2),
 
-        aux_input_ports: &[],
+        // Optional stereo aux input the analysis can read instead of the main input
+        aux_input_ports: &[new_nonzero_u32(2)],
         aux_output_ports: &[],
 
         // Individual ports and the layout as a whole can be named here. By default these names
         // are generated as needed. This layout will be called 'Stereo', while a layout with
         // only one input and output channel would be called 'Mono'.
-        names: PortNames::const_default(),
+        names: PortNames {
+            aux_inputs: &["Aux In"],
+            ..PortNames::const_default()
+        },
     }];
 
     const MIDI_INPUT: MidiConfig = MidiConfig::None;
@@ -206,10 +967,8 @@ impl Plugin for SAPlugin {
     // messages here. The type implements the `SysExMessage` trait, which allows conversion to and
     // from plain byte buffers.
     type SysExMessage = ();
-    // More advanced plugins can use this to run expensive background tasks. See the field's
-    // documentation for more information. `()` means that the plugin does not have any background
-    // tasks.
-    type BackgroundTask = ();
+    // Screenshot exports render and write their file here, off the UI thread
+    type BackgroundTask = AnalyserTask;
 
     fn params(&self) -> Arc<dyn Params> {
         self.params.clone()
@@ -230,6 +989,7 @@ impl Plugin for SAPlugin {
     fn reset(&mut self) {
         // Called when processing starts/resumes
         self.process_stopped.store(false, Ordering::Relaxed);
+        self.audio_meter_producer.reset_dc_offset();
     }
 
     fn process_stopped(&mut self) {
@@ -241,31 +1001,100 @@ impl Plugin for SAPlugin {
     fn process(
         &mut self,
         buffer: &mut Buffer,
-        _aux: &mut AuxiliaryBuffers,
+        aux: &mut AuxiliaryBuffers,
         _context: &mut impl ProcessContext<Self>,
     ) -> ProcessStatus {
         let sample_rate = self.sample_rate.load(Ordering::Relaxed);
 
+        // While paused, skip all analysis work so the display holds the last frame
+        let paused = self.analysis_paused.load(Ordering::Relaxed);
+        self.audio_spectrum_producer.set_paused(paused);
+        if paused {
+            return ProcessStatus::Normal;
+        }
+
         // Read current parameter values
         let tilt = self.params.tilt.value();
         let speed = self.params.speed.value();
         let resolution = self.params.resolution.value();
 
         self.audio_spectrum_producer
-            .process(buffer, sample_rate, tilt, speed, resolution);
-        self.audio_meter_producer.update_peaks(buffer);
+            .set_calibration(self.params.calibration.value());
+        self.audio_spectrum_producer
+            .set_publish_rate(self.params.publish_rate.value());
+        self.audio_spectrum_producer
+            .set_overlap(self.params.fft_overlap.value());
+        self.audio_spectrum_producer
+            .set_envelope_enabled(self.params.envelope.value());
+        self.audio_spectrum_producer.set_band_edges(
+            self.params.band_low_mid.value(),
+            self.params.band_mid_high.value(),
+        );
+        self.audio_spectrum_producer.set_alarm_band(
+            self.params.band_alarm_low.value(),
+            self.params.band_alarm_high.value(),
+        );
+        self.audio_spectrum_producer
+            .set_reference_normalization(self.params.reference_normalization.value());
+        self.audio_spectrum_producer
+            .set_slew_limit(self.params.slew_limit.value());
+        self.audio_spectrum_producer
+            .set_welch_averages(self.params.welch_averages.value() as usize);
+        self.audio_spectrum_producer
+            .set_hide_edge_bins(self.params.hide_edge_bins.value());
+        self.audio_spectrum_producer.set_channel_trims(
+            self.params.left_trim_db.value(),
+            self.params.right_trim_db.value(),
+        );
+
+        // Analyse the selected input; the main buffer always passes through untouched
+        // Falls back to main if the host didn't provide the aux port
+        let aux_source = match self.params.analysis_source.value() {
+            AnalysisSource::Main => None,
+            AnalysisSource::Aux => aux.inputs.first(),
+        };
+        match aux_source {
+            Some(aux_buffer) => {
+                self.audio_spectrum_producer.process(
+                    aux_buffer,
+                    sample_rate,
+                    tilt,
+                    speed,
+                    resolution,
+                );
+                self.audio_meter_producer.update_peaks(aux_buffer);
+            }
+            None => {
+                self.audio_spectrum_producer
+                    .process(buffer, sample_rate, tilt, speed, resolution);
+                self.audio_meter_producer.update_peaks(buffer);
+            }
+        }
 
         ProcessStatus::Normal
     }
 
-    fn editor(&mut self, _async_executor: AsyncExecutor<Self>) -> Option<Box<dyn Editor>> {
+    fn task_executor(&mut self) -> TaskExecutor<Self> {
+        Box::new(|task| match task {
+            AnalyserTask::ExportPng { screenshot, path } => {
+                match png_export::export_png(&screenshot, &path) {
+                    Ok(path) => nih_log!("Saved spectrum screenshot to {}", path.display()),
+                    Err(error) => nih_error!("Spectrum screenshot export failed: {error}"),
+                }
+            }
+        })
+    }
+
+    fn editor(&mut self, async_executor: AsyncExecutor<Self>) -> Option<Box<dyn Editor>> {
         let init_flags = EditorInitFlags {
             plugin_params: self.params.clone(),
             sample_rate: self.sample_rate.clone(),
             process_stopped: self.process_stopped.clone(),
+            analysis_paused: self.analysis_paused.clone(),
             spectrum_output: self.ui_spectrum_consumer.clone(),
             meter_output: self.ui_meter_consumer.clone(),
             iced_state: self.iced_state.clone(),
+            async_executor,
         };
 
         create_iced_editor::<PluginEditor>(
@@ -294,4 +1123,7 @@ impl Vst3Plugin for SAPlugin {
         &[Vst3SubCategory::Analyzer, Vst3SubCategory::Tools];
 }
 
+// Crates embedding the analyser turn off the `export` feature so their own plugin
+// is the only one exported from the binary
+#[cfg(feature = "export")]
 nih_export_clap!(SAPlugin);
@@ -0,0 +1,38 @@
+// Public surface for embedding the analyser in another plugin
+//
+// The audio-thread producers, the UI-thread consumers, the settings they take and the
+// analysis core they run are exported. The display widgets are bound to this plugin's
+// own parameter set, so they stay private until they can be configured without it.
+
+pub use crate::audio::meter::{create_meter_channels, MeterConsumer, MeterProducer};
+pub use crate::audio::spectrum::{
+    AnalysisTiming, BandLevels, FftOverlap, PublishRate, ReferenceNormalization, SpectrumConfig,
+    SpectrumConsumer, SpectrumData, SpectrumFrame, SpectrumPeak, SpectrumProducer, SpectrumSpeed,
+    DEFAULT_FFT_SIZE, MAX_FFT_SIZE_USIZE, MIN_FFT_SIZE,
+};
+pub use crate::audio::window_functions::WindowType;
+
+// Compile-time sized analysis for embedders with a fixed FFT size
+pub use crate::audio::fixed_spectrum::{
+    FixedSpectrum, FixedSpectrum1024, FixedSpectrum2048, FixedSpectrum4096,
+};
+pub use crate::{PeakReadoutChannel, ResolutionLevel, TiltLevel};
+
+// The analysis core, generic over `Sample` so offline tooling can run it in f64
+pub use crate::audio::spectrum::{
+    apply_slew_limit, apply_temporal_envelope_sized, apply_tilt_compensation, apply_window,
+    compute_magnitude_spectrum, compute_magnitude_spectrum_into,
+};
+pub use crate::audio::window_functions::{
+    generate_blackman_window, generate_hamming_window, generate_hann_window, is_cola, Sample,
+};
+
+#[cfg(feature = "serialize")]
+pub use crate::audio::spectrum::{SpectrumSnapshot, SNAPSHOT_VERSION};
+
+/// Error types returned by the consumers' fallible reads
+pub use crate::audio::errors::{MeterError, SpectrumError};
+
+// Re-exported for the consumers' signatures, not a stable part of the surface
+#[doc(hidden)]
+pub use crate::audio::errors::{MeterResult, SpectrumResult};
@@ -0,0 +1,161 @@
+use crate::audio::constants;
+use crate::ui::spectrum_display::format_frequency;
+use crate::ui::{SpectrumDisplay, UITheme};
+use nih_plug_iced::alignment::{Horizontal, Vertical};
+use nih_plug_iced::widget::canvas::{Frame, Geometry, Path, Program, Stroke, Text};
+use nih_plug_iced::{mouse, Font, Point, Rectangle, Renderer, Size, Theme};
+
+/// Frequency range the inspector always shows (Hz)
+const INSPECTOR_MIN_FREQUENCY: f32 = 20.0;
+const INSPECTOR_MAX_FREQUENCY: f32 = 200.0;
+
+/// Level range the inspector shows; tighter than any main range so bass detail is taller
+const INSPECTOR_MIN_DB: f32 = -48.0;
+const INSPECTOR_MAX_DB: f32 = 0.0;
+
+/// Frequency grid lines, and which of them are labelled
+const GRID_FREQUENCIES: [f32; 8] = [20.0, 30.0, 40.0, 50.0, 70.0, 100.0, 150.0, 200.0];
+const LABELLED_FREQUENCIES: [f32; 5] = [20.0, 50.0, 100.0, 150.0, 200.0];
+
+/// Spacing of the level grid lines (dB)
+const GRID_DB_STEP: f32 = 12.0;
+
+/// Label font size, smaller than the main axes to suit the short panel
+const LABEL_SIZE: f32 = 8.0;
+
+/// Width kept clear on the right for the level labels
+const LABEL_GUTTER: f32 = 24.0;
+
+/// Zoomed view of the bottom of the spectrum, drawn beneath the main display
+/// Borrows the main `SpectrumDisplay` so both show the same frame with the same curve
+/// style; only the axes differ
+pub struct BassInspector<'a> {
+    spectrum_display: &'a SpectrumDisplay,
+}
+
+impl<'a> BassInspector<'a> {
+    pub fn new(spectrum_display: &'a SpectrumDisplay) -> Self {
+        Self { spectrum_display }
+    }
+}
+
+impl<Message> Program<Message, Theme> for BassInspector<'_> {
+    type State = ();
+
+    fn draw(
+        &self,
+        _state: &Self::State,
+        renderer: &Renderer,
+        _theme: &Theme,
+        bounds: Rectangle,
+        _cursor: mouse::Cursor,
+    ) -> Vec<Geometry> {
+        let mut frame = Frame::new(renderer, bounds.size());
+
+        let background = Path::rectangle(Point::ORIGIN, bounds.size());
+        frame.fill(&background, UITheme::BACKGROUND_MAIN);
+
+        let plot = Size::new(bounds.width - LABEL_GUTTER, bounds.height);
+        draw_grid(&mut frame, plot);
+
+        self.spectrum_display.draw_inspector_curve(
+            &mut frame,
+            plot,
+            (INSPECTOR_MIN_FREQUENCY, INSPECTOR_MAX_FREQUENCY),
+            (INSPECTOR_MIN_DB, INSPECTOR_MAX_DB),
+        );
+
+        draw_labels(&mut frame, plot);
+
+        vec![frame.into_geometry()]
+    }
+}
+
+/// X position of `frequency` on the inspector's log axis
+fn frequency_to_x(frequency: f32, plot: Size) -> f32 {
+    constants::freq_to_log_position(frequency, INSPECTOR_MIN_FREQUENCY, INSPECTOR_MAX_FREQUENCY)
+        * plot.width
+}
+
+/// Y position of `db` on the inspector's level axis
+fn db_to_y(db: f32, plot: Size) -> f32 {
+    plot.height * (1.0 - constants::db_to_normalized(db, INSPECTOR_MIN_DB, INSPECTOR_MAX_DB))
+}
+
+/// Levels of the horizontal grid lines, top of the range first
+fn grid_levels() -> impl Iterator<Item = f32> {
+    let steps = ((INSPECTOR_MAX_DB - INSPECTOR_MIN_DB) / GRID_DB_STEP) as usize;
+    (0..=steps).map(|step| INSPECTOR_MAX_DB - step as f32 * GRID_DB_STEP)
+}
+
+/// Draw the inspector's own frequency and level lines
+fn draw_grid(frame: &mut Frame, plot: Size) {
+    let stroke = Stroke::default()
+        .with_width(UITheme::GRID_LINE_WIDTH)
+        .with_color(UITheme::GRID_LINE);
+
+    for frequency in GRID_FREQUENCIES {
+        let x = frequency_to_x(frequency, plot);
+        frame.stroke(
+            &Path::line(Point::new(x, 0.0), Point::new(x, plot.height)),
+            stroke,
+        );
+    }
+
+    for db in grid_levels() {
+        let y = db_to_y(db, plot);
+        frame.stroke(
+            &Path::line(Point::new(0.0, y), Point::new(plot.width, y)),
+            stroke,
+        );
+    }
+}
+
+/// Label the frequency lines along the bottom and the level lines in the right gutter
+fn draw_labels(frame: &mut Frame, plot: Size) {
+    let label = |content: String, position: Point, align_x: Horizontal, align_y: Vertical| Text {
+        content,
+        position,
+        color: UITheme::TEXT_SECONDARY,
+        size: nih_plug_iced::Pixels(LABEL_SIZE),
+        font: Font::default(),
+        align_x: align_x.into(),
+        align_y: align_y.into(),
+        line_height: nih_plug_iced::widget::text::LineHeight::default(),
+        shaping: nih_plug_iced::widget::text::Shaping::default(),
+        max_width: f32::INFINITY,
+    };
+
+    for frequency in LABELLED_FREQUENCIES {
+        // Keep the end labels inside the plot rather than centred on its edges
+        let align_x = if frequency == INSPECTOR_MIN_FREQUENCY {
+            Horizontal::Left
+        } else if frequency == INSPECTOR_MAX_FREQUENCY {
+            Horizontal::Right
+        } else {
+            Horizontal::Center
+        };
+        frame.fill_text(label(
+            format_frequency(frequency),
+            Point::new(frequency_to_x(frequency, plot), plot.height - 1.0),
+            align_x,
+            Vertical::Bottom,
+        ));
+    }
+
+    for db in grid_levels() {
+        let align_y = if db == INSPECTOR_MAX_DB {
+            Vertical::Top
+        } else if db == INSPECTOR_MIN_DB {
+            Vertical::Bottom
+        } else {
+            Vertical::Center
+        };
+        frame.fill_text(label(
+            format!("{:.0}", db),
+            Point::new(plot.width + 3.0, db_to_y(db, plot)),
+            Horizontal::Left,
+            align_y,
+        ));
+    }
+}
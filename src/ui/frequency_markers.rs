@@ -0,0 +1,122 @@
+use crate::audio::constants;
+use crate::ui::UITheme;
+use nih_plug_iced::widget::canvas::{Frame, LineDash, Path, Stroke, Text};
+use nih_plug_iced::{Color, Font, Point, Size};
+#[cfg(feature = "serialize")]
+use serde::{Deserialize, Serialize};
+
+/// Frequency given to a newly added marker (Hz)
+pub const NEW_MARKER_FREQUENCY_HZ: f32 = 1000.0;
+
+/// Dash pattern of the marker lines (dash, gap), distinct from the reference line
+const MARKER_LINE_DASH: [f32; 2] = [2.0, 3.0];
+
+/// Colour choices for a frequency marker, cycled in the marker editor
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[cfg_attr(feature = "serialize", derive(Serialize, Deserialize))]
+pub enum MarkerColor {
+    Amber,
+    Cyan,
+    Magenta,
+    Green,
+}
+
+impl MarkerColor {
+    pub fn color(self) -> Color {
+        match self {
+            Self::Amber => UITheme::FREQUENCY_MARKER_AMBER,
+            Self::Cyan => UITheme::FREQUENCY_MARKER_CYAN,
+            Self::Magenta => UITheme::FREQUENCY_MARKER_MAGENTA,
+            Self::Green => UITheme::FREQUENCY_MARKER_GREEN,
+        }
+    }
+
+    /// The colour after this one in the editor's cycle
+    pub fn next(self) -> Self {
+        match self {
+            Self::Amber => Self::Cyan,
+            Self::Cyan => Self::Magenta,
+            Self::Magenta => Self::Green,
+            Self::Green => Self::Amber,
+        }
+    }
+}
+
+/// A user-defined vertical marker, e.g. 60 Hz hum or a 3.2 kHz harshness band
+/// Stored with the plugin state, so markers travel with the session
+#[derive(Debug, Clone, PartialEq)]
+#[cfg_attr(feature = "serialize", derive(Serialize, Deserialize))]
+pub struct FrequencyMarker {
+    pub frequency_hz: f32,
+    /// Text drawn beside the line; empty for a bare line
+    pub label: String,
+    pub color: MarkerColor,
+}
+
+impl Default for FrequencyMarker {
+    fn default() -> Self {
+        Self {
+            frequency_hz: NEW_MARKER_FREQUENCY_HZ,
+            label: String::new(),
+            color: MarkerColor::Amber,
+        }
+    }
+}
+
+/// Draw each marker as a dashed vertical line with its label at the top
+/// Markers outside `min_frequency..MAX_FREQUENCY` are skipped
+pub fn draw_frequency_markers(
+    frame: &mut Frame,
+    size: Size,
+    spectrum_width: f32,
+    min_frequency: f32,
+    markers: &[FrequencyMarker],
+) {
+    for marker in markers {
+        if !(min_frequency..=constants::MAX_FREQUENCY).contains(&marker.frequency_hz) {
+            continue;
+        }
+
+        let x = constants::freq_to_log_position(
+            marker.frequency_hz,
+            min_frequency,
+            constants::MAX_FREQUENCY,
+        ) * spectrum_width;
+        let color = marker.color.color();
+
+        let line = Path::line(Point::new(x, 0.0), Point::new(x, size.height));
+        frame.stroke(
+            &line,
+            Stroke {
+                line_dash: LineDash {
+                    segments: &MARKER_LINE_DASH,
+                    offset: 0,
+                },
+                ..Stroke::default().with_width(1.0).with_color(color)
+            },
+        );
+
+        if marker.label.is_empty() {
+            continue;
+        }
+
+        // Flip the label to the left of the line near the right edge
+        let (align_x, offset) = if x > spectrum_width * 0.8 {
+            (nih_plug_iced::alignment::Horizontal::Right, -3.0)
+        } else {
+            (nih_plug_iced::alignment::Horizontal::Left, 3.0)
+        };
+        frame.fill_text(Text {
+            content: marker.label.clone(),
+            position: Point::new(x + offset, 3.0),
+            color,
+            size: nih_plug_iced::Pixels(9.0),
+            font: Font::default(),
+            align_x: align_x.into(),
+            align_y: nih_plug_iced::alignment::Vertical::Top.into(),
+            line_height: nih_plug_iced::widget::text::LineHeight::default(),
+            shaping: nih_plug_iced::widget::text::Shaping::default(),
+            max_width: f32::INFINITY,
+        });
+    }
+}
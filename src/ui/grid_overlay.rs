@@ -1,15 +1,60 @@
 use crate::audio::constants;
+use crate::ui::frequency_markers::{draw_frequency_markers, FrequencyMarker};
+use crate::ui::label_layout::{
+    db_label_priority, estimate_label_width, fit_labels, frequency_label_priority, AxisGutters,
+    AxisUnits, LabelSpan, FREQUENCY_LABEL_OFFSET, GUTTER_PADDING,
+};
 use crate::ui::UITheme;
+use crate::{GridDensity, SAPluginParams};
 use nih_plug_iced::widget::canvas::{Frame, Geometry, Path, Program, Stroke, Text};
 use nih_plug_iced::{mouse, Font, Point, Rectangle, Renderer, Size, Theme};
+use std::sync::Arc;
 
 /// Grid overlay component - draws static grid lines and labels
 /// No data processing, just visual grid elements
-pub struct GridOverlay;
+pub struct GridOverlay {
+    /// Plugin parameters for the ranges and grid style the grid is drawn with
+    plugin_params: Arc<SAPluginParams>,
+    /// User marker lines drawn over the grid
+    frequency_markers: Vec<FrequencyMarker>,
+}
 
 impl GridOverlay {
-    pub fn new() -> Self {
-        Self
+    pub fn new(plugin_params: Arc<SAPluginParams>) -> Self {
+        Self {
+            plugin_params,
+            frequency_markers: Vec::new(),
+        }
+    }
+
+    /// Replace the user marker lines drawn over the grid
+    pub fn set_frequency_markers(&mut self, frequency_markers: Vec<FrequencyMarker>) {
+        self.frequency_markers = frequency_markers;
+    }
+
+    /// Lowest frequency shown, the left edge of the spectrum
+    fn min_frequency(&self) -> f32 {
+        self.plugin_params.low_frequency.value().to_hz()
+    }
+
+    /// How many minor lines are drawn between the majors
+    fn density(&self) -> GridDensity {
+        self.plugin_params.grid_density.value()
+    }
+
+    /// Amplitude range the labels and lines are drawn for, the same one as the spectrum
+    fn db_range(&self) -> (f32, f32) {
+        self.plugin_params.range.value().to_db_range()
+    }
+
+    /// Units drawn as axis titles, None when they're turned off
+    fn titles(&self) -> Option<AxisUnits> {
+        AxisUnits::for_params(&self.plugin_params)
+    }
+
+    /// Label gutters for the current amplitude range and titles
+    fn gutters(&self) -> AxisGutters {
+        AxisGutters::for_params(&self.plugin_params)
     }
 }
 
@@ -29,36 +74,61 @@ impl<Message> Program<Message, Theme> for GridOverlay {
         // Draw grid
         self.draw_grid(&mut frame, bounds.size());
 
+        // Draw user marker lines on top of the standard grid
+        draw_frequency_markers(
+            &mut frame,
+            bounds.size(),
+            bounds.width - self.gutters().right,
+            self.min_frequency(),
+            &self.frequency_markers,
+        );
+
         // Draw frequency labels (bottom)
         self.draw_frequency_labels(&mut frame, bounds.size());
 
         // Draw dB scale labels (right side)
         self.draw_db_labels(&mut frame, bounds.size());
 
+        // Draw axis titles (gutter corners)
+        self.draw_axis_titles(&mut frame, bounds.size());
+
         vec![frame.into_geometry()]
     }
 }
 
 impl GridOverlay {
     fn draw_grid(&self, frame: &mut Frame, size: Size) {
+        let gutters = self.gutters();
         let stroke = Stroke::default()
             .with_width(UITheme::GRID_LINE_WIDTH)
             .with_color(UITheme::GRID_LINE);
 
         // Calculate the spectrum area (same as used for spectrum drawing)
-        let spectrum_width = size.width - UITheme::SPECTRUM_MARGIN_RIGHT;
-        let spectrum_height = size.height - UITheme::SPECTRUM_MARGIN_BOTTOM;
+        let spectrum_width = size.width - gutters.right;
+        let spectrum_height = size.height - gutters.bottom;
 
         // Draw horizontal grid lines using pure function
-        let db_grid_lines = generate_db_grid_lines(spectrum_width, spectrum_height);
+        let (min_db, max_db) = self.db_range();
+        let db_grid_lines = generate_db_grid_lines(
+            spectrum_width,
+            spectrum_height,
+            min_db,
+            max_db,
+            self.density(),
+        );
         for grid_line in db_grid_lines {
             let path = Path::line(grid_line.start, grid_line.end);
             frame.stroke(&path, stroke.clone());
         }
 
         // Draw vertical grid lines using pure function with different weights
-        let frequency_grid_lines =
-            generate_frequency_grid_lines_with_weights(spectrum_width, spectrum_height);
+        let frequency_grid_lines = generate_frequency_grid_lines_with_weights(
+            spectrum_width,
+            spectrum_height,
+            self.min_frequency(),
+            self.plugin_params.musical_grid.value().to_hz(),
+            self.density(),
+        );
         for (grid_line, is_major) in frequency_grid_lines {
             let path = Path::line(grid_line.start, grid_line.end);
             if is_major {
@@ -74,46 +144,127 @@ impl GridOverlay {
         }
     }
 
-    /// Draw frequency labels at the bottom
+    /// Draw frequency labels at the bottom, dropping minor labels that would overlap
     fn draw_frequency_labels(&self, frame: &mut Frame, size: Size) {
-        let spectrum_width = size.width - UITheme::SPECTRUM_MARGIN_RIGHT;
+        let gutters = self.gutters();
+        let spectrum_width = size.width - gutters.right;
+        let spectrum_height = size.height - gutters.bottom;
+        let text_size = UITheme::FREQUENCY_LABEL_SIZE;
+        let min_frequency = self.min_frequency();
+        let label_x = |freq: f32| {
+            let log_pos =
+                constants::freq_to_log_position(freq, min_frequency, constants::MAX_FREQUENCY);
+            log_pos * spectrum_width
+        };
+
+        // Labels are left-aligned, so each spans rightwards from its line
+        let markers = constants::visible_frequency_markers(min_frequency);
+        let spans: Vec<LabelSpan> = markers
+            .iter()
+            .map(|&(freq, label)| LabelSpan {
+                start: label_x(freq),
+                end: label_x(freq) + estimate_label_width(label, text_size),
+                priority: frequency_label_priority(freq),
+            })
+            .collect();
+        let title = self
+            .titles()
+            .map(|units| units.frequency_title_span(size.width));
+        let fitted: Vec<(f32, &str)> = markers
+            .iter()
+            .zip(fit_labels(&spans, title, size.width))
+            .filter_map(|(&marker, keep)| keep.then_some(marker))
+            .collect();
 
         self.draw_labels(
             frame,
-            constants::FREQUENCY_MARKERS,
+            &fitted,
             UITheme::TEXT_SECONDARY,
-            nih_plug_iced::Pixels(9.0),
-            |&(freq, _)| {
-                let log_pos = constants::freq_to_log_position(freq);
-                let spectrum_height = size.height - UITheme::SPECTRUM_MARGIN_BOTTOM;
-                (log_pos * spectrum_width, spectrum_height + 10.0) // Just below the spectrum area
-            },
+            nih_plug_iced::Pixels(text_size),
+            // Just below the spectrum area
+            |&(freq, _)| (label_x(freq), spectrum_height + FREQUENCY_LABEL_OFFSET),
             nih_plug_iced::alignment::Horizontal::Left, // Align to right of position
             nih_plug_iced::alignment::Vertical::Top,
         );
     }
 
-    /// Draw dB scale labels on the right side
+    /// Draw dB scale labels on the right side, dropping labels that would overlap
     fn draw_db_labels(&self, frame: &mut Frame, size: Size) {
-        let spectrum_height = size.height - UITheme::SPECTRUM_MARGIN_BOTTOM;
+        let gutters = self.gutters();
+        let spectrum_height = size.height - gutters.bottom;
+        let text_size = UITheme::DB_LABEL_SIZE;
+        let (min_db, max_db) = self.db_range();
+        let label_y = |db_value: f32| {
+            let normalized = constants::db_to_normalized(db_value, min_db, max_db);
+            let y = spectrum_height * (1.0 - normalized);
+            // Clamp Y position to keep text within visible area
+            y.max(5.0).min(spectrum_height - 5.0)
+        };
+
+        // Labels stay at the normal step so dense grids don't crowd the text
+        let labels: Vec<(f32, String)> =
+            constants::db_grid_markers(min_db, max_db, GridDensity::Normal)
+                .into_iter()
+                .map(|db| (db, constants::format_db_marker(db)))
+                .collect();
+
+        // Labels are vertically centred, so each spans half the text size either side
+        let spans: Vec<LabelSpan> = labels
+            .iter()
+            .enumerate()
+            .map(|(index, &(db, _))| LabelSpan {
+                start: label_y(db) - text_size / 2.0,
+                end: label_y(db) + text_size / 2.0,
+                priority: db_label_priority(index),
+            })
+            .collect();
+        let title = self.titles().map(|units| units.level_title_span());
+        let markers: Vec<(f32, &str)> = labels
+            .iter()
+            .zip(fit_labels(&spans, title, spectrum_height))
+            .filter_map(|((db, label), keep)| keep.then_some((*db, label.as_str())))
+            .collect();
 
         self.draw_labels(
             frame,
-            constants::DB_MARKERS,
+            &markers,
             UITheme::TEXT_DB_MARKER,
-            nih_plug_iced::Pixels(10.0),
-            |&(db_value, _)| {
-                let normalized = constants::db_to_normalized(db_value);
-                let y = spectrum_height * (1.0 - normalized);
-                // Clamp Y position to keep text within visible area
-                let clamped_y = y.max(5.0).min(spectrum_height - 5.0);
-                (size.width - 5.0, clamped_y)
-            },
+            nih_plug_iced::Pixels(text_size),
+            |&(db_value, _)| (size.width - GUTTER_PADDING, label_y(db_value)),
             nih_plug_iced::alignment::Horizontal::Right,
             nih_plug_iced::alignment::Vertical::Center,
         );
     }
 
+    /// Draw the unit titles, "Hz" in the bottom-right corner and the level unit at the
+    /// top of the dB labels; tick labels in their way were already dropped
+    fn draw_axis_titles(&self, frame: &mut Frame, size: Size) {
+        let Some(units) = self.titles() else {
+            return;
+        };
+        let spectrum_height = size.height - self.gutters().bottom;
+        let title_x = size.width - GUTTER_PADDING;
+
+        self.draw_labels(
+            frame,
+            &[(0.0, units.frequency)],
+            UITheme::TEXT_SECONDARY,
+            nih_plug_iced::Pixels(UITheme::AXIS_TITLE_SIZE),
+            |_| (title_x, spectrum_height + FREQUENCY_LABEL_OFFSET),
+            nih_plug_iced::alignment::Horizontal::Right,
+            nih_plug_iced::alignment::Vertical::Top,
+        );
+        self.draw_labels(
+            frame,
+            &[(0.0, units.level)],
+            UITheme::TEXT_SECONDARY,
+            nih_plug_iced::Pixels(UITheme::AXIS_TITLE_SIZE),
+            |_| (title_x, units.level_title_span().start),
+            nih_plug_iced::alignment::Horizontal::Right,
+            nih_plug_iced::alignment::Vertical::Top,
+        );
+    }
+
     /// Generic function to draw text labels
     fn draw_labels(
         &self,
@@ -152,12 +303,18 @@ pub struct GridLine {
     pub end: Point,
 }
 
-/// Generate horizontal grid lines for dB levels
-pub fn generate_db_grid_lines(spectrum_width: f32, spectrum_height: f32) -> Vec<GridLine> {
-    constants::DB_MARKERS
-        .iter()
-        .map(|&(db, _)| {
-            let normalized = constants::db_to_normalized(db);
+/// Generate horizontal grid lines for dB levels across the given amplitude range
+pub fn generate_db_grid_lines(
+    spectrum_width: f32,
+    spectrum_height: f32,
+    min_db: f32,
+    max_db: f32,
+    density: GridDensity,
+) -> Vec<GridLine> {
+    constants::db_grid_markers(min_db, max_db, density)
+        .into_iter()
+        .map(|db| {
+            let normalized = constants::db_to_normalized(db, min_db, max_db);
             let y = spectrum_height * (1.0 - normalized);
             GridLine {
                 start: Point::new(0.0, y),
@@ -170,12 +327,21 @@ pub fn generate_db_grid_lines(spectrum_width: f32, spectrum_height: f32) -> Vec<
 pub fn generate_frequency_grid_lines_with_weights(
     spectrum_width: f32,
     spectrum_height: f32,
+    min_frequency: f32,
+    musical_root_hz: Option<f32>,
+    density: GridDensity,
 ) -> Vec<(GridLine, bool)> {
-    let frequency_positions = constants::generate_frequency_grid_positions();
+    let frequency_positions = constants::grid_positions(
+        min_frequency,
+        constants::MAX_FREQUENCY,
+        musical_root_hz,
+        density,
+    );
     frequency_positions
         .iter()
         .map(|&(freq, is_major)| {
-            let log_pos = constants::freq_to_log_position(freq);
+            let log_pos =
+                constants::freq_to_log_position(freq, min_frequency, constants::MAX_FREQUENCY);
             let x = log_pos * spectrum_width;
             let grid_line = GridLine {
                 start: Point::new(x, 0.0),
@@ -185,3 +351,48 @@ pub fn generate_frequency_grid_lines_with_weights(
         })
         .collect()
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const TEST_WIDTH: f32 = 400.0;
+    const TEST_HEIGHT: f32 = 360.0;
+
+    /// y of each horizontal line for the range, top line first
+    fn db_line_ys(min_db: f32) -> Vec<f32> {
+        generate_db_grid_lines(TEST_WIDTH, TEST_HEIGHT, min_db, 0.0, GridDensity::Normal)
+            .into_iter()
+            .map(|line| {
+                assert_eq!(line.start.y, line.end.y, "dB lines are horizontal");
+                assert_eq!((line.start.x, line.end.x), (0.0, TEST_WIDTH));
+                line.start.y
+            })
+            .collect()
+    }
+
+    #[test]
+    fn db_grid_lines_are_placed_for_each_amplitude_range() {
+        // 10 dB steps for the -60 and -90 dB ranges, 20 dB steps for -120 dB
+        let cases: [(f32, &[f32]); 3] = [
+            (-60.0, &[0.0, 60.0, 120.0, 180.0, 240.0, 300.0, 360.0]),
+            (
+                -90.0,
+                &[
+                    0.0, 40.0, 80.0, 120.0, 160.0, 200.0, 240.0, 280.0, 320.0, 360.0,
+                ],
+            ),
+            (-120.0, &[0.0, 60.0, 120.0, 180.0, 240.0, 300.0, 360.0]),
+        ];
+        for (min_db, expected) in cases {
+            let ys = db_line_ys(min_db);
+            assert_eq!(ys.len(), expected.len(), "{min_db} dB range: {ys:?}");
+            for (&y, &expected_y) in ys.iter().zip(expected) {
+                assert!(
+                    (y - expected_y).abs() < 1e-3,
+                    "{min_db} dB range: line at {y} instead of {expected_y}"
+                );
+            }
+        }
+    }
+}
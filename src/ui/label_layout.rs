@@ -0,0 +1,344 @@
+// Axis label collision avoidance
+//
+// Labels are measured with a fixed per-character advance rather than real text
+// shaping, which is close enough for short numeric labels in the default font.
+
+use crate::audio::constants;
+use crate::audio::spectrum::ReferenceNormalization;
+use crate::ui::UITheme;
+use crate::{GridDensity, SAPluginParams};
+
+/// Approximate advance of one label character, as a fraction of the font size
+const CHAR_ADVANCE_EM: f32 = 0.6;
+
+/// Line height of a label, as a multiple of the font size
+const LABEL_LINE_HEIGHT_EM: f32 = 1.3;
+
+/// Space between a gutter's labels and its edges (px)
+pub const GUTTER_PADDING: f32 = 5.0;
+
+/// Gap between the bottom of the spectrum and the top of the frequency labels (px)
+pub const FREQUENCY_LABEL_OFFSET: f32 = 10.0;
+
+/// Space kept clear between neighbouring labels (px)
+const MIN_LABEL_GAP: f32 = 4.0;
+
+/// A label's extent along its axis and how important it is to keep
+/// Priority 0 labels are always drawn; higher numbers are dropped first
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct LabelSpan {
+    pub start: f32,
+    pub end: f32,
+    pub priority: u8,
+}
+
+/// Unit names drawn as axis titles, "Hz" in the bottom-right corner and the level unit
+/// at the top of the dB labels
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct AxisUnits {
+    pub frequency: &'static str,
+    pub level: &'static str,
+}
+
+impl AxisUnits {
+    /// Units of the spectrum under a level convention
+    /// Noise-power normalisation reads each bin's noise power, so its unit says so
+    pub fn for_normalization(normalization: ReferenceNormalization) -> Self {
+        Self {
+            frequency: "Hz",
+            level: match normalization {
+                ReferenceNormalization::SineRms | ReferenceNormalization::SinePeak => "dBFS",
+                ReferenceNormalization::NoisePower => "dBFS/bin",
+            },
+        }
+    }
+
+    /// Axis titles for the current settings, None when they're turned off
+    pub fn for_params(params: &SAPluginParams) -> Option<Self> {
+        params
+            .axis_titles
+            .value()
+            .then(|| Self::for_normalization(params.reference_normalization.value()))
+    }
+
+    /// Extent of the frequency title, right-aligned in the bottom-right corner
+    pub fn frequency_title_span(&self, width: f32) -> LabelSpan {
+        let end = width - GUTTER_PADDING;
+        LabelSpan {
+            start: end - estimate_label_width(self.frequency, UITheme::AXIS_TITLE_SIZE),
+            end,
+            priority: 0,
+        }
+    }
+
+    /// Extent of the level title, top-aligned at the top of the dB label column
+    pub fn level_title_span(&self) -> LabelSpan {
+        LabelSpan {
+            start: 0.0,
+            end: UITheme::AXIS_TITLE_SIZE * LABEL_LINE_HEIGHT_EM,
+            priority: 0,
+        }
+    }
+}
+
+impl Default for AxisUnits {
+    fn default() -> Self {
+        Self::for_normalization(ReferenceNormalization::SineRms)
+    }
+}
+
+/// Space reserved right of and below the spectrum for the axis labels
+/// Shared by the spectrum, the canvas grid and the shader grid so they line up
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct AxisGutters {
+    /// Width of the dB label column
+    pub right: f32,
+    /// Height of the frequency label row
+    pub bottom: f32,
+}
+
+impl AxisGutters {
+    /// Gutters that fit the dB labels of `min_db..max_db`, the frequency labels and any
+    /// axis titles at the theme's label sizes, so no label is clipped
+    pub fn for_db_range(min_db: f32, max_db: f32, titles: Option<AxisUnits>) -> Self {
+        let widest_db_label = constants::db_grid_markers(min_db, max_db, GridDensity::Normal)
+            .into_iter()
+            .map(|db| {
+                estimate_label_width(&constants::format_db_marker(db), UITheme::DB_LABEL_SIZE)
+            })
+            .fold(0.0, f32::max);
+        let widest_title = titles.map_or(0.0, |units| {
+            estimate_label_width(units.level, UITheme::AXIS_TITLE_SIZE).max(estimate_label_width(
+                units.frequency,
+                UITheme::AXIS_TITLE_SIZE,
+            ))
+        });
+
+        Self {
+            right: widest_db_label.max(widest_title) + 2.0 * GUTTER_PADDING,
+            bottom: FREQUENCY_LABEL_OFFSET
+                + UITheme::FREQUENCY_LABEL_SIZE * LABEL_LINE_HEIGHT_EM
+                + GUTTER_PADDING,
+        }
+    }
+
+    /// Gutters for the current amplitude range and axis title settings
+    pub fn for_params(params: &SAPluginParams) -> Self {
+        let (min_db, max_db) = params.range.value().to_db_range();
+        Self::for_db_range(min_db, max_db, AxisUnits::for_params(params))
+    }
+}
+
+/// Estimated rendered width of `text` at `text_size` px
+pub fn estimate_label_width(text: &str, text_size: f32) -> f32 {
+    text.chars().count() as f32 * text_size * CHAR_ADVANCE_EM
+}
+
+/// Keep priority of a frequency label
+/// Decades (10, 100, 1K, 10K) are always kept, then the 5x labels, then the 2x labels
+pub fn frequency_label_priority(frequency_hz: f32) -> u8 {
+    let mantissa = frequency_hz / 10.0_f32.powf(frequency_hz.log10().floor());
+    if (mantissa - 1.0).abs() < 1e-3 {
+        0
+    } else if (mantissa - 5.0).abs() < 1e-3 {
+        1
+    } else {
+        2
+    }
+}
+
+/// Keep priority of the `index`th dB label counted from the top
+/// The top label is always kept, then every other label, then the rest
+pub fn db_label_priority(index: usize) -> u8 {
+    match index {
+        0 => 0,
+        i if i % 2 == 0 => 1,
+        _ => 2,
+    }
+}
+
+/// Decide which labels to draw so neighbours don't overlap
+///
+/// Labels are placed in priority order (lowest number first, then by position), each
+/// one only if it clears every label already placed by `MIN_LABEL_GAP`. Labels that
+/// run outside `0..available_length` are dropped unless they have priority 0. Labels
+/// overlapping `title`, an axis title that is always drawn, are dropped whatever their
+/// priority.
+///
+/// # Returns
+/// One flag per input span, true if the label should be drawn
+pub fn fit_labels(
+    spans: &[LabelSpan],
+    title: Option<LabelSpan>,
+    available_length: f32,
+) -> Vec<bool> {
+    let mut order: Vec<usize> = (0..spans.len()).collect();
+    order.sort_by(|&a, &b| {
+        spans[a]
+            .priority
+            .cmp(&spans[b].priority)
+            .then(spans[a].start.total_cmp(&spans[b].start))
+    });
+
+    let mut keep = vec![false; spans.len()];
+    for index in order {
+        let span = spans[index];
+        if title.is_some_and(|title| !spans_clear(&span, &title)) {
+            continue;
+        }
+        if span.priority == 0 {
+            keep[index] = true;
+            continue;
+        }
+
+        let fits_axis = span.start >= 0.0 && span.end <= available_length;
+        let clears_placed = spans
+            .iter()
+            .zip(&keep)
+            .filter(|&(_, &kept)| kept)
+            .all(|(placed, _)| spans_clear(&span, placed));
+        keep[index] = fits_axis && clears_placed;
+    }
+
+    keep
+}
+
+/// Whether two spans are at least `MIN_LABEL_GAP` apart
+fn spans_clear(a: &LabelSpan, b: &LabelSpan) -> bool {
+    a.end + MIN_LABEL_GAP <= b.start || b.end + MIN_LABEL_GAP <= a.start
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// Font size for the frequency label spans in these tests (px)
+    const TEST_TEXT_SIZE: f32 = 10.0;
+
+    fn span(start: f32, end: f32, priority: u8) -> LabelSpan {
+        LabelSpan {
+            start,
+            end,
+            priority,
+        }
+    }
+
+    /// The frequency axis labels as the grid overlay lays them out across `width`
+    fn frequency_label_spans(width: f32) -> Vec<LabelSpan> {
+        constants::visible_frequency_markers(constants::MIN_FREQUENCY)
+            .iter()
+            .map(|&(freq, label)| {
+                let start = constants::freq_to_log_position(
+                    freq,
+                    constants::MIN_FREQUENCY,
+                    constants::MAX_FREQUENCY,
+                ) * width;
+                span(
+                    start,
+                    start + estimate_label_width(label, TEST_TEXT_SIZE),
+                    frequency_label_priority(freq),
+                )
+            })
+            .collect()
+    }
+
+    #[test]
+    fn crowded_labels_are_dropped_lowest_priority_first() {
+        // Each label overlaps the next; only the always-kept one survives
+        let spans = [span(4.0, 14.0, 2), span(8.0, 18.0, 1), span(16.0, 26.0, 0)];
+        assert_eq!(fit_labels(&spans, None, 100.0), [false, false, true]);
+
+        // With room beside the kept label, the more important of two rivals wins
+        let spans = [span(0.0, 10.0, 0), span(40.0, 50.0, 2), span(30.0, 42.0, 1)];
+        assert_eq!(fit_labels(&spans, None, 100.0), [true, false, true]);
+
+        // Equal priorities are placed left to right
+        let spans = [span(20.0, 30.0, 1), span(0.0, 10.0, 1), span(8.0, 18.0, 1)];
+        assert_eq!(fit_labels(&spans, None, 100.0), [true, true, false]);
+    }
+
+    #[test]
+    fn frequency_labels_thin_out_as_the_axis_narrows() {
+        // 20K starts at the right edge and so runs off the axis; everything else fits
+        let wide = fit_labels(&frequency_label_spans(2000.0), None, 2000.0);
+        let (last, rest) = wide.split_last().unwrap();
+        assert!(
+            rest.iter().all(|&kept| kept),
+            "a wide axis has room for every label"
+        );
+        assert!(!last);
+
+        for width in (100..=2000).step_by(50).map(|width| width as f32) {
+            let spans = frequency_label_spans(width);
+            let keep = fit_labels(&spans, None, width);
+            let kept: Vec<&LabelSpan> = spans
+                .iter()
+                .zip(&keep)
+                .filter(|&(_, &kept)| kept)
+                .map(|(span, _)| span)
+                .collect();
+
+            // Decades always stay, and nothing drawn overlaps
+            assert!(spans
+                .iter()
+                .zip(&keep)
+                .all(|(span, &kept)| kept || span.priority > 0));
+            assert!(
+                kept.windows(2).all(|pair| spans_clear(pair[0], pair[1])),
+                "{width} px: kept labels overlap"
+            );
+
+            // A label is only dropped for one at least as important, never for a lesser one
+            for (span, _) in spans.iter().zip(&keep).filter(|&(_, &kept)| !kept) {
+                let blocked = spans
+                    .iter()
+                    .zip(&keep)
+                    .filter(|&(other, &kept)| kept && other.priority <= span.priority)
+                    .any(|(other, _)| !spans_clear(span, other));
+                let off_axis = span.start < 0.0 || span.end > width;
+                assert!(
+                    blocked || off_axis,
+                    "{width} px: {span:?} dropped without cause"
+                );
+            }
+        }
+
+        let narrow = fit_labels(&frequency_label_spans(100.0), None, 100.0);
+        assert!(narrow.iter().filter(|&&kept| kept).count() < wide.len() - 1);
+    }
+
+    #[test]
+    fn labels_past_the_axis_ends_are_dropped_unless_always_kept() {
+        let spans = [
+            span(0.0, 10.0, 1),
+            span(-1.0, 9.0, 1),
+            span(90.0, 100.0, 2),
+            span(91.0, 101.0, 2),
+            span(-5.0, 5.0, 0),
+        ];
+        let keep = fit_labels(&spans, None, 100.0);
+
+        // Touching an end is fine; the always-kept label clears the first two regardless
+        assert_eq!(keep, [false, false, true, false, true]);
+
+        let spans = [
+            span(0.0, 10.0, 1),
+            span(90.0, 100.0, 2),
+            span(91.0, 101.0, 2),
+        ];
+        assert_eq!(fit_labels(&spans, None, 100.0), [true, true, false]);
+    }
+
+    #[test]
+    fn labels_under_the_axis_title_are_dropped() {
+        let spans = [span(0.0, 10.0, 0), span(40.0, 50.0, 0), span(70.0, 80.0, 1)];
+        let title = span(75.0, 100.0, 0);
+        assert_eq!(fit_labels(&spans, Some(title), 100.0), [true, true, false]);
+    }
+
+    #[test]
+    fn no_labels_fit_to_nothing() {
+        assert!(fit_labels(&[], None, 100.0).is_empty());
+        assert!(fit_labels(&[], Some(span(0.0, 10.0, 0)), 0.0).is_empty());
+    }
+}
@@ -0,0 +1,159 @@
+use crate::ui::UITheme;
+use nih_plug::util;
+use nih_plug_iced::widget::canvas::{Frame, Geometry, Path, Program, Stroke, Text};
+use nih_plug_iced::{mouse, Font, Point, Rectangle, Renderer, Size, Theme};
+use std::collections::VecDeque;
+use std::time::Instant;
+
+/// How often the strip samples the meter (Hz)
+const SAMPLE_RATE_HZ: f32 = 10.0;
+
+/// How much history the strip shows (seconds)
+const HISTORY_SECONDS: f32 = 30.0;
+
+/// Number of samples kept (`HISTORY_SECONDS` at `SAMPLE_RATE_HZ`)
+const HISTORY_LEN: usize = (HISTORY_SECONDS * SAMPLE_RATE_HZ) as usize;
+
+/// Level range drawn by the strip
+const STRIP_MIN_DB: f32 = -60.0;
+const STRIP_MAX_DB: f32 = 0.0;
+
+/// Scrolling strip of recent short-term level
+/// Sampled from the editor's tick so "that moment it got harsh" can be found in time
+pub struct LevelHistoryDisplay {
+    /// Oldest sample first
+    levels: VecDeque<f32>,
+    /// Time of the last sample
+    last_sample: Instant,
+}
+
+impl LevelHistoryDisplay {
+    pub fn new() -> Self {
+        Self {
+            levels: VecDeque::with_capacity(HISTORY_LEN),
+            last_sample: Instant::now(),
+        }
+    }
+
+    /// Record `level_db` if a sample is due
+    /// Called every tick; throttles itself to `SAMPLE_RATE_HZ`
+    pub fn update(&mut self, level_db: f32) {
+        let now = Instant::now();
+        if now.duration_since(self.last_sample).as_secs_f32() < 1.0 / SAMPLE_RATE_HZ {
+            return;
+        }
+        self.last_sample = now;
+
+        if self.levels.len() == HISTORY_LEN {
+            self.levels.pop_front();
+        }
+        self.levels.push_back(level_db);
+    }
+
+    /// Drop all recorded levels
+    pub fn clear(&mut self) {
+        self.levels.clear();
+    }
+}
+
+impl<Message> Program<Message, Theme> for LevelHistoryDisplay {
+    type State = ();
+
+    fn draw(
+        &self,
+        _state: &Self::State,
+        renderer: &Renderer,
+        _theme: &Theme,
+        bounds: Rectangle,
+        _cursor: mouse::Cursor,
+    ) -> Vec<Geometry> {
+        let mut frame = Frame::new(renderer, bounds.size());
+
+        let background = Path::rectangle(Point::ORIGIN, bounds.size());
+        frame.fill(&background, UITheme::BACKGROUND_MAIN);
+
+        self.draw_level_line(&mut frame, bounds.size());
+        self.draw_min_max_labels(&mut frame, bounds.size());
+
+        vec![frame.into_geometry()]
+    }
+}
+
+impl LevelHistoryDisplay {
+    /// Draw the history right-aligned so the newest sample is at the right edge
+    fn draw_level_line(&self, frame: &mut Frame, size: Size) {
+        if self.levels.len() < 2 {
+            return;
+        }
+
+        let step = size.width / (HISTORY_LEN - 1) as f32;
+        let start_x = size.width - (self.levels.len() - 1) as f32 * step;
+
+        let path = Path::new(|builder| {
+            for (i, &level_db) in self.levels.iter().enumerate() {
+                let point = Point::new(
+                    start_x + i as f32 * step,
+                    size.height * (1.0 - normalize_strip_level(level_db)),
+                );
+                if i == 0 {
+                    builder.move_to(point);
+                } else {
+                    builder.line_to(point);
+                }
+            }
+        });
+
+        frame.stroke(
+            &path,
+            Stroke::default()
+                .with_width(1.0)
+                .with_color(UITheme::SPECTRUM_LINE),
+        );
+    }
+
+    /// Label the quietest and loudest level in view
+    fn draw_min_max_labels(&self, frame: &mut Frame, size: Size) {
+        let audible = self
+            .levels
+            .iter()
+            .copied()
+            .filter(|&level_db| level_db > util::MINUS_INFINITY_DB);
+        let Some((min_db, max_db)) = audible.fold(None, |range, level_db| match range {
+            None => Some((level_db, level_db)),
+            Some((min_db, max_db)) => {
+                Some((f32::min(min_db, level_db), f32::max(max_db, level_db)))
+            }
+        }) else {
+            return;
+        };
+
+        let label = |content: String, y: f32, align_y: nih_plug_iced::alignment::Vertical| Text {
+            content,
+            position: Point::new(2.0, y),
+            color: UITheme::TEXT_SECONDARY,
+            size: nih_plug_iced::Pixels(8.0),
+            font: Font::default(),
+            align_x: nih_plug_iced::alignment::Horizontal::Left.into(),
+            align_y: align_y.into(),
+            line_height: nih_plug_iced::widget::text::LineHeight::default(),
+            shaping: nih_plug_iced::widget::text::Shaping::default(),
+            max_width: f32::INFINITY,
+        };
+
+        frame.fill_text(label(
+            format!("max {:.1} dB", max_db),
+            1.0,
+            nih_plug_iced::alignment::Vertical::Top,
+        ));
+        frame.fill_text(label(
+            format!("min {:.1} dB", min_db),
+            size.height - 1.0,
+            nih_plug_iced::alignment::Vertical::Bottom,
+        ));
+    }
+}
+
+/// Convert dB level to normalized 0-1 height within the strip
+pub fn normalize_strip_level(level_db: f32) -> f32 {
+    ((level_db - STRIP_MIN_DB) / (STRIP_MAX_DB - STRIP_MIN_DB)).clamp(0.0, 1.0)
+}
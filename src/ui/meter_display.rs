@@ -1,31 +1,117 @@
 use crate::audio::meter::MeterConsumer;
+use crate::ui::spectrum_display::reference_line_stroke;
 use crate::ui::UITheme;
+use crate::{MeterMode, MeterScale, SAPluginParams};
 use nih_plug_iced::widget::canvas::{
-    fill::Rule, gradient::Linear, Fill, Frame, Geometry, Gradient, Path, Program, Style,
+    fill::Rule, gradient::Linear, Fill, Frame, Geometry, Gradient, Path, Program, Stroke, Style,
+    Text,
 };
-use nih_plug_iced::{border::Radius, mouse, Color, Point, Rectangle, Renderer, Size, Theme};
+use nih_plug_iced::{border::Radius, mouse, Color, Font, Point, Rectangle, Renderer, Size, Theme};
+use std::sync::Arc;
+use std::time::Instant;
 
 // Local constants for meter display
 const METER_MAX_DB: f32 = 0.0;
 const METER_MIN_DB: f32 = -60.0;
 const METER_RANGE_DB: f32 = METER_MAX_DB - METER_MIN_DB; // 60dB range
+const MIN_LED_HEIGHT: f32 = 1.0; // Smallest LED worth drawing (px)
+const METER_LED_COUNT: usize = 110; // LEDs per column before the layout drops any
+const LED_TRAIL_FADE_SECONDS: f32 = 0.15; // Time for an extinguished LED to fade out
+const PEAK_OVER_DB: f32 = 0.0; // Peaks at or above this light the cap in the over colour
+
+/// Meter gradient stops from bottom (0.0) to top (1.0)
+const METER_GRADIENT_STOPS: [(f32, Color); 4] = [
+    (
+        0.0,
+        Color::from_rgb(44.0 / 255.0, 67.0 / 255.0, 27.0 / 255.0),
+    ), // Green
+    (
+        0.95,
+        Color::from_rgb(214.0 / 255.0, 198.0 / 255.0, 82.0 / 255.0),
+    ), // Yellow at 95%
+    (0.97, Color::from_rgb(255.0 / 255.0, 140.0 / 255.0, 0.0)), // Orange transition
+    (
+        1.0,
+        Color::from_rgb(255.0 / 255.0, 77.0 / 255.0, 26.0 / 255.0),
+    ), // Red for top 3%
+];
 
 #[derive(Clone, Copy, PartialEq)]
 pub enum Channel {
     Left,
     Right,
+    /// Single bar spanning the meter (aggregate mode)
+    All,
 }
 
 /// Pure meter display component - no processing logic
-/// Reads meter data from MeterConsumer communication channel
+/// Reads meter data from MeterConsumer communication channel; the editor's tick
+/// advances the meter, so drawing has no side effects
 pub struct MeterDisplay {
     /// Communication channel from audio thread
     meter_output: MeterConsumer,
+    /// Plugin parameters for the reference level line
+    plugin_params: Arc<SAPluginParams>,
+    /// Per-LED trail brightness (0.0-1.0) for the left, right and aggregate columns
+    /// Empty while trails are off
+    led_trails: [Vec<f32>; 3],
+    /// Time of the last trail update
+    last_trail_update: Instant,
 }
 
 impl MeterDisplay {
-    pub fn new(meter_output: MeterConsumer) -> Self {
-        Self { meter_output }
+    pub fn new(meter_output: MeterConsumer, plugin_params: Arc<SAPluginParams>) -> Self {
+        Self {
+            meter_output,
+            plugin_params,
+            led_trails: Default::default(),
+            last_trail_update: Instant::now(),
+        }
+    }
+
+    /// Light the trail for every active LED and fade the rest
+    /// Called from the editor's tick after the meter update; frees the trails when disabled
+    pub fn update_trails(&mut self) {
+        let now = Instant::now();
+        let elapsed = now.duration_since(self.last_trail_update).as_secs_f32();
+        self.last_trail_update = now;
+
+        if !self.plugin_params.led_trails.value() {
+            if !self.led_trails[0].is_empty() {
+                self.led_trails = Default::default();
+            }
+            return;
+        }
+
+        let (rms_left, rms_right) = self.meter_output.get_smoothed_rms_levels_or_silence();
+        let rms_all = self.meter_output.get_smoothed_aggregate_rms_or_silence();
+        let fade_step = elapsed / LED_TRAIL_FADE_SECONDS;
+
+        for (trail, level_db) in self
+            .led_trails
+            .iter_mut()
+            .zip([rms_left, rms_right, rms_all])
+        {
+            trail.resize(METER_LED_COUNT, 0.0);
+            let active_leds = calculate_active_leds(normalize_db_level(level_db), METER_LED_COUNT);
+            for (i, brightness) in trail.iter_mut().enumerate() {
+                *brightness = if i < active_leds {
+                    1.0
+                } else {
+                    (*brightness - fade_step).max(0.0)
+                };
+            }
+        }
+    }
+
+    /// Trail brightness for LED `led_index` of `led_total` in a channel's column
+    /// The layout may draw fewer LEDs than `METER_LED_COUNT`, so indices are rescaled
+    fn trail_brightness(&self, channel: Channel, led_index: usize, led_total: usize) -> f32 {
+        let trail = &self.led_trails[channel as usize];
+        if trail.is_empty() || led_total == 0 {
+            return 0.0;
+        }
+        trail[(led_index * trail.len() / led_total).min(trail.len() - 1)]
     }
 }
 
@@ -48,6 +134,17 @@ impl<Message> Program<Message, Theme> for MeterDisplay {
         // Draw level bars with gradient
         self.draw_level_bars(&mut frame, bounds.size());
 
+        // Draw the target level line across both channels
+        if self.plugin_params.reference_enabled.value() {
+            self.draw_reference_line(&mut frame, bounds.size());
+        }
+
+        // Mark the alignment level when reading in analogue units
+        let scale = self.plugin_params.meter_scale.value();
+        if scale != MeterScale::Dbfs {
+            self.draw_alignment_line(&mut frame, bounds.size(), scale);
+        }
+
         vec![frame.into_geometry()]
     }
 }
@@ -59,13 +156,61 @@ impl MeterDisplay {
         frame.fill(&background, Color::from_rgb(0.06, 0.06, 0.08));
     }
 
+    fn draw_reference_line(&self, frame: &mut Frame, size: Size) {
+        let normalized = normalize_db_level(self.plugin_params.reference_db.value());
+        let y = size.height * (1.0 - normalized);
+
+        let path = Path::line(Point::new(0.0, y), Point::new(size.width, y));
+        frame.stroke(&path, reference_line_stroke());
+    }
+
+    /// Solid line at the alignment level, labelled with its value on `scale`
+    fn draw_alignment_line(&self, frame: &mut Frame, size: Size, scale: MeterScale) {
+        let alignment_dbfs = self.plugin_params.meter_alignment.value();
+        let y = size.height * (1.0 - normalize_db_level(alignment_dbfs));
+
+        let path = Path::line(Point::new(0.0, y), Point::new(size.width, y));
+        frame.stroke(
+            &path,
+            Stroke::default()
+                .with_width(1.0)
+                .with_color(UITheme::METER_ALIGNMENT_LINE),
+        );
+
+        frame.fill_text(Text {
+            content: format!(
+                "{:+.0} {}",
+                scale.from_dbfs(alignment_dbfs, alignment_dbfs),
+                scale.unit()
+            ),
+            position: Point::new(size.width / 2.0, y - 1.0),
+            color: UITheme::METER_ALIGNMENT_LINE,
+            size: nih_plug_iced::Pixels(7.0),
+            font: Font::default(),
+            align_x: nih_plug_iced::alignment::Horizontal::Center.into(),
+            align_y: nih_plug_iced::alignment::Vertical::Bottom.into(),
+            line_height: nih_plug_iced::widget::text::LineHeight::default(),
+            shaping: nih_plug_iced::widget::text::Shaping::default(),
+            max_width: f32::INFINITY,
+        });
+    }
+
     fn draw_level_bars(&self, frame: &mut Frame, size: Size) {
-        // UPDATE - Process latest meter data from audio thread
-        // The MeterConsumer handles smoothing and peak hold in the UI thread
-        self.meter_output.update();
+        // Aggregate mode shows one bar for the loudest of all channels
+        if self.plugin_params.meter_mode.value() == MeterMode::Aggregate {
+            let levels = BarLevels {
+                rms_db: self.meter_output.get_smoothed_aggregate_rms_or_silence(),
+                peak_db: self.meter_output.get_smoothed_aggregate_level_or_silence(),
+                peak_hold_db: self.meter_output.get_peak_hold_db_or_silence(),
+            };
+            self.draw_single_level_bar(frame, Point::ORIGIN, size, levels, Channel::All);
+            return;
+        }
 
         // Get smoothed levels for LED display
         let (smooth_left, smooth_right) = self.meter_output.get_smoothed_levels_or_silence();
+        let (rms_left, rms_right) = self.meter_output.get_smoothed_rms_levels_or_silence();
+        let (hold_left, hold_right) = self.meter_output.get_peak_hold_levels_or_silence();
 
         // Draw level bars with consistent gap
         let channel_gap = 1.0; // Same as LED gap
@@ -77,7 +222,11 @@ impl MeterDisplay {
             frame,
             Point::new(0.0, 0.0),
             Size::new(bar_width, size.height),
-            smooth_left,
+            BarLevels {
+                rms_db: rms_left,
+                peak_db: smooth_left,
+                peak_hold_db: hold_left,
+            },
             Channel::Left,
         );
 
@@ -86,23 +235,52 @@ impl MeterDisplay {
             frame,
             Point::new(bar_width + channel_gap, 0.0),
             Size::new(bar_width, size.height),
-            smooth_right,
+            BarLevels {
+                rms_db: rms_right,
+                peak_db: smooth_right,
+                peak_hold_db: hold_right,
+            },
             Channel::Right,
         );
     }
 
+    /// Draw one channel column: RMS as the gradient fill, a bright LED at the
+    /// smoothed peak, and the sticky peak-hold line above it
+    /// With over colouring on, the peak cap and hold line turn red on 0 dBFS overs
     fn draw_single_level_bar(
         &self,
         frame: &mut Frame,
         position: Point,
         size: Size,
-        level_db: f32,
+        levels: BarLevels,
         channel: Channel,
     ) {
-        let led_count = 110;
+        let led_count = METER_LED_COUNT;
         let led_gap = 1.0;
 
-        let leds = generate_meter_leds(position, size, level_db, channel, led_count, led_gap);
+        let fractional = self.plugin_params.fractional_leds.value();
+        let leds = generate_meter_leds(
+            position,
+            size,
+            levels.rms_db,
+            channel,
+            led_count,
+            led_gap,
+            fractional,
+        );
+        let led_total = leds.len();
+        let peak_led = calculate_active_leds(normalize_db_level(levels.peak_db), leds.len());
+        let over_color = self.plugin_params.meter_over_color.value();
+        let peak_color = if over_color && levels.peak_db >= PEAK_OVER_DB {
+            UITheme::METER_PEAK_OVER
+        } else {
+            UITheme::METER_PEAK_MARKER
+        };
+        let hold_color = if over_color && levels.peak_hold_db >= PEAK_OVER_DB {
+            UITheme::METER_PEAK_OVER
+        } else {
+            UITheme::METER_PEAK_HOLD
+        };
 
         let gradient = create_meter_gradient(
             Point::new(position.x, position.y + size.height), // Bottom
@@ -114,16 +292,50 @@ impl MeterDisplay {
             rule: Rule::NonZero,
         };
 
-        for led in leds {
-            if led.is_active {
+        for (i, led) in leds.into_iter().enumerate() {
+            if peak_led > 0 && i == peak_led - 1 {
+                frame.fill(&led.path, peak_color);
+            } else if led.is_active && led.alpha >= 1.0 {
                 frame.fill(&led.path, gradient_fill.clone());
             } else {
                 frame.fill(&led.path, UITheme::BACKGROUND_MAIN);
+                // Partial top LED or a fading trail: its gradient colour, dimmed
+                let alpha = if led.is_active { led.alpha } else { 0.0 };
+                let alpha = alpha.max(self.trail_brightness(channel, i, led_total));
+                if alpha > 0.0 {
+                    let led_center = (i as f32 + 0.5) / led_total as f32;
+                    let color = get_meter_color(led_center);
+                    frame.fill(&led.path, Color { a: alpha, ..color });
+                }
             }
         }
+
+        let hold_normalized = normalize_db_level(levels.peak_hold_db);
+        if hold_normalized > 0.0 {
+            let y = position.y + size.height * (1.0 - hold_normalized);
+            let path = Path::line(
+                Point::new(position.x, y),
+                Point::new(position.x + size.width, y),
+            );
+            frame.stroke(
+                &path,
+                Stroke::default().with_width(1.0).with_color(hold_color),
+            );
+        }
     }
 }
 
+/// The three values shown in one meter column
+#[derive(Clone, Copy)]
+struct BarLevels {
+    /// Filled LED region
+    rms_db: f32,
+    /// Single bright marker LED
+    peak_db: f32,
+    /// Sticky line above the marker
+    peak_hold_db: f32,
+}
+
 /// Convert dB level to normalized 0-1 range for meter display
 ///
 /// Maps the meter's dB range to a 0.0-1.0 scale for visual representation.
@@ -138,15 +350,31 @@ pub fn normalize_db_level(level_db: f32) -> f32 {
 ///
 /// Determines the optimal LED height and positioning to fill the available
 /// space while maintaining consistent gaps between LEDs.
+/// When the container is too short for every LED, the gaps are dropped first
+/// and then the LED count is reduced so each LED keeps `MIN_LED_HEIGHT`.
 /// Returns (led_height, led_gap, total_leds).
 pub fn calculate_led_layout(
     container_height: f32,
     led_count: usize,
     led_gap: f32,
 ) -> (f32, f32, usize) {
-    let total_gap_space = (led_count - 1) as f32 * led_gap;
-    let led_height = (container_height - total_gap_space) / led_count as f32;
-    (led_height, led_gap, led_count)
+    let container_height = container_height.max(0.0);
+    let led_gap = led_gap.max(0.0);
+    if led_count == 0 || container_height < MIN_LED_HEIGHT {
+        return (0.0, 0.0, 0);
+    }
+
+    let led_height_for =
+        |count: usize, gap: f32| (container_height - (count - 1) as f32 * gap) / count as f32;
+
+    let led_height = led_height_for(led_count, led_gap);
+    if led_height >= MIN_LED_HEIGHT {
+        return (led_height, led_gap, led_count);
+    }
+
+    // Too short for the gaps: pack LEDs edge to edge, fewer if needed
+    let fitted_count = led_count.min((container_height / MIN_LED_HEIGHT) as usize);
+    (led_height_for(fitted_count, 0.0), 0.0, fitted_count)
 }
 
 /// Calculate number of active LEDs based on normalized level
@@ -154,7 +382,31 @@ pub fn calculate_led_layout(
 /// Converts a 0.0-1.0 level to the corresponding number of LEDs that should
 /// be illuminated, with proper rounding for smooth visual transitions.
 pub fn calculate_active_leds(normalized_level: f32, total_leds: usize) -> usize {
-    (normalized_level * total_leds as f32).round() as usize
+    let normalized_level = if normalized_level.is_nan() {
+        0.0
+    } else {
+        normalized_level.clamp(0.0, 1.0)
+    };
+    ((normalized_level * total_leds as f32).round() as usize).min(total_leds)
+}
+
+/// Calculate fully lit LEDs plus how far the next LED is lit
+///
+/// Unlike `calculate_active_leds` this doesn't round, so the level can move
+/// smoothly through the top LED. Returns (full_leds, partial_fraction) where
+/// the fraction (0.0-1.0) applies to the LED at index `full_leds`.
+pub fn calculate_led_fill(normalized_level: f32, total_leds: usize) -> (usize, f32) {
+    let normalized_level = if normalized_level.is_nan() {
+        0.0
+    } else {
+        normalized_level.clamp(0.0, 1.0)
+    };
+    let lit = normalized_level * total_leds as f32;
+    let full_leds = (lit.floor() as usize).min(total_leds);
+    if full_leds == total_leds {
+        return (total_leds, 0.0);
+    }
+    (full_leds, lit - full_leds as f32)
 }
 
 /// Calculate LED position for a specific LED index
@@ -168,9 +420,12 @@ pub fn calculate_led_position(
     led_height: f32,
     led_gap: f32,
 ) -> Point {
-    let led_y = container_position.y + container_height
+    let led_height = led_height.max(0.0);
+    let led_gap = led_gap.max(0.0);
+    let led_y = container_position.y + container_height.max(0.0)
         - (led_index as f32 * (led_height + led_gap) + led_height);
-    Point::new(container_position.x, led_y)
+    // Never place an LED above the top of the container
+    Point::new(container_position.x, led_y.max(container_position.y))
 }
 
 /// Create gradient for meter LED visualization
@@ -178,26 +433,37 @@ pub fn calculate_led_position(
 /// Generates a linear gradient from green (bottom) through yellow to red (top),
 /// matching professional audio meter color schemes.
 pub fn create_meter_gradient(start_point: Point, end_point: Point) -> Linear {
-    Linear::new(start_point, end_point)
-        .add_stop(
-            0.0,
-            Color::from_rgb(44.0 / 255.0, 67.0 / 255.0, 27.0 / 255.0),
-        ) // Green
-        .add_stop(
-            0.95,
-            Color::from_rgb(214.0 / 255.0, 198.0 / 255.0, 82.0 / 255.0),
-        ) // Yellow at 95%
-        .add_stop(0.97, Color::from_rgb(255.0 / 255.0, 140.0 / 255.0, 0.0)) // Orange transition
-        .add_stop(
-            1.0,
-            Color::from_rgb(255.0 / 255.0, 77.0 / 255.0, 26.0 / 255.0),
-        ) // Red for top 3%
+    METER_GRADIENT_STOPS.iter().fold(
+        Linear::new(start_point, end_point),
+        |gradient, &(offset, color)| gradient.add_stop(offset, color),
+    )
+}
+
+/// Meter colour at a normalized (0.0-1.0) level, sampled from the meter gradient
+///
+/// Lets widgets that draw flat rects (rather than a gradient fill) match the meter.
+pub fn get_meter_color(normalized_level: f32) -> Color {
+    let level = normalized_level.clamp(0.0, 1.0);
+    METER_GRADIENT_STOPS
+        .windows(2)
+        .find(|stops| level <= stops[1].0)
+        .map(|stops| {
+            let ((start, from), (end, to)) = (stops[0], stops[1]);
+            let t = (level - start) / (end - start);
+            Color::from_rgb(
+                from.r + (to.r - from.r) * t,
+                from.g + (to.g - from.g) * t,
+                from.b + (to.b - from.b) * t,
+            )
+        })
+        .unwrap_or(METER_GRADIENT_STOPS[METER_GRADIENT_STOPS.len() - 1].1)
 }
 
 /// Create rounded rectangle path for channel-specific LED shape
 ///
 /// Generates the appropriate rounded rectangle path for left or right channel LEDs.
-/// Left channel has rounded left corners, right channel has rounded right corners.
+/// Left channel has rounded left corners, right channel has rounded right corners,
+/// and the aggregate bar is rounded on both sides.
 pub fn create_channel_led_path(position: Point, size: Size, radius: f32, channel: Channel) -> Path {
     match channel {
         Channel::Left => Path::rounded_rectangle(
@@ -220,6 +486,7 @@ pub fn create_channel_led_path(position: Point, size: Size, radius: f32, channel
                 bottom_left: 0.0,
             },
         ),
+        Channel::All => Path::rounded_rectangle(position, size, Radius::from(radius)),
     }
 }
 
@@ -229,7 +496,12 @@ pub fn create_channel_led_path(position: Point, size: Size, radius: f32, channel
 /// sizes, and active/inactive states for each LED. Returns a vector of
 /// LED rendering information.
 pub struct LedInfo {
+    /// Top-left corner of the LED
+    pub position: Point,
+    pub size: Size,
     pub is_active: bool,
+    /// Brightness of an active LED: 1.0 when fully lit, less for a partial top LED
+    pub alpha: f32,
     pub path: Path,
 }
 
@@ -240,10 +512,17 @@ pub fn generate_meter_leds(
     channel: Channel,
     led_count: usize,
     led_gap: f32,
+    fractional: bool,
 ) -> Vec<LedInfo> {
     let normalized_level = normalize_db_level(level_db);
-    let (led_height, _, _) = calculate_led_layout(container_size.height, led_count, led_gap);
-    let active_leds = calculate_active_leds(normalized_level, led_count);
+    let (led_height, led_gap, led_count) =
+        calculate_led_layout(container_size.height, led_count, led_gap);
+    // Fractional mode lights the next LED partially instead of rounding to whole LEDs
+    let (active_leds, top_alpha) = if fractional {
+        calculate_led_fill(normalized_level, led_count)
+    } else {
+        (calculate_active_leds(normalized_level, led_count), 0.0)
+    };
     let radius = led_height / 2.0;
 
     (0..led_count)
@@ -256,10 +535,161 @@ pub fn generate_meter_leds(
                 led_gap,
             );
             let size = Size::new(container_size.width, led_height);
-            let is_active = i < active_leds;
+            let (is_active, alpha) = if i < active_leds {
+                (true, 1.0)
+            } else if i == active_leds && top_alpha > 0.0 {
+                (true, top_alpha)
+            } else {
+                (false, 0.0)
+            };
             let path = create_channel_led_path(position, size, radius, channel);
 
-            LedInfo { is_active, path }
+            LedInfo {
+                position,
+                size,
+                is_active,
+                alpha,
+                path,
+            }
         })
         .collect()
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// A 20-LED column whose LEDs come out 9 px tall with 2 px gaps
+    const TEST_BOUNDS: Rectangle = Rectangle {
+        x: 10.0,
+        y: 20.0,
+        width: 8.0,
+        height: 218.0,
+    };
+    const TEST_LED_COUNT: usize = 20;
+    const TEST_LED_GAP: f32 = 2.0;
+
+    fn test_leds(level_db: f32, fractional: bool) -> Vec<LedInfo> {
+        generate_meter_leds(
+            TEST_BOUNDS.position(),
+            TEST_BOUNDS.size(),
+            level_db,
+            Channel::All,
+            TEST_LED_COUNT,
+            TEST_LED_GAP,
+            fractional,
+        )
+    }
+
+    #[test]
+    fn full_scale_lights_every_led() {
+        for fractional in [false, true] {
+            for level_db in [METER_MAX_DB, 6.0] {
+                let leds = test_leds(level_db, fractional);
+                assert_eq!(leds.len(), TEST_LED_COUNT);
+                for (index, led) in leds.iter().enumerate() {
+                    assert!(
+                        led.is_active && led.alpha == 1.0,
+                        "LED {index} not fully lit at {level_db} dBFS (fractional {fractional})"
+                    );
+                }
+            }
+        }
+    }
+
+    #[test]
+    fn silence_lights_no_led() {
+        for fractional in [false, true] {
+            let leds = test_leds(METER_MIN_DB - 20.0, fractional);
+            assert!(leds.iter().all(|led| !led.is_active));
+        }
+    }
+
+    #[test]
+    fn leds_stack_up_from_the_bottom_of_the_bounds() {
+        let leds = test_leds(-30.0, false);
+        let led_height = 9.0;
+        let bottom = TEST_BOUNDS.y + TEST_BOUNDS.height;
+        for (index, led) in leds.iter().enumerate() {
+            let expected_y = bottom - index as f32 * (led_height + TEST_LED_GAP) - led_height;
+            assert_eq!(led.position.x, TEST_BOUNDS.x, "LED {index} x");
+            assert!(
+                (led.position.y - expected_y).abs() < 1e-4,
+                "LED {index} at y {} instead of {expected_y}",
+                led.position.y
+            );
+            assert_eq!(led.size, Size::new(TEST_BOUNDS.width, led_height));
+        }
+
+        // The column fills the bounds exactly, bottom LED first
+        let (first, last) = (&leds[0], &leds[TEST_LED_COUNT - 1]);
+        assert!((first.position.y + first.size.height - bottom).abs() < 1e-4);
+        assert!((last.position.y - TEST_BOUNDS.y).abs() < 1e-4);
+    }
+
+    #[test]
+    fn a_single_led_fills_its_container() {
+        let mut state: u64 = 0x1ed;
+        for _ in 0..100 {
+            state = state
+                .wrapping_mul(6364136223846793005)
+                .wrapping_add(1442695040888963407);
+            let height = 1.0 + (state >> 40) as f32 / (1u64 << 24) as f32 * 400.0;
+            let gap = (state >> 20 & 0xff) as f32 / 32.0;
+            let origin = Point::new(5.0, (state >> 10 & 0x3ff) as f32);
+
+            let (led_height, led_gap, led_count) = calculate_led_layout(height, 1, gap);
+            assert_eq!(led_count, 1);
+            assert!(
+                (led_height - height).abs() < 1e-3,
+                "{led_height} of {height}"
+            );
+
+            let position = calculate_led_position(0, origin, height, led_height, led_gap);
+            assert_eq!(position.x, origin.x);
+            assert!(
+                (position.y - origin.y).abs() < 1e-3,
+                "{height} px container, {gap} px gap: LED at y {} instead of {}",
+                position.y,
+                origin.y
+            );
+        }
+    }
+
+    #[test]
+    fn zero_height_layouts_stay_inside_the_container() {
+        // A collapsed meter has no LEDs to draw
+        assert_eq!(
+            calculate_led_layout(0.0, METER_LED_COUNT, 2.0),
+            (0.0, 0.0, 0)
+        );
+        let empty = generate_meter_leds(
+            Point::new(3.0, 40.0),
+            Size::new(8.0, 0.0),
+            METER_MAX_DB,
+            Channel::Left,
+            METER_LED_COUNT,
+            2.0,
+            false,
+        );
+        assert!(empty.is_empty());
+
+        // Positions for stale or inconsistent sizes never leave the top of the container
+        let mut state: u64 = 0xfeed;
+        for _ in 0..500 {
+            state = state
+                .wrapping_mul(6364136223846793005)
+                .wrapping_add(1442695040888963407);
+            let origin = Point::new(0.0, (state >> 54) as f32);
+            let led_index = (state >> 40) as usize % METER_LED_COUNT;
+            let led_height = (state >> 24 & 0xff) as f32 / 16.0;
+            let led_gap = (state >> 16 & 0xff) as f32 / 64.0 - 1.0;
+
+            let position = calculate_led_position(led_index, origin, 0.0, led_height, led_gap);
+            assert_eq!(
+                position, origin,
+                "LED {led_index}, {led_height} px tall with a {led_gap} px gap"
+            );
+        }
+    }
+}
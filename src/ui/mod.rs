@@ -1,11 +1,24 @@
+pub mod bass_inspector;
+pub mod frequency_markers;
 pub mod grid_overlay;
+pub mod label_layout;
+pub mod level_history;
 pub mod meter_display;
+pub mod peak_history;
+pub mod png_export;
 pub mod spectrum_display;
+pub mod spectrum_history;
 pub mod style;
+pub mod tilt_meter;
 pub mod shaders;  // Our new WGPU shaders
 
+pub use bass_inspector::BassInspector;
 pub use grid_overlay::GridOverlay;
+pub use level_history::LevelHistoryDisplay;
 pub use meter_display::MeterDisplay;
+pub use peak_history::PeakHistoryDisplay;
 pub use spectrum_display::SpectrumDisplay;
+pub use spectrum_history::SpectrumHistory;
 pub use style::UITheme;
-pub use shaders::GridShader;  // Re-export for easy access
+pub use tilt_meter::TiltMeter;
+pub use shaders::{GridLabels, GridShader};  // Re-export for easy access
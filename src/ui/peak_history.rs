@@ -0,0 +1,160 @@
+use crate::ui::meter_display::{get_meter_color, normalize_db_level};
+use crate::ui::UITheme;
+use nih_plug::util;
+use nih_plug_iced::widget::canvas::{Frame, Geometry, Path, Program};
+use nih_plug_iced::{mouse, Point, Rectangle, Renderer, Size, Theme};
+use std::collections::VecDeque;
+use std::time::Instant;
+
+/// How often the strip samples the meter (Hz)
+const SAMPLE_RATE_HZ: f32 = 20.0;
+
+/// How much history the strip shows (seconds)
+const HISTORY_SECONDS: f32 = 20.0;
+
+/// Number of samples kept (`HISTORY_SECONDS` at `SAMPLE_RATE_HZ`)
+const HISTORY_LEN: usize = (HISTORY_SECONDS * SAMPLE_RATE_HZ) as usize;
+
+/// Peaks at or above this level count as clips
+const CLIP_DB: f32 = 0.0;
+
+/// Width of the latched clip indicator at the right edge
+const CLIP_LATCH_WIDTH: f32 = 3.0;
+
+/// Scrolling strip of recent per-channel peaks
+/// Left channel in the top half, right in the bottom, newest sample at the right edge
+pub struct PeakHistoryDisplay {
+    /// Oldest sample first, (left, right) in dB
+    peaks: VecDeque<(f32, f32)>,
+    /// Loudest peaks seen since the last sample, so ticks between samples aren't lost
+    pending: (f32, f32),
+    /// Time of the last sample
+    last_sample: Instant,
+    /// Whether each channel has clipped since the last clear
+    clipped: [bool; 2],
+}
+
+impl PeakHistoryDisplay {
+    pub fn new() -> Self {
+        Self {
+            peaks: VecDeque::with_capacity(HISTORY_LEN),
+            pending: (util::MINUS_INFINITY_DB, util::MINUS_INFINITY_DB),
+            last_sample: Instant::now(),
+            clipped: [false; 2],
+        }
+    }
+
+    /// Fold the current peaks in and record a sample if one is due
+    /// Called every tick; throttles itself to `SAMPLE_RATE_HZ`
+    pub fn update(&mut self, left_db: f32, right_db: f32) {
+        self.pending = (self.pending.0.max(left_db), self.pending.1.max(right_db));
+
+        let now = Instant::now();
+        if now.duration_since(self.last_sample).as_secs_f32() < 1.0 / SAMPLE_RATE_HZ {
+            return;
+        }
+        self.last_sample = now;
+
+        let (left_db, right_db) = self.pending;
+        self.clipped[0] |= left_db >= CLIP_DB;
+        self.clipped[1] |= right_db >= CLIP_DB;
+
+        if self.peaks.len() == HISTORY_LEN {
+            self.peaks.pop_front();
+        }
+        self.peaks.push_back(self.pending);
+        self.pending = (util::MINUS_INFINITY_DB, util::MINUS_INFINITY_DB);
+    }
+
+    /// Drop all recorded peaks and clear the clip latches
+    pub fn clear(&mut self) {
+        self.peaks.clear();
+        self.clear_clip_latches();
+    }
+
+    /// Clear the clip latches but keep the recorded peaks
+    pub fn clear_clip_latches(&mut self) {
+        self.clipped = [false; 2];
+    }
+}
+
+impl<Message> Program<Message, Theme> for PeakHistoryDisplay {
+    type State = ();
+
+    fn draw(
+        &self,
+        _state: &Self::State,
+        renderer: &Renderer,
+        _theme: &Theme,
+        bounds: Rectangle,
+        _cursor: mouse::Cursor,
+    ) -> Vec<Geometry> {
+        let mut frame = Frame::new(renderer, bounds.size());
+
+        let background = Path::rectangle(Point::ORIGIN, bounds.size());
+        frame.fill(&background, UITheme::BACKGROUND_MAIN);
+
+        let half_height = bounds.height / 2.0;
+        let lane_height = half_height - 0.5; // 1px gap between the channels
+        self.draw_channel_lane(&mut frame, bounds.size(), 0.0, lane_height, |peaks| peaks.0);
+        self.draw_channel_lane(
+            &mut frame,
+            bounds.size(),
+            half_height + 0.5,
+            lane_height,
+            |peaks| peaks.1,
+        );
+
+        for (channel, &clipped) in self.clipped.iter().enumerate() {
+            if clipped {
+                let latch = Path::rectangle(
+                    Point::new(
+                        bounds.width - CLIP_LATCH_WIDTH,
+                        channel as f32 * half_height,
+                    ),
+                    Size::new(CLIP_LATCH_WIDTH, half_height),
+                );
+                frame.fill(&latch, UITheme::CLIP_INDICATOR);
+            }
+        }
+
+        vec![frame.into_geometry()]
+    }
+}
+
+impl PeakHistoryDisplay {
+    /// Draw one channel's samples as 1px columns rising from the lane's bottom edge
+    /// Clipped samples get a full-height red tick
+    fn draw_channel_lane(
+        &self,
+        frame: &mut Frame,
+        size: Size,
+        lane_top: f32,
+        lane_height: f32,
+        channel_peak: fn(&(f32, f32)) -> f32,
+    ) {
+        let step = (size.width - CLIP_LATCH_WIDTH) / HISTORY_LEN as f32;
+        let start_x = size.width - CLIP_LATCH_WIDTH - self.peaks.len() as f32 * step;
+        let lane_bottom = lane_top + lane_height;
+
+        for (i, peaks) in self.peaks.iter().enumerate() {
+            let peak_db = channel_peak(peaks);
+            let x = start_x + i as f32 * step;
+
+            if peak_db >= CLIP_DB {
+                let tick = Path::rectangle(Point::new(x, lane_top), Size::new(1.0, lane_height));
+                frame.fill(&tick, UITheme::CLIP_INDICATOR);
+                continue;
+            }
+
+            let normalized = normalize_db_level(peak_db);
+            if normalized <= 0.0 {
+                continue;
+            }
+            let height = lane_height * normalized;
+            let column =
+                Path::rectangle(Point::new(x, lane_bottom - height), Size::new(1.0, height));
+            frame.fill(&column, get_meter_color(normalized));
+        }
+    }
+}
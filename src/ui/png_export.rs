@@ -0,0 +1,542 @@
+use crate::audio::constants;
+use crate::audio::spectrum::SpectrumData;
+use crate::ui::meter_display::{get_meter_color, normalize_db_level};
+use crate::ui::spectrum_display::{interpolate_bin_value, spectrum_to_screen};
+use crate::ui::UITheme;
+use nih_plug_iced::Color;
+use std::io::{BufWriter, Write};
+use std::path::{Path, PathBuf};
+use std::time::{SystemTime, UNIX_EPOCH};
+use thiserror::Error;
+
+/// Size of an exported image in pixels
+pub const EXPORT_WIDTH: u32 = 1200;
+pub const EXPORT_HEIGHT: u32 = 600;
+
+/// Width of the meter column on the right of an export
+const METER_COLUMN_WIDTH: u32 = 48;
+
+/// Gap between the two meter bars and around them
+const METER_BAR_GAP: u32 = 6;
+
+/// Height of the frequency label row under the spectrum
+const LABEL_ROW_HEIGHT: u32 = 20;
+
+/// Each glyph pixel is drawn as a square of this many image pixels
+const GLYPH_SCALE: u32 = 2;
+
+/// Glyph cell size in glyph pixels, including one column of spacing
+const GLYPH_WIDTH: u32 = 4;
+const GLYPH_HEIGHT: u32 = 5;
+
+/// Half the thickness of the spectrum curve in pixels
+const CURVE_HALF_WIDTH: f32 = 0.75;
+
+/// Everything an export draws, captured on the UI thread
+/// The background task renders from this copy alone and never reads shared state
+#[derive(Debug, Clone)]
+pub struct Screenshot {
+    /// Display spectrum in dB, evenly spaced from DC to Nyquist
+    pub spectrum: SpectrumData,
+    /// Sample rate and floor the spectrum was analysed with
+    pub sample_rate: f32,
+    pub floor_db: f32,
+    /// Frequency axis span (Hz)
+    pub frequency_range: (f32, f32),
+    /// Level axis span (dB)
+    pub db_range: (f32, f32),
+    /// Frequency grid lines as (frequency, is_major), as the live grid draws them
+    pub frequency_grid: Vec<(f32, bool)>,
+    /// Level grid lines (dB)
+    pub db_grid: Vec<f32>,
+    /// Smoothed left and right meter levels (dB)
+    pub meter_levels_db: (f32, f32),
+}
+
+/// Errors from writing an export to disk
+#[derive(Debug, Error)]
+pub enum ExportError {
+    /// The file couldn't be created or written
+    #[error("Failed to write {path}: {source}")]
+    Io {
+        path: PathBuf,
+        source: std::io::Error,
+    },
+
+    /// PNG encoding failed
+    #[error("Failed to encode PNG: {0}")]
+    Encoding(#[from] png::EncodingError),
+}
+
+/// An 8-bit RGBA image drawn on the CPU
+pub struct Image {
+    width: u32,
+    height: u32,
+    /// Rows top to bottom, four bytes per pixel
+    pixels: Vec<u8>,
+}
+
+impl Image {
+    /// Opaque image filled with `background`
+    fn new(width: u32, height: u32, background: Color) -> Self {
+        let pixel = to_rgba8(Color {
+            a: 1.0,
+            ..background
+        });
+        Self {
+            width,
+            height,
+            pixels: pixel.repeat((width * height) as usize),
+        }
+    }
+
+    /// Blend `color` over the pixels whose centres lie in `x0..x1` x `y0..y1`
+    /// Coordinates are in pixels and clipped to the image
+    fn fill_rect(&mut self, x0: f32, y0: f32, x1: f32, y1: f32, color: Color) {
+        let clip = |value: f32, limit: u32| value.round().clamp(0.0, limit as f32) as u32;
+        let (x0, x1) = (clip(x0.min(x1), self.width), clip(x0.max(x1), self.width));
+        let (y0, y1) = (clip(y0.min(y1), self.height), clip(y0.max(y1), self.height));
+
+        for y in y0..y1 {
+            for x in x0..x1 {
+                let index = ((y * self.width + x) * 4) as usize;
+                blend_over(&mut self.pixels[index..index + 4], color);
+            }
+        }
+    }
+
+    /// One pixel wide vertical line at `x` from `y0` to `y1`
+    fn vertical_line(&mut self, x: f32, y0: f32, y1: f32, color: Color) {
+        self.fill_rect(x - 0.5, y0, x + 0.5, y1, color);
+    }
+
+    /// One pixel high horizontal line at `y` from `x0` to `x1`
+    fn horizontal_line(&mut self, y: f32, x0: f32, x1: f32, color: Color) {
+        self.fill_rect(x0, y - 0.5, x1, y + 0.5, color);
+    }
+
+    /// Draw `label` with its top-left corner at (`x`, `y`)
+    /// Characters without a glyph are left as blank space
+    fn draw_label(&mut self, label: &str, x: f32, y: f32, color: Color) {
+        let cell = GLYPH_SCALE as f32;
+        for (column, character) in label.chars().enumerate() {
+            let Some(rows) = glyph(character) else {
+                continue;
+            };
+            let left = x + (column as u32 * GLYPH_WIDTH * GLYPH_SCALE) as f32;
+            for (row, bits) in rows.iter().enumerate() {
+                for bit in 0..3 {
+                    if bits & (0b100 >> bit) != 0 {
+                        let px = left + bit as f32 * cell;
+                        let py = y + row as f32 * cell;
+                        self.fill_rect(px, py, px + cell, py + cell, color);
+                    }
+                }
+            }
+        }
+    }
+}
+
+/// Width of `label` in pixels when drawn with `Image::draw_label`
+fn label_width(label: &str) -> f32 {
+    (label.chars().count() as u32 * GLYPH_WIDTH * GLYPH_SCALE) as f32
+}
+
+/// Rows of a 3x5 glyph, most significant of the low three bits on the left
+/// Covers what the axis labels use: digits, '-', '.' and the 'K' of "1K"
+fn glyph(character: char) -> Option<[u8; 5]> {
+    Some(match character {
+        '0' => [0b111, 0b101, 0b101, 0b101, 0b111],
+        '1' => [0b010, 0b110, 0b010, 0b010, 0b111],
+        '2' => [0b111, 0b001, 0b111, 0b100, 0b111],
+        '3' => [0b111, 0b001, 0b111, 0b001, 0b111],
+        '4' => [0b101, 0b101, 0b111, 0b001, 0b001],
+        '5' => [0b111, 0b100, 0b111, 0b001, 0b111],
+        '6' => [0b111, 0b100, 0b111, 0b101, 0b111],
+        '7' => [0b111, 0b001, 0b010, 0b010, 0b010],
+        '8' => [0b111, 0b101, 0b111, 0b101, 0b111],
+        '9' => [0b111, 0b101, 0b111, 0b001, 0b111],
+        'K' => [0b101, 0b101, 0b110, 0b101, 0b101],
+        '-' => [0b000, 0b000, 0b111, 0b000, 0b000],
+        '.' => [0b000, 0b000, 0b000, 0b000, 0b010],
+        _ => return None,
+    })
+}
+
+/// Quantise a colour to 8-bit RGBA
+fn to_rgba8(color: Color) -> [u8; 4] {
+    let channel = |value: f32| (value.clamp(0.0, 1.0) * 255.0).round() as u8;
+    [
+        channel(color.r),
+        channel(color.g),
+        channel(color.b),
+        channel(color.a),
+    ]
+}
+
+/// Source-over blend of `color` onto an opaque RGBA pixel
+fn blend_over(pixel: &mut [u8], color: Color) {
+    let alpha = color.a.clamp(0.0, 1.0);
+    for (channel, source) in pixel.iter_mut().zip([color.r, color.g, color.b]) {
+        let destination = *channel as f32 / 255.0;
+        let blended = source * alpha + destination * (1.0 - alpha);
+        *channel = (blended.clamp(0.0, 1.0) * 255.0).round() as u8;
+    }
+}
+
+/// Draw the spectrum, its grid and the meters into a `width` x `height` image
+///
+/// Uses the live display's mapping (`spectrum_to_screen`, `interpolate_bin_value`) so an
+/// export lines up with what was on screen. The curve is sampled once per pixel column;
+/// frequencies above Nyquist are left empty, as on screen.
+pub fn render(screenshot: &Screenshot, width: u32, height: u32) -> Image {
+    let mut image = Image::new(width, height, UITheme::BACKGROUND_MAIN);
+
+    let spectrum_width = width.saturating_sub(METER_COLUMN_WIDTH) as f32;
+    let spectrum_height = height.saturating_sub(LABEL_ROW_HEIGHT) as f32;
+    let (min_frequency, max_frequency) = screenshot.frequency_range;
+    let (min_db, _) = screenshot.db_range;
+    let to_screen = |frequency: f32, db: f32| {
+        spectrum_to_screen(
+            frequency,
+            db,
+            screenshot.frequency_range,
+            screenshot.db_range,
+            spectrum_width,
+            spectrum_height,
+        )
+    };
+
+    // Grid first so the curve and its fill sit on top
+    for &(frequency, is_major) in &screenshot.frequency_grid {
+        let color = if is_major {
+            UITheme::GRID_LINE
+        } else {
+            UITheme::GRID_LINE_LIGHT
+        };
+        let x = to_screen(frequency, min_db).x;
+        image.vertical_line(x, 0.0, spectrum_height, color);
+    }
+    for &db in &screenshot.db_grid {
+        let y = to_screen(min_frequency, db).y;
+        image.horizontal_line(y, 0.0, spectrum_width, UITheme::GRID_LINE);
+
+        let label = constants::format_db_marker(db);
+        let label_y = (y + 2.0).min(spectrum_height - (GLYPH_HEIGHT * GLYPH_SCALE) as f32);
+        image.draw_label(&label, 3.0, label_y, UITheme::TEXT_DB_MARKER);
+    }
+    for &(frequency, label) in constants::visible_frequency_markers(min_frequency) {
+        if frequency > max_frequency {
+            break;
+        }
+        let x = to_screen(frequency, min_db).x;
+        let label_x =
+            (x - label_width(label) / 2.0).clamp(0.0, spectrum_width - label_width(label));
+        image.draw_label(
+            label,
+            label_x,
+            spectrum_height + 5.0,
+            UITheme::TEXT_SECONDARY,
+        );
+    }
+
+    // Curve, filled down to the bottom edge
+    let nyquist = screenshot.sample_rate / 2.0;
+    let mut previous_y = None;
+    for column in 0..spectrum_width as u32 {
+        let position = (column as f32 + 0.5) / spectrum_width;
+        let frequency = constants::log_position_to_freq(position, min_frequency, max_frequency);
+        if frequency > nyquist {
+            break;
+        }
+        let db = interpolate_bin_value(
+            &screenshot.spectrum,
+            frequency,
+            screenshot.sample_rate,
+            screenshot.floor_db,
+        );
+        let y = to_screen(frequency, db).y;
+        let x = column as f32;
+
+        image.fill_rect(x, y, x + 1.0, spectrum_height, UITheme::SPECTRUM_FILL);
+        let previous = previous_y.unwrap_or(y);
+        image.fill_rect(
+            x,
+            y.min(previous) - CURVE_HALF_WIDTH,
+            x + 1.0,
+            y.max(previous) + CURVE_HALF_WIDTH,
+            UITheme::SPECTRUM_LINE,
+        );
+        previous_y = Some(y);
+    }
+
+    // Left and right meter bars, coloured along the meter gradient
+    let bar_width = (METER_COLUMN_WIDTH - 3 * METER_BAR_GAP) as f32 / 2.0;
+    let (left_db, right_db) = screenshot.meter_levels_db;
+    for (bar, level_db) in [left_db, right_db].into_iter().enumerate() {
+        let x0 =
+            spectrum_width + (METER_BAR_GAP as f32) * (bar as f32 + 1.0) + bar_width * bar as f32;
+        let lit_rows = (normalize_db_level(level_db) * spectrum_height).round() as u32;
+        for row in 0..lit_rows {
+            let y = spectrum_height - row as f32 - 1.0;
+            let color = get_meter_color(row as f32 / spectrum_height);
+            image.fill_rect(x0, y, x0 + bar_width, y + 1.0, color);
+        }
+    }
+
+    image
+}
+
+/// Encode `image` as an 8-bit RGBA PNG
+pub fn write_png(image: &Image, writer: impl Write) -> Result<(), png::EncodingError> {
+    let mut encoder = png::Encoder::new(writer, image.width, image.height);
+    encoder.set_color(png::ColorType::Rgba);
+    encoder.set_depth(png::BitDepth::Eight);
+    let mut writer = encoder.write_header()?;
+    writer.write_image_data(&image.pixels)?;
+    writer.finish()
+}
+
+/// Render `screenshot` at the export size and save it to `path`, or to a numbered
+/// variant of it if that file already exists
+/// Runs on the plugin's background thread; file IO never touches the UI thread
+///
+/// # Returns
+/// The path the image was written to
+pub fn export_png(screenshot: &Screenshot, path: &Path) -> Result<PathBuf, ExportError> {
+    let image = render(screenshot, EXPORT_WIDTH, EXPORT_HEIGHT);
+    let (file, path) = create_export_file(path)?;
+    write_png(&image, BufWriter::new(file))?;
+    Ok(path)
+}
+
+/// Most numbered variants tried before an export gives up
+const MAX_EXPORT_NAME_ATTEMPTS: u32 = 1000;
+
+/// Create `path`, or `<stem>-2.png`, `<stem>-3.png`, ... if it's taken
+/// Files are only ever created new, so an earlier export is never overwritten
+pub fn create_export_file(path: &Path) -> Result<(std::fs::File, PathBuf), ExportError> {
+    let stem = path
+        .file_stem()
+        .map(|stem| stem.to_string_lossy().into_owned())
+        .unwrap_or_default();
+    let mut candidate = path.to_path_buf();
+    for attempt in 2..=MAX_EXPORT_NAME_ATTEMPTS {
+        match std::fs::OpenOptions::new()
+            .write(true)
+            .create_new(true)
+            .open(&candidate)
+        {
+            Ok(file) => return Ok((file, candidate)),
+            Err(error) if error.kind() == std::io::ErrorKind::AlreadyExists => {
+                candidate = path.with_file_name(format!("{stem}-{attempt}.png"));
+            }
+            Err(source) => {
+                return Err(ExportError::Io {
+                    path: candidate,
+                    source,
+                })
+            }
+        }
+    }
+    Err(ExportError::Io {
+        path: candidate,
+        source: std::io::ErrorKind::AlreadyExists.into(),
+    })
+}
+
+/// Folder exports are saved to: the user's home directory, or the temporary
+/// directory when the host doesn't pass one through
+pub fn export_directory() -> PathBuf {
+    std::env::var_os("HOME")
+        .or_else(|| std::env::var_os("USERPROFILE"))
+        .map(PathBuf::from)
+        .unwrap_or_else(std::env::temp_dir)
+}
+
+/// Path for an export taken at `time`, stamped to the millisecond
+/// `create_export_file` numbers it if two exports still land on the same name
+pub fn export_path(directory: &Path, time: SystemTime) -> PathBuf {
+    let milliseconds = time
+        .duration_since(UNIX_EPOCH)
+        .map(|elapsed| elapsed.as_millis())
+        .unwrap_or(0);
+    directory.join(format!("spectrum-{milliseconds}.png"))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::audio::spectrum::MAX_SPECTRUM_BINS;
+    use std::time::Duration;
+
+    const TEST_WIDTH: u32 = 600;
+    const TEST_HEIGHT: u32 = 320;
+
+    /// Height of the spectrum area in a test render
+    const SPECTRUM_HEIGHT: f32 = (TEST_HEIGHT - LABEL_ROW_HEIGHT) as f32;
+
+    /// A flat spectrum at `level_db` on a -60..0 dB, 20 Hz..20 kHz display with no grid
+    fn flat_screenshot(level_db: f32, sample_rate: f32) -> Screenshot {
+        Screenshot {
+            spectrum: vec![level_db; MAX_SPECTRUM_BINS],
+            sample_rate,
+            floor_db: -140.0,
+            frequency_range: (constants::MIN_FREQUENCY, constants::MAX_FREQUENCY),
+            db_range: (-60.0, 0.0),
+            frequency_grid: Vec::new(),
+            db_grid: Vec::new(),
+            meter_levels_db: (-100.0, -100.0),
+        }
+    }
+
+    /// Colour of the pixel at (`x`, `y`)
+    fn pixel(image: &Image, x: u32, y: u32) -> [u8; 4] {
+        let index = ((y * image.width + x) * 4) as usize;
+        image.pixels[index..index + 4].try_into().unwrap()
+    }
+
+    fn background() -> [u8; 4] {
+        to_rgba8(UITheme::BACKGROUND_MAIN)
+    }
+
+    #[test]
+    fn curve_is_drawn_at_its_level_and_filled_below() {
+        let image = render(&flat_screenshot(-30.0, 48000.0), TEST_WIDTH, TEST_HEIGHT);
+
+        // -30 dB is half way down a -60..0 dB display
+        let curve_y = (SPECTRUM_HEIGHT / 2.0) as u32;
+        let column = 300;
+        assert_eq!(
+            pixel(&image, column, curve_y),
+            to_rgba8(UITheme::SPECTRUM_LINE)
+        );
+        assert_eq!(pixel(&image, column, curve_y - 20), background());
+
+        let fill = pixel(&image, column, curve_y + 40);
+        assert_ne!(fill, background(), "area under the curve should be filled");
+        assert_ne!(fill, to_rgba8(UITheme::SPECTRUM_LINE));
+    }
+
+    #[test]
+    fn curve_stops_at_nyquist() {
+        let sample_rate = 16000.0;
+        let image = render(
+            &flat_screenshot(-30.0, sample_rate),
+            TEST_WIDTH,
+            TEST_HEIGHT,
+        );
+        let spectrum_width = (TEST_WIDTH - METER_COLUMN_WIDTH) as f32;
+        let nyquist_x = constants::freq_to_log_position(
+            sample_rate / 2.0,
+            constants::MIN_FREQUENCY,
+            constants::MAX_FREQUENCY,
+        ) * spectrum_width;
+
+        let bottom = SPECTRUM_HEIGHT as u32 - 1;
+        assert_ne!(pixel(&image, nyquist_x as u32 - 2, bottom), background());
+        assert_eq!(pixel(&image, nyquist_x as u32 + 2, bottom), background());
+    }
+
+    #[test]
+    fn grid_lines_use_the_display_mapping() {
+        let mut screenshot = flat_screenshot(-140.0, 48000.0);
+        screenshot.frequency_grid = vec![(1000.0, true)];
+        screenshot.db_grid = vec![-20.0];
+        let image = render(&screenshot, TEST_WIDTH, TEST_HEIGHT);
+
+        let spectrum_width = (TEST_WIDTH - METER_COLUMN_WIDTH) as f32;
+        let crossing = spectrum_to_screen(
+            1000.0,
+            -20.0,
+            screenshot.frequency_range,
+            screenshot.db_range,
+            spectrum_width,
+            SPECTRUM_HEIGHT,
+        );
+        // A one pixel line covers the pixel containing its position
+        let (x, y) = (crossing.x.floor() as u32, crossing.y.floor() as u32);
+
+        // Away from the labels at the left edge and the crossing itself
+        assert_ne!(pixel(&image, x, 10), background(), "1 kHz line missing");
+        assert_eq!(pixel(&image, x + 3, 10), background());
+        assert_ne!(
+            pixel(&image, x + 50, y),
+            background(),
+            "-20 dB line missing"
+        );
+        assert_eq!(pixel(&image, x + 50, y + 3), background());
+    }
+
+    #[test]
+    fn meter_bars_fill_to_their_level() {
+        let mut screenshot = flat_screenshot(-140.0, 48000.0);
+        screenshot.meter_levels_db = (-30.0, -100.0);
+        let image = render(&screenshot, TEST_WIDTH, TEST_HEIGHT);
+
+        let spectrum_width = TEST_WIDTH - METER_COLUMN_WIDTH;
+        let bar_width = (METER_COLUMN_WIDTH - 3 * METER_BAR_GAP) / 2;
+        let left_x = spectrum_width + METER_BAR_GAP + bar_width / 2;
+        let right_x = left_x + bar_width + METER_BAR_GAP;
+
+        // -30 dB lights the bottom half of the -60..0 dB meter
+        let half = (SPECTRUM_HEIGHT / 2.0) as u32;
+        assert_ne!(pixel(&image, left_x, half + 5), background());
+        assert_eq!(pixel(&image, left_x, half - 5), background());
+        assert_eq!(
+            pixel(&image, right_x, SPECTRUM_HEIGHT as u32 - 1),
+            background()
+        );
+    }
+
+    #[test]
+    fn png_round_trips() {
+        let image = render(&flat_screenshot(-30.0, 48000.0), TEST_WIDTH, TEST_HEIGHT);
+        let mut encoded = Vec::new();
+        write_png(&image, &mut encoded).unwrap();
+
+        let mut reader = png::Decoder::new(encoded.as_slice()).read_info().unwrap();
+        let mut decoded = vec![0; reader.output_buffer_size()];
+        let info = reader.next_frame(&mut decoded).unwrap();
+        assert_eq!((info.width, info.height), (TEST_WIDTH, TEST_HEIGHT));
+        assert_eq!(info.color_type, png::ColorType::Rgba);
+        assert_eq!(&decoded[..info.buffer_size()], image.pixels.as_slice());
+    }
+
+    #[test]
+    fn export_paths_are_timestamped_to_the_millisecond() {
+        let directory = Path::new("exports");
+        let time = UNIX_EPOCH + Duration::from_millis(1_700_000_000_123);
+        assert_eq!(
+            export_path(directory, time),
+            directory.join("spectrum-1700000000123.png")
+        );
+
+        // Two clicks in the same second still get different names
+        let later = time + Duration::from_millis(400);
+        assert_ne!(export_path(directory, time), export_path(directory, later));
+    }
+
+    #[test]
+    fn exports_to_a_taken_name_are_numbered_instead_of_overwriting() {
+        let directory = std::env::temp_dir().join(format!(
+            "spectrum-analyser-export-test-{}",
+            std::process::id()
+        ));
+        std::fs::create_dir_all(&directory).unwrap();
+        let path = directory.join("spectrum-1700000000123.png");
+
+        let (mut first, first_path) = create_export_file(&path).unwrap();
+        first.write_all(b"first").unwrap();
+        drop(first);
+        let (_, second_path) = create_export_file(&path).unwrap();
+        let (_, third_path) = create_export_file(&path).unwrap();
+
+        assert_eq!(first_path, path);
+        assert_eq!(second_path, directory.join("spectrum-1700000000123-2.png"));
+        assert_eq!(third_path, directory.join("spectrum-1700000000123-3.png"));
+        assert_eq!(std::fs::read(&path).unwrap(), b"first");
+
+        std::fs::remove_dir_all(&directory).unwrap();
+    }
+}
@@ -3,9 +3,9 @@ struct Uniforms {
     line_width: f32,
     spectrum_margin_right: f32,
     spectrum_margin_bottom: f32,
-    grid_inset_right: f32,
     _padding0: f32,
     _padding1: f32,
+    _padding2: f32,
 }
 
 // Storage buffer for grid line data
@@ -63,15 +63,12 @@ fn fs_main(input: VertexOutput) -> @location(0) vec4<f32> {
     // UVs are widget-local, unlike @builtin(position) which is window-absolute
     let pixel_coord = input.uv * uniforms.resolution;
 
-    // Calculate spectrum area
+    // Calculate spectrum area, the same rect the spectrum and canvas grid draw in
     let spectrum_width = uniforms.resolution.x - uniforms.spectrum_margin_right;
     let spectrum_height = uniforms.resolution.y - uniforms.spectrum_margin_bottom;
 
-    // Calculate grid drawing area
-    let grid_max_x = spectrum_width - uniforms.grid_inset_right;
-
     // Early exit for out-of-bounds pixels
-    if pixel_coord.x >= grid_max_x || pixel_coord.y >= spectrum_height {
+    if pixel_coord.x >= spectrum_width || pixel_coord.y >= spectrum_height {
         return vec4<f32>(0.0, 0.0, 0.0, 0.0);
     }
 
@@ -1,15 +1,44 @@
 use crate::audio::constants;
+use crate::ui::label_layout::{
+    db_label_priority, estimate_label_width, fit_labels, frequency_label_priority, AxisGutters,
+    AxisUnits, LabelSpan, FREQUENCY_LABEL_OFFSET, GUTTER_PADDING,
+};
 use crate::ui::UITheme;
+use crate::{GridDensity, SAPluginParams};
 use nih_plug_iced::widget::canvas::{Frame, Geometry, Path, Program, Text};
 use nih_plug_iced::{mouse, Font, Point, Rectangle, Renderer, Size, Theme};
+use std::sync::Arc;
 
 /// Label overlay for the shader grid - draws text labels only
 /// This renders on top of the shader grid using canvas text rendering
-pub struct GridLabels;
+pub struct GridLabels {
+    /// Plugin parameters for the frequency and amplitude ranges the labels are drawn for
+    plugin_params: Arc<SAPluginParams>,
+}
 
 impl GridLabels {
-    pub fn new() -> Self {
-        Self
+    pub fn new(plugin_params: Arc<SAPluginParams>) -> Self {
+        Self { plugin_params }
+    }
+
+    /// Lowest frequency shown, the left edge of the shader grid
+    fn min_frequency(&self) -> f32 {
+        self.plugin_params.low_frequency.value().to_hz()
+    }
+
+    /// Amplitude range the labels are drawn for, the same one as the shader grid
+    fn db_range(&self) -> (f32, f32) {
+        self.plugin_params.range.value().to_db_range()
+    }
+
+    /// Units drawn as axis titles, None when they're turned off
+    fn titles(&self) -> Option<AxisUnits> {
+        AxisUnits::for_params(&self.plugin_params)
+    }
+
+    /// Label gutters for the current amplitude range and titles
+    fn gutters(&self) -> AxisGutters {
+        AxisGutters::for_params(&self.plugin_params)
     }
 }
 
@@ -32,51 +61,135 @@ impl<Message> Program<Message, Theme> for GridLabels {
         // Draw dB scale labels (right side)
         self.draw_db_labels(&mut frame, bounds.size());
 
+        // Draw axis titles (gutter corners)
+        self.draw_axis_titles(&mut frame, bounds.size());
+
         vec![frame.into_geometry()]
     }
 }
 
 impl GridLabels {
-    /// Draw frequency labels at the bottom
+    /// Draw frequency labels at the bottom, dropping minor labels that would overlap
     fn draw_frequency_labels(&self, frame: &mut Frame, size: Size) {
-        let spectrum_width = size.width - UITheme::SPECTRUM_MARGIN_RIGHT;
+        let gutters = self.gutters();
+        let spectrum_width = size.width - gutters.right;
+        let spectrum_height = size.height - gutters.bottom;
+        let text_size = UITheme::FREQUENCY_LABEL_SIZE;
+        let min_frequency = self.min_frequency();
+        let label_x = |freq: f32| {
+            let log_pos =
+                constants::freq_to_log_position(freq, min_frequency, constants::MAX_FREQUENCY);
+            log_pos * spectrum_width
+        };
+
+        // Labels are left-aligned, so each spans rightwards from its line
+        let markers = constants::visible_frequency_markers(min_frequency);
+        let spans: Vec<LabelSpan> = markers
+            .iter()
+            .map(|&(freq, label)| LabelSpan {
+                start: label_x(freq),
+                end: label_x(freq) + estimate_label_width(label, text_size),
+                priority: frequency_label_priority(freq),
+            })
+            .collect();
+        let title = self
+            .titles()
+            .map(|units| units.frequency_title_span(size.width));
+        let fitted: Vec<(f32, &str)> = markers
+            .iter()
+            .zip(fit_labels(&spans, title, size.width))
+            .filter_map(|(&marker, keep)| keep.then_some(marker))
+            .collect();
 
         self.draw_labels(
             frame,
-            constants::FREQUENCY_MARKERS,
+            &fitted,
             UITheme::TEXT_SECONDARY,
-            nih_plug_iced::Pixels(9.0),
-            |&(freq, _)| {
-                let log_pos = constants::freq_to_log_position(freq);
-                let spectrum_height = size.height - UITheme::SPECTRUM_MARGIN_BOTTOM;
-                (log_pos * spectrum_width, spectrum_height + 10.0) // Just below the spectrum area
-            },
+            nih_plug_iced::Pixels(text_size),
+            // Just below the spectrum area
+            |&(freq, _)| (label_x(freq), spectrum_height + FREQUENCY_LABEL_OFFSET),
             nih_plug_iced::alignment::Horizontal::Left,
             nih_plug_iced::alignment::Vertical::Top,
         );
     }
 
-    /// Draw dB scale labels on the right side
+    /// Draw dB scale labels on the right side, dropping labels that would overlap
     fn draw_db_labels(&self, frame: &mut Frame, size: Size) {
-        let spectrum_height = size.height - UITheme::SPECTRUM_MARGIN_BOTTOM;
+        let gutters = self.gutters();
+        let spectrum_height = size.height - gutters.bottom;
+        let text_size = UITheme::DB_LABEL_SIZE;
+        let (min_db, max_db) = self.db_range();
+        let label_y = |db_value: f32| {
+            let normalized = constants::db_to_normalized(db_value, min_db, max_db);
+            let y = spectrum_height * (1.0 - normalized);
+            // Clamp Y position to keep text within visible area
+            y.max(5.0).min(spectrum_height - 5.0)
+        };
+
+        // Labels stay at the normal step so dense grids don't crowd the text
+        let labels: Vec<(f32, String)> =
+            constants::db_grid_markers(min_db, max_db, GridDensity::Normal)
+                .into_iter()
+                .map(|db| (db, constants::format_db_marker(db)))
+                .collect();
+
+        // Labels are vertically centred, so each spans half the text size either side
+        let spans: Vec<LabelSpan> = labels
+            .iter()
+            .enumerate()
+            .map(|(index, &(db, _))| LabelSpan {
+                start: label_y(db) - text_size / 2.0,
+                end: label_y(db) + text_size / 2.0,
+                priority: db_label_priority(index),
+            })
+            .collect();
+        let title = self.titles().map(|units| units.level_title_span());
+        let markers: Vec<(f32, &str)> = labels
+            .iter()
+            .zip(fit_labels(&spans, title, spectrum_height))
+            .filter_map(|((db, label), keep)| keep.then_some((*db, label.as_str())))
+            .collect();
 
         self.draw_labels(
             frame,
-            constants::DB_MARKERS,
+            &markers,
             UITheme::TEXT_DB_MARKER,
-            nih_plug_iced::Pixels(10.0),
-            |&(db_value, _)| {
-                let normalized = constants::db_to_normalized(db_value);
-                let y = spectrum_height * (1.0 - normalized);
-                // Clamp Y position to keep text within visible area
-                let clamped_y = y.max(5.0).min(spectrum_height - 5.0);
-                (size.width - 5.0, clamped_y)
-            },
+            nih_plug_iced::Pixels(text_size),
+            |&(db_value, _)| (size.width - GUTTER_PADDING, label_y(db_value)),
             nih_plug_iced::alignment::Horizontal::Right,
             nih_plug_iced::alignment::Vertical::Center,
         );
     }
 
+    /// Draw the unit titles, "Hz" in the bottom-right corner and the level unit at the
+    /// top of the dB labels; tick labels in their way were already dropped
+    fn draw_axis_titles(&self, frame: &mut Frame, size: Size) {
+        let Some(units) = self.titles() else {
+            return;
+        };
+        let spectrum_height = size.height - self.gutters().bottom;
+        let title_x = size.width - GUTTER_PADDING;
+
+        self.draw_labels(
+            frame,
+            &[(0.0, units.frequency)],
+            UITheme::TEXT_SECONDARY,
+            nih_plug_iced::Pixels(UITheme::AXIS_TITLE_SIZE),
+            |_| (title_x, spectrum_height + FREQUENCY_LABEL_OFFSET),
+            nih_plug_iced::alignment::Horizontal::Right,
+            nih_plug_iced::alignment::Vertical::Top,
+        );
+        self.draw_labels(
+            frame,
+            &[(0.0, units.level)],
+            UITheme::TEXT_SECONDARY,
+            nih_plug_iced::Pixels(UITheme::AXIS_TITLE_SIZE),
+            |_| (title_x, units.level_title_span().start),
+            nih_plug_iced::alignment::Horizontal::Right,
+            nih_plug_iced::alignment::Vertical::Top,
+        );
+    }
+
     /// Generic function to draw text labels
     fn draw_labels(
         &self,
@@ -108,9 +221,3 @@ impl GridLabels {
         }
     }
 }
-
-impl Default for GridLabels {
-    fn default() -> Self {
-        Self::new()
-    }
-}
@@ -1,18 +1,25 @@
+pub mod labels;
 pub mod pipeline;
 
-use pipeline::GridPipeline;
+use pipeline::{GridLayout, GridPipeline};
 
+use crate::ui::label_layout::AxisGutters;
+use crate::SAPluginParams;
 use nih_plug_iced::{mouse, Rectangle};
 use nih_plug_iced::widget::shader::{self, Primitive};
 use nih_plug_iced::renderer::wgpu::wgpu;
+use std::sync::Arc;
 
 // GridShader implements the Program trait, which is iced's interface for custom shaders
 // It acts as the bridge between iced's widget system and our WGPU rendering code
-pub struct GridShader;
+pub struct GridShader {
+    // Plugin parameters for the configurable frequency range
+    plugin_params: Arc<SAPluginParams>,
+}
 
 impl GridShader {
-    pub fn new() -> Self {
-        Self
+    pub fn new(plugin_params: Arc<SAPluginParams>) -> Self {
+        Self { plugin_params }
     }
 }
 
@@ -35,11 +42,22 @@ impl<Message> shader::Program<Message> for GridShader {
         _cursor: mouse::Cursor,   // Mouse position (unused here)
         bounds: Rectangle,        // Widget bounds in screen space
     ) -> Self::Primitive {
-        GridPrimitive::new(bounds)
+        let (min_db, max_db) = self.plugin_params.range.value().to_db_range();
+        GridPrimitive::new(
+            bounds,
+            AxisGutters::for_params(&self.plugin_params),
+            GridLayout {
+                min_frequency: self.plugin_params.low_frequency.value().to_hz(),
+                musical_root_hz: self.plugin_params.musical_grid.value().to_hz(),
+                min_db,
+                max_db,
+                density: self.plugin_params.grid_density.value(),
+            },
+        )
     }
 
     // Note: update() method omitted - using default implementation
-    // The default returns None, which is perfect for our static grid
+    // The default returns None; the grid only changes with its parameters
 }
 
 // GridPrimitive holds the data needed for one frame of rendering
@@ -47,12 +65,18 @@ impl<Message> shader::Program<Message> for GridShader {
 #[derive(Debug)]
 pub struct GridPrimitive {
     bounds: Rectangle,
+    // Label gutters, sized from the labels of the current amplitude range
+    gutters: AxisGutters,
+    // Frequency range, grid style and amplitude range to draw lines for
+    layout: GridLayout,
 }
 
 impl GridPrimitive {
-    pub fn new(bounds: Rectangle) -> Self {
+    pub fn new(bounds: Rectangle, gutters: AxisGutters, layout: GridLayout) -> Self {
         Self {
             bounds,
+            gutters,
+            layout,
         }
     }
 }
@@ -70,14 +94,14 @@ impl Primitive for GridPrimitive {
         _queue: &wgpu::Queue,
         format: wgpu::TextureFormat,
     ) -> Self::Renderer {
-        GridPipeline::new(device, format)
+        GridPipeline::new(device, format, self.layout)
     }
 
     // Called before rendering to prepare GPU resources
     fn prepare(
         &self,
         renderer: &mut Self::Renderer,
-        _device: &wgpu::Device,
+        device: &wgpu::Device,
         queue: &wgpu::Queue,
         _bounds: &Rectangle,
         viewport: &nih_plug_iced::graphics::Viewport,
@@ -87,9 +111,13 @@ impl Primitive for GridPrimitive {
         // not the logical size which would be scaled/zoomed
         let physical_size = viewport.physical_size();
 
+        // Rebuild the line buffers if the frequency range, grid style, amplitude range or
+        // density changed
+        renderer.update_layout(device, self.layout);
+
         // Update uniforms with physical dimensions
         // This uploads the new data to the GPU
-        renderer.update_with_physical_size(queue, &self.bounds, physical_size);
+        renderer.update_with_physical_size(queue, &self.bounds, physical_size, self.gutters);
     }
 
     // Called to execute the actual rendering
@@ -104,10 +132,3 @@ impl Primitive for GridPrimitive {
         renderer.render(encoder, target, *clip_bounds);
     }
 }
-
-// Default implementation for convenience
-impl Default for GridShader {
-    fn default() -> Self {
-        Self::new()
-    }
-}
\ No newline at end of file
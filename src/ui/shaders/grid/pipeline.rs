@@ -1,11 +1,13 @@
+use crate::audio::constants;
+use crate::ui::label_layout::AxisGutters;
+use crate::GridDensity;
 use bytemuck::{Pod, Zeroable};
-use nih_plug_iced::Rectangle;
 use nih_plug_iced::renderer::wgpu::wgpu::{
-    self as wgpu, BindGroup, BindGroupDescriptor, BindGroupEntry, BindGroupLayoutDescriptor,
-    BindGroupLayoutEntry, BindingType, BufferBindingType, BufferUsages, Device, Queue,
-    RenderPipeline, ShaderStages, TextureFormat,
+    self as wgpu, BindGroup, BindGroupDescriptor, BindGroupEntry, BindGroupLayout,
+    BindGroupLayoutDescriptor, BindGroupLayoutEntry, BindingType, BufferBindingType, BufferUsages,
+    Device, Queue, RenderPipeline, ShaderStages, TextureFormat,
 };
-use crate::audio::constants;
+use nih_plug_iced::Rectangle;
 
 // Uniforms are data passed from CPU to GPU that remain constant during a draw call
 // They're used for things like screen resolution, time, user settings, etc.
@@ -26,31 +28,43 @@ pub struct Uniforms {
     // Width of all grid lines in pixels (uniform thickness)
     pub line_width: f32,
 
-    // Spectrum area margins (matching UITheme constants)
+    // Spectrum area margins (the label gutters shared with the spectrum canvas)
+    // The grid fills exactly the area left inside them, like the canvas grid and spectrum
     pub spectrum_margin_right: f32,
     pub spectrum_margin_bottom: f32,
 
-    // Inset from right edge where grid stops (leaves room for frequency labels)
-    pub grid_inset_right: f32,
-
     // Padding for alignment (ensures struct meets GPU alignment requirements)
     // WGSL uniform buffers require proper alignment - do not remove
-    pub _padding: [f32; 2],
+    pub _padding: [f32; 3],
 }
 
 impl Uniforms {
-    pub fn new(bounds: &Rectangle) -> Self {
+    pub fn new(bounds: &Rectangle, gutters: AxisGutters) -> Self {
         Self {
             resolution: [bounds.width, bounds.height],
-            line_width: 0.8,         // Line anti-aliasing width (smoothstep falloff distance)
-            spectrum_margin_right: 30.0,  // Right margin for frequency labels
-            spectrum_margin_bottom: 30.0, // Bottom margin for amplitude labels
-            grid_inset_right: 20.0,  // Stop grid 20px before right edge for label space
-            _padding: [0.0, 0.0],    // Alignment padding
+            line_width: 0.8, // Line anti-aliasing width (smoothstep falloff distance)
+            spectrum_margin_right: gutters.right, // Right margin for dB labels
+            spectrum_margin_bottom: gutters.bottom, // Bottom margin for frequency labels
+            _padding: [0.0; 3], // Alignment padding
         }
     }
 }
 
+// Everything the line buffers are built from
+// The pipeline rebuilds its buffers whenever this changes
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct GridLayout {
+    // Lower edge of the frequency axis in Hz
+    pub min_frequency: f32,
+    // Root of the musical-interval grid in Hz, None for the decade grid
+    pub musical_root_hz: Option<f32>,
+    // Active amplitude range in dB
+    pub min_db: f32,
+    pub max_db: f32,
+    // How many minor lines are drawn between the majors on both axes
+    pub density: GridDensity,
+}
+
 // Storage buffer structure matching the WGSL definition
 // This holds metadata about our grid lines
 #[repr(C)]
@@ -70,23 +84,29 @@ pub struct GridMetadata {
 //
 // The flag array structure allows O(1) lookup in the fragment shader to determine
 // line type without nested loops, improving per-pixel performance
-fn build_grid_data() -> (GridMetadata, Vec<f32>) {
+fn build_grid_data(layout: GridLayout) -> (GridMetadata, Vec<f32>) {
     let mut positions = Vec::new();
 
-    // Add dB line positions (normalized Y values)
-    let db_markers = constants::DB_MARKERS;
-    for &(db, _) in db_markers {
-        let normalized = constants::db_to_normalized(db);
+    // Add dB line positions (normalized Y values) for the active amplitude range
+    let db_markers = constants::db_grid_markers(layout.min_db, layout.max_db, layout.density);
+    for &db in db_markers.iter() {
+        let normalized = constants::db_to_normalized(db, layout.min_db, layout.max_db);
         positions.push(normalized);
     }
     let db_line_count = db_markers.len() as u32;
 
     // Generate frequency positions with major/minor distinction
-    let freq_positions = constants::generate_frequency_grid_positions();
+    let freq_positions = constants::grid_positions(
+        layout.min_frequency,
+        constants::MAX_FREQUENCY,
+        layout.musical_root_hz,
+        layout.density,
+    );
 
     // First, add all frequency positions
     for &(freq, _is_major) in freq_positions.iter() {
-        let log_pos = constants::freq_to_log_position(freq);
+        let log_pos =
+            constants::freq_to_log_position(freq, layout.min_frequency, constants::MAX_FREQUENCY);
         positions.push(log_pos);
     }
     let freq_line_count = freq_positions.len() as u32;
@@ -130,10 +150,93 @@ pub struct GridPipeline {
     // Bind group links our buffers/textures to shader variables
     // It's like connecting wires between CPU data and GPU shader inputs
     bind_group: BindGroup,
+
+    // Kept so the bind group can be rebuilt when the line buffers change
+    bind_group_layout: BindGroupLayout,
+
+    // Frequency range, grid style and amplitude range the line buffers were built for
+    layout: GridLayout,
+}
+
+// Line data buffers plus the bind group that points at them
+// Rebuilt together because storage buffer sizes depend on the line count
+struct GridBuffers {
+    grid_metadata_buffer: wgpu::Buffer,
+    line_positions_buffer: wgpu::Buffer,
+    bind_group: BindGroup,
+}
+
+impl GridBuffers {
+    fn new(
+        device: &Device,
+        bind_group_layout: &BindGroupLayout,
+        uniform_buffer: &wgpu::Buffer,
+        layout: GridLayout,
+    ) -> Self {
+        // Build grid data from constants and the configured layout
+        let (metadata, positions) = build_grid_data(layout);
+
+        // Storage buffer for grid metadata
+        let grid_metadata_buffer = device.create_buffer(&wgpu::BufferDescriptor {
+            label: Some("Grid Metadata Buffer"),
+            size: std::mem::size_of::<GridMetadata>() as u64,
+            usage: BufferUsages::STORAGE | BufferUsages::COPY_DST,
+            mapped_at_creation: true,  // Map immediately to write data
+        });
+
+        // Write metadata to buffer
+        {
+            let mut buffer_view = grid_metadata_buffer.slice(..).get_mapped_range_mut();
+            buffer_view.copy_from_slice(bytemuck::bytes_of(&metadata));
+        }
+        grid_metadata_buffer.unmap();
+
+        // Storage buffer for line positions
+        let line_positions_buffer = device.create_buffer(&wgpu::BufferDescriptor {
+            label: Some("Grid Line Positions Buffer"),
+            size: (positions.len() * std::mem::size_of::<f32>()) as u64,
+            usage: BufferUsages::STORAGE | BufferUsages::COPY_DST,
+            mapped_at_creation: true,  // Map immediately to write data
+        });
+
+        // Write positions to buffer
+        {
+            let mut buffer_view = line_positions_buffer.slice(..).get_mapped_range_mut();
+            buffer_view.copy_from_slice(bytemuck::cast_slice(&positions));
+        }
+        line_positions_buffer.unmap();
+
+        // This connects our actual buffers to the bind group layout
+        // It's like plugging in the actual data sources
+        let bind_group = device.create_bind_group(&BindGroupDescriptor {
+            label: Some("Grid Bind Group"),
+            layout: bind_group_layout,
+            entries: &[
+                BindGroupEntry {
+                    binding: 0,  // Uniform buffer
+                    resource: uniform_buffer.as_entire_binding(),
+                },
+                BindGroupEntry {
+                    binding: 1,  // Grid metadata storage buffer
+                    resource: grid_metadata_buffer.as_entire_binding(),
+                },
+                BindGroupEntry {
+                    binding: 2,  // Line positions storage buffer
+                    resource: line_positions_buffer.as_entire_binding(),
+                },
+            ],
+        });
+
+        Self {
+            grid_metadata_buffer,
+            line_positions_buffer,
+            bind_group,
+        }
+    }
 }
 
 impl GridPipeline {
-    pub fn new(device: &Device, format: TextureFormat) -> Self {
+    pub fn new(device: &Device, format: TextureFormat, layout: GridLayout) -> Self {
         // Step 1: Compile our WGSL shader code
         // The shader is embedded in the binary using include_str!
         // This happens at compile time, so the shader becomes part of the executable
@@ -249,10 +352,7 @@ impl GridPipeline {
             multiview: None,  // Not using multiview rendering
         });
 
-        // Step 5: Build grid data from constants
-        let (metadata, positions) = build_grid_data();
-
-        // Step 6: Create GPU buffers
+        // Step 5: Create GPU buffers
         // Uniform buffer for basic parameters
         let uniform_buffer = device.create_buffer(&wgpu::BufferDescriptor {
             label: Some("Grid Uniform Buffer"),
@@ -261,57 +361,12 @@ impl GridPipeline {
             mapped_at_creation: false,  // Don't map to CPU memory immediately
         });
 
-        // Storage buffer for grid metadata
-        let grid_metadata_buffer = device.create_buffer(&wgpu::BufferDescriptor {
-            label: Some("Grid Metadata Buffer"),
-            size: std::mem::size_of::<GridMetadata>() as u64,
-            usage: BufferUsages::STORAGE | BufferUsages::COPY_DST,
-            mapped_at_creation: true,  // Map immediately to write data
-        });
-
-        // Write metadata to buffer
-        {
-            let mut buffer_view = grid_metadata_buffer.slice(..).get_mapped_range_mut();
-            buffer_view.copy_from_slice(bytemuck::bytes_of(&metadata));
-        }
-        grid_metadata_buffer.unmap();
-
-        // Storage buffer for line positions
-        let line_positions_buffer = device.create_buffer(&wgpu::BufferDescriptor {
-            label: Some("Grid Line Positions Buffer"),
-            size: (positions.len() * std::mem::size_of::<f32>()) as u64,
-            usage: BufferUsages::STORAGE | BufferUsages::COPY_DST,
-            mapped_at_creation: true,  // Map immediately to write data
-        });
-
-        // Write positions to buffer
-        {
-            let mut buffer_view = line_positions_buffer.slice(..).get_mapped_range_mut();
-            buffer_view.copy_from_slice(bytemuck::cast_slice(&positions));
-        }
-        line_positions_buffer.unmap();
-
-        // Step 7: Create bind group
-        // This connects our actual buffers to the bind group layout
-        // It's like plugging in the actual data sources
-        let bind_group = device.create_bind_group(&BindGroupDescriptor {
-            label: Some("Grid Bind Group"),
-            layout: &bind_group_layout,
-            entries: &[
-                BindGroupEntry {
-                    binding: 0,  // Uniform buffer
-                    resource: uniform_buffer.as_entire_binding(),
-                },
-                BindGroupEntry {
-                    binding: 1,  // Grid metadata storage buffer
-                    resource: grid_metadata_buffer.as_entire_binding(),
-                },
-                BindGroupEntry {
-                    binding: 2,  // Line positions storage buffer
-                    resource: line_positions_buffer.as_entire_binding(),
-                },
-            ],
-        });
+        // Step 6: Create line buffers and the bind group
+        let GridBuffers {
+            grid_metadata_buffer,
+            line_positions_buffer,
+            bind_group,
+        } = GridBuffers::new(device, &bind_group_layout, &uniform_buffer, layout);
 
         Self {
             render_pipeline,
@@ -319,20 +374,41 @@ impl GridPipeline {
             grid_metadata_buffer,
             line_positions_buffer,
             bind_group,
+            bind_group_layout,
+            layout,
+        }
+    }
+
+    // Rebuild line buffers when the frequency range, grid style or amplitude range changes
+    // The line count changes with all of them, so the buffers are recreated rather than written
+    pub fn update_layout(&mut self, device: &Device, layout: GridLayout) {
+        if layout == self.layout {
+            return;
         }
+
+        let buffers = GridBuffers::new(
+            device,
+            &self.bind_group_layout,
+            &self.uniform_buffer,
+            layout,
+        );
+        self.grid_metadata_buffer = buffers.grid_metadata_buffer;
+        self.line_positions_buffer = buffers.line_positions_buffer;
+        self.bind_group = buffers.bind_group;
+        self.layout = layout;
     }
 
     // Update uniform data when window resizes or settings change
     #[allow(dead_code)]
-    pub fn update(&mut self, queue: &Queue, bounds: &Rectangle) {
-        self.update_with_bounds(queue, bounds);
+    pub fn update(&mut self, queue: &Queue, bounds: &Rectangle, gutters: AxisGutters) {
+        self.update_with_bounds(queue, bounds, gutters);
     }
 
     // Update uniform data with current bounds (logical size)
     // Note: This may cause zooming if scale factor changes
-    pub fn update_with_bounds(&mut self, queue: &Queue, bounds: &Rectangle) {
+    pub fn update_with_bounds(&mut self, queue: &Queue, bounds: &Rectangle, gutters: AxisGutters) {
         // Create new uniforms with current bounds
-        let uniforms = Uniforms::new(bounds);
+        let uniforms = Uniforms::new(bounds, gutters);
 
         // Write the uniform data to GPU
         // bytemuck::bytes_of safely converts our struct to raw bytes
@@ -346,6 +422,7 @@ impl GridPipeline {
         queue: &Queue,
         bounds: &Rectangle,
         physical_size: nih_plug_iced::Size<u32>,
+        gutters: AxisGutters,
     ) {
         // Calculate scale factor from physical vs logical size
         let scale_x = physical_size.width as f32 / bounds.width;
@@ -357,10 +434,9 @@ impl GridPipeline {
             resolution: [physical_size.width as f32, physical_size.height as f32],
             line_width: 0.8,
             // Scale margins from logical to physical space
-            spectrum_margin_right: 30.0 * scale_x,
-            spectrum_margin_bottom: 30.0 * scale_y,
-            grid_inset_right: 20.0 * scale_x,
-            _padding: [0.0, 0.0],
+            spectrum_margin_right: gutters.right * scale_x,
+            spectrum_margin_bottom: gutters.bottom * scale_y,
+            _padding: [0.0; 3],
         };
 
         // Write the uniform data to GPU
@@ -370,8 +446,15 @@ impl GridPipeline {
     // Alternative update method that accepts line counts (currently unused)
     // Line counts are determined by constants in build_grid_data()
     #[allow(dead_code)]
-    pub fn update_with_lines(&mut self, queue: &Queue, bounds: &Rectangle, _h_lines: u32, _v_lines: u32) {
-        self.update_with_bounds(queue, bounds);
+    pub fn update_with_lines(
+        &mut self,
+        queue: &Queue,
+        bounds: &Rectangle,
+        gutters: AxisGutters,
+        _h_lines: u32,
+        _v_lines: u32,
+    ) {
+        self.update_with_bounds(queue, bounds, gutters);
     }
 
     // Render the grid to the screen
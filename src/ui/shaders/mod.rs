@@ -3,4 +3,5 @@ pub mod grid;
 // pub mod meter;     // Coming later
 
 // Re-export commonly used types for convenience
+pub use grid::labels::GridLabels;
 pub use grid::GridShader;
\ No newline at end of file
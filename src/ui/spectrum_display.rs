@@ -1,44 +1,413 @@
-use crate::audio::spectrum::{SpectrumConsumer, SpectrumData};
+use crate::audio::constants;
+use crate::audio::errors::{ReadFailureTracker, SpectrumError};
+use crate::audio::spectrum::{
+    SpectrumConsumer, SpectrumData, SpectrumFrame, SpectrumPeak, MAX_PEAK_MARKERS,
+};
+use crate::ui::frequency_markers::draw_frequency_markers;
+use crate::ui::label_layout::AxisGutters;
+use crate::ui::png_export::Screenshot;
+use crate::ui::spectrum_history::{SpectrumHistory, HISTORY_CAPACITY};
 use crate::ui::UITheme;
-use crate::{ResolutionLevel, SAPluginParams};
-use atomic_float::AtomicF32;
-use nih_plug_iced::widget::canvas::{self, Frame, Geometry, Path, Program, Stroke};
-use nih_plug_iced::{mouse, Point, Rectangle, Renderer, Size, Theme};
-use std::sync::{atomic::Ordering, Arc};
+use crate::{
+    CurveSmoothness, CurveStyle, PercentileWindow, ResolutionLevel, SAPluginParams,
+    SpectrumInterpolation,
+};
+use nih_plug::util;
+use nih_plug_iced::widget::canvas::{self, Frame, Geometry, LineDash, Path, Program, Stroke, Text};
+use nih_plug_iced::{keyboard, mouse, Color, Font, Point, Rectangle, Renderer, Size, Theme};
+use std::ops::Range;
+use std::sync::Arc;
+use std::time::Instant;
+
+/// Meter level range below the low-level threshold over which the spectrum fades (dB)
+const LOW_LEVEL_FADE_RANGE_DB: f32 = 12.0;
+
+/// Opacity of the spectrum once the meter level is a full fade range below threshold
+const LOW_LEVEL_MIN_OPACITY: f32 = 0.3;
+
+/// How far above the display floor the curve must rise before it is filled (px)
+const FILL_FLOOR_EPSILON_PX: f32 = 0.5;
+
+/// Number of bins the display aims for when interpolating a coarse spectrum
+const TARGET_INTERPOLATED_BINS: usize = 1024;
+
+/// Below this frequency bass oversampling adds display points (Hz)
+/// Around here a 4096-point FFT has only a handful of bins per octave
+const BASS_OVERSAMPLE_BELOW_HZ: f32 = 300.0;
+
+/// Display points drawn per normal point in the oversampled bass region
+const BASS_OVERSAMPLE_FACTOR: usize = 4;
+
+/// Half-width of the Lanczos kernel in bins
+const LANCZOS_LOBES: isize = 3;
+
+/// Horizontal spacing of the bass inspector's curve points (px)
+const INSPECTOR_POINT_SPACING_PX: f32 = 2.0;
+
+/// Bounds for the measured time between frame arrivals (seconds)
+/// Keeps a stalled or bursty producer from freezing or snapping the blend
+const MIN_FRAME_INTERVAL_SECONDS: f32 = 0.005;
+const MAX_FRAME_INTERVAL_SECONDS: f32 = 0.1;
+
+/// Time without a new frame after which the display counts as stale
+/// Several frames at the slowest publish rate, so normal jitter never trips it
+const STALE_FRAME_SECONDS: f32 = 0.5;
+
+/// Percentile shown by the percentile trace
+const TRACE_PERCENTILE: f32 = 95.0;
+
+/// Minimum time between percentile recomputes (selection over the whole ring isn't free)
+const PERCENTILE_RECOMPUTE_SECONDS: f32 = 0.25;
+
+/// Time between peak marker refreshes, slow enough to read the labels
+const PEAK_MARKER_UPDATE_SECONDS: f32 = 1.0;
+
+/// Vertical spacing of the stacked peak marker labels
+const PEAK_MARKER_ROW_HEIGHT: f32 = 11.0;
+
+/// What the cursor readout snaps to while a modifier is held
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum CursorSnap {
+    /// Follow the cursor freely
+    Off,
+    /// Nearest line of the active grid (Ctrl)
+    Grid,
+    /// Nearest equal-tempered note (Alt)
+    Note,
+}
+
+impl CursorSnap {
+    /// Snap mode for the held modifiers; Ctrl wins when both are held
+    pub fn from_modifiers(modifiers: keyboard::Modifiers) -> Self {
+        if modifiers.control() {
+            Self::Grid
+        } else if modifiers.alt() {
+            Self::Note
+        } else {
+            Self::Off
+        }
+    }
+}
 
 /// Spectrum display component
 pub struct SpectrumDisplay {
     /// Communication channel from audio thread
     spectrum_output: SpectrumConsumer,
-    /// Sample rate for frequency calculation
-    sample_rate: Arc<AtomicF32>,
     /// Plugin parameters for accessing amplitude range and resolution
     plugin_params: Arc<SAPluginParams>,
+    /// Latest frame published by the audio thread, before display interpolation
+    /// Carries the sample rate and floor used for frequency mapping
+    frame: SpectrumFrame,
+    /// Interpolation setting `spectrum` was last built with
+    interpolation: SpectrumInterpolation,
+    /// Upsampled spectrum of the latest frame (rebuilt only when a new frame arrives)
+    target_spectrum: SpectrumData,
+    /// What was on screen when the latest frame arrived, blended towards `target_spectrum`
+    previous_spectrum: SpectrumData,
+    /// Display-ready spectrum
+    spectrum: SpectrumData,
+    /// Arrival time of the latest frame
+    frame_arrived: Instant,
+    /// Measured time between the last two frame arrivals
+    frame_interval_seconds: f32,
+    /// Slowly decaying envelope used only for the fill polygon
+    fill_envelope: SpectrumData,
+    /// Recent display frames for UI-side analytics
+    history: SpectrumHistory,
+    /// Per-bin maximum over `history`, drawn as a ghost trace
+    max_trace: SpectrumData,
+    /// Frames sampled so the ring spans the selected percentile window
+    percentile_history: SpectrumHistory,
+    /// Per-bin percentile over `percentile_history`, drawn dashed
+    percentile_trace: SpectrumData,
+    /// Window and resolution the percentile history was collected with
+    percentile_window: PercentileWindow,
+    percentile_resolution: ResolutionLevel,
+    /// When the percentile history was last sampled and last recomputed
+    last_percentile_sample: Instant,
+    last_percentile_compute: Instant,
+    /// Interpolated peak of the latest frame
+    peak: SpectrumPeak,
+    /// Loudest peaks, loudest first, refreshed every `PEAK_MARKER_UPDATE_SECONDS`
+    held_peaks: [SpectrumPeak; MAX_PEAK_MARKERS],
+    /// When `held_peaks` was last refreshed
+    last_peak_marker_update: Instant,
+    /// Time of the last tick, for frame-rate independent fill decay
+    last_update: Instant,
+    /// What the cursor readout snaps to, from the held modifiers
+    cursor_snap: CursorSnap,
+    /// Display spectrum at the loudest peak seen since the last clear, empty if none
+    captured_spectrum: SpectrumData,
+    /// Meter peak level that triggered `captured_spectrum`
+    captured_peak_db: f32,
+    /// Consecutive failed reads of the spectrum output
+    read_failures: ReadFailureTracker,
+    /// Curve style currently drawn, following the parameter on each tick
+    curve_style: CurveStyle,
+    /// Style being faded out after a change, until the crossfade completes
+    outgoing_curve_style: Option<CurveStyle>,
+    /// When `curve_style` last changed
+    curve_style_changed: Instant,
+    /// Smoothed all-channel meter level, for dimming the spectrum at low levels
+    meter_level_db: f32,
 }
 
 impl SpectrumDisplay {
-    pub fn new(
-        spectrum_output: SpectrumConsumer,
-        sample_rate: Arc<AtomicF32>,
-        plugin_params: Arc<SAPluginParams>,
-    ) -> Self {
+    pub fn new(spectrum_output: SpectrumConsumer, plugin_params: Arc<SAPluginParams>) -> Self {
+        let curve_style = plugin_params.curve_style.value();
         Self {
             spectrum_output,
-            sample_rate,
             plugin_params,
+            frame: SpectrumFrame::silence(0, 44100.0),
+            interpolation: SpectrumInterpolation::Off,
+            target_spectrum: SpectrumData::new(),
+            previous_spectrum: SpectrumData::new(),
+            spectrum: SpectrumData::new(),
+            frame_arrived: Instant::now(),
+            frame_interval_seconds: MAX_FRAME_INTERVAL_SECONDS,
+            fill_envelope: SpectrumData::new(),
+            history: SpectrumHistory::new(HISTORY_CAPACITY),
+            max_trace: SpectrumData::new(),
+            percentile_history: SpectrumHistory::new(HISTORY_CAPACITY),
+            percentile_trace: SpectrumData::new(),
+            percentile_window: PercentileWindow::Off,
+            percentile_resolution: ResolutionLevel::Medium,
+            last_percentile_sample: Instant::now(),
+            last_percentile_compute: Instant::now(),
+            peak: SpectrumPeak {
+                frequency_hz: 0.0,
+                level_db: constants::MIN_DB,
+            },
+            held_peaks: [SpectrumPeak {
+                frequency_hz: 0.0,
+                level_db: constants::MIN_DB,
+            }; MAX_PEAK_MARKERS],
+            last_peak_marker_update: Instant::now(),
+            last_update: Instant::now(),
+            cursor_snap: CursorSnap::Off,
+            captured_spectrum: SpectrumData::new(),
+            captured_peak_db: util::MINUS_INFINITY_DB,
+            read_failures: ReadFailureTracker::new("spectrum output"),
+            curve_style,
+            outgoing_curve_style: None,
+            curve_style_changed: Instant::now(),
+            meter_level_db: util::MINUS_INFINITY_DB,
+        }
+    }
+
+    /// Set the meter level the low-level dimming follows
+    /// Called every tick with the smoothed all-channel level
+    pub fn set_meter_level(&mut self, level_db: f32) {
+        self.meter_level_db = level_db;
+    }
+
+    /// Opacity of the spectrum curve for the current meter level
+    /// Fades linearly from full at the threshold to `LOW_LEVEL_MIN_OPACITY` one fade range
+    /// below it, so a quiet input reads as low confidence rather than real content
+    fn low_level_opacity(&self) -> f32 {
+        if !self.plugin_params.low_level_dim.value() {
+            return 1.0;
+        }
+
+        let threshold_db = self.plugin_params.low_level_threshold.value();
+        let below_threshold_db = threshold_db - self.meter_level_db;
+        let fade = (below_threshold_db / LOW_LEVEL_FADE_RANGE_DB).clamp(0.0, 1.0);
+        1.0 - fade * (1.0 - LOW_LEVEL_MIN_OPACITY)
+    }
+
+    /// FFT size of the latest frame, as stamped by the producer
+    pub fn window_size(&self) -> usize {
+        self.frame.window_size
+    }
+
+    /// Why the spectrum has stopped updating, once reads keep failing
+    pub fn read_error(&self) -> Option<SpectrumError> {
+        self.read_failures.error()
+    }
+
+    /// Snapshot the display spectrum if `peak_db` is louder than the captured peak
+    /// Starts from the meter's silence level, so silence alone never captures
+    /// Called every tick with the meter's held peak while peak capture is on
+    pub fn capture_on_peak(&mut self, peak_db: f32) {
+        if peak_db > self.captured_peak_db {
+            self.captured_peak_db = peak_db;
+            self.captured_spectrum.clone_from(&self.spectrum);
+        }
+    }
+
+    /// Drop the captured spectrum so the next peak of any level is captured
+    pub fn clear_captured_spectrum(&mut self) {
+        self.captured_spectrum.clear();
+        self.captured_peak_db = util::MINUS_INFINITY_DB;
+    }
+
+    /// Copy of what the spectrum and grid are showing, for a PNG export
+    /// The export renders from this on the background thread, so it never reads the params
+    pub fn screenshot(&self, meter_levels_db: (f32, f32)) -> Screenshot {
+        let params = &self.plugin_params;
+        let min_frequency = params.low_frequency.value().to_hz();
+        let (min_db, max_db) = params.range.value().to_db_range();
+        let density = params.grid_density.value();
+        Screenshot {
+            spectrum: self.spectrum.clone(),
+            sample_rate: self.frame.sample_rate,
+            floor_db: self.frame.floor_db,
+            frequency_range: (min_frequency, constants::MAX_FREQUENCY),
+            db_range: (min_db, max_db),
+            frequency_grid: constants::grid_positions(
+                min_frequency,
+                constants::MAX_FREQUENCY,
+                params.musical_grid.value().to_hz(),
+                density,
+            ),
+            db_grid: constants::db_grid_markers(min_db, max_db, density),
+            meter_levels_db,
+        }
+    }
+
+    /// Set what the cursor readout snaps to
+    pub fn set_cursor_snap(&mut self, cursor_snap: CursorSnap) {
+        self.cursor_snap = cursor_snap;
+    }
+
+    /// Refresh display data from the audio thread
+    /// Called from the editor's tick, before the canvas is redrawn
+    pub fn update(&mut self) {
+        let now = Instant::now();
+        let elapsed_seconds = now.duration_since(self.last_update).as_secs_f32();
+        self.last_update = now;
+        self.update_curve_style(now);
+
+        // Only rebuild the display spectrum when the producer actually published
+        // (or the interpolation changed) - between publishes the last frame stands
+        let interpolation = self.plugin_params.interpolation.value();
+        let read = self.spectrum_output.read_if_newer(self.frame.frame_index);
+        self.read_failures.record(&read);
+        let new_frame = read.unwrap_or(None);
+        let frame_changed = new_frame.is_some();
+        if let Some(frame) = new_frame {
+            self.frame = frame;
+            self.frame_interval_seconds = now
+                .duration_since(self.frame_arrived)
+                .as_secs_f32()
+                .clamp(MIN_FRAME_INTERVAL_SECONDS, MAX_FRAME_INTERVAL_SECONDS);
+            self.frame_arrived = now;
+        }
+        let settings_changed =
+            interpolation != self.interpolation || self.target_spectrum.is_empty();
+        if frame_changed || settings_changed {
+            if self.frame.data.is_empty() {
+                self.frame = self.spectrum_output.read_or_silence();
+            }
+            self.interpolation = interpolation;
+            // Blend onwards from whatever is on screen so a frame arriving mid-blend
+            // doesn't snap; a settings change starts clean from the new target
+            std::mem::swap(&mut self.previous_spectrum, &mut self.spectrum);
+            self.target_spectrum = upsample_spectrum(&self.frame.data, interpolation);
+            if settings_changed {
+                self.previous_spectrum.clone_from(&self.target_spectrum);
+            }
+            self.peak = self.spectrum_output.peak();
+
+            // History only advances on real data, not on every tick
+            if frame_changed {
+                self.history.push(&self.target_spectrum);
+            }
+            if self.plugin_params.max_trace.value() {
+                self.history.max(&mut self.max_trace);
+            }
+        }
+
+        self.update_percentile_trace(now, frame_changed);
+
+        // Markers update slowly so several resonances can be read at once
+        if self.plugin_params.peak_markers.value() > 0
+            && now
+                .duration_since(self.last_peak_marker_update)
+                .as_secs_f32()
+                >= PEAK_MARKER_UPDATE_SECONDS
+        {
+            self.held_peaks = self.spectrum_output.top_peaks();
+            self.last_peak_marker_update = now;
         }
+
+        // Blend between frames by how far we are into the expected frame interval
+        let blend = if self.plugin_params.frame_blend.value() {
+            now.duration_since(self.frame_arrived).as_secs_f32() / self.frame_interval_seconds
+        } else {
+            1.0
+        };
+        blend_frames(
+            &self.previous_spectrum,
+            &self.target_spectrum,
+            blend,
+            &mut self.spectrum,
+        );
+
+        let decay_ms = self.plugin_params.fill_decay.value();
+        self.fill_envelope = apply_fill_decay(
+            &self.spectrum,
+            &self.fill_envelope,
+            decay_ms,
+            elapsed_seconds,
+        );
     }
 
-    /// Get spectrum data for display - just read final processed data from audio thread
-    fn get_display_spectrum(&self) -> SpectrumData {
-        self.spectrum_output.read_or_silence()
+    /// Whether the audio thread has stopped publishing new frames
+    /// True while paused, stopped or starved; the last frame stays on screen
+    pub fn is_stale(&self) -> bool {
+        self.frame_arrived.elapsed().as_secs_f32() > STALE_FRAME_SECONDS
+    }
+
+    /// Sample the percentile history and periodically recompute the trace
+    fn update_percentile_trace(&mut self, now: Instant, frame_changed: bool) {
+        let window = self.plugin_params.percentile_window.value();
+        let resolution = self.plugin_params.resolution.value();
+
+        // Old samples describe a different time span or bin layout
+        if window != self.percentile_window || resolution != self.percentile_resolution {
+            self.percentile_window = window;
+            self.percentile_resolution = resolution;
+            self.percentile_history.clear();
+            self.percentile_trace.clear();
+        }
+
+        let Some(window_seconds) = window.to_seconds() else {
+            return;
+        };
+
+        // Space samples out so the whole ring covers the window
+        let sample_interval = window_seconds / HISTORY_CAPACITY as f32;
+        if frame_changed
+            && now
+                .duration_since(self.last_percentile_sample)
+                .as_secs_f32()
+                >= sample_interval
+        {
+            self.percentile_history.push(&self.target_spectrum);
+            self.last_percentile_sample = now;
+        }
+
+        if now
+            .duration_since(self.last_percentile_compute)
+            .as_secs_f32()
+            >= PERCENTILE_RECOMPUTE_SECONDS
+        {
+            self.percentile_history
+                .percentile(TRACE_PERCENTILE, &mut self.percentile_trace);
+            self.last_percentile_compute = now;
+        }
     }
 
     /// Convert dB to normalized position based on current amplitude range
     fn db_to_normalized(&self, db: f32) -> f32 {
         let (min_db, max_db) = self.plugin_params.range.value().to_db_range();
-        let db_range = max_db - min_db;
-        ((db - min_db) / db_range).max(0.0).min(1.0)
+        constants::db_to_normalized(db, min_db, max_db)
+    }
+
+    /// Label gutters for the current amplitude range and axis titles, matching the grid's
+    fn gutters(&self) -> AxisGutters {
+        AxisGutters::for_params(&self.plugin_params)
     }
 }
 
@@ -51,7 +420,7 @@ impl<Message> Program<Message, Theme> for SpectrumDisplay {
         renderer: &Renderer,
         _theme: &Theme,
         bounds: Rectangle,
-        _cursor: mouse::Cursor,
+        cursor: mouse::Cursor,
     ) -> Vec<Geometry> {
         let mut frame = Frame::new(renderer, bounds.size());
 
@@ -59,26 +428,93 @@ impl<Message> Program<Message, Theme> for SpectrumDisplay {
         let background = Path::rectangle(Point::ORIGIN, bounds.size());
         frame.fill(&background, UITheme::BACKGROUND_MAIN);
 
-        // Get final processed spectrum data from audio thread
-        let spectrum_data = self.get_display_spectrum();
+        // Session min/max envelope brackets the live curve
+        if self.plugin_params.envelope.value() {
+            self.draw_envelope(&mut frame, bounds.size());
+        }
+
+        // Ghost of the recent maximum sits behind the live curve
+        if self.plugin_params.max_trace.value() {
+            self.draw_max_trace(&mut frame, bounds.size());
+        }
+
+        // Spectrum held from the loudest transient, for inspecting clicks and hits
+        if self.plugin_params.peak_capture.value() {
+            self.draw_captured_spectrum(&mut frame, bounds.size());
+        }
+
+        // Long-term percentile trace, dashed so it doesn't read as live data
+        if let Some(window_seconds) = self.plugin_params.percentile_window.value().to_seconds() {
+            self.draw_percentile_trace(&mut frame, bounds.size(), window_seconds);
+        }
+
+        // Shade the part of the axis above Nyquist, where there is no data
+        if self.display_max_frequency() < constants::MAX_FREQUENCY {
+            self.draw_nyquist_boundary(&mut frame, bounds.size());
+        }
+
+        // Draw spectrum curve using the data refreshed on the last tick
+        self.draw_spectrum(
+            &mut frame,
+            bounds.size(),
+            &self.spectrum,
+            &self.fill_envelope,
+        );
 
-        // Draw spectrum curve using processed data
-        self.draw_spectrum(&mut frame, bounds.size(), &spectrum_data);
+        // Dim the traces once frames stop arriving, so old data doesn't read as live
+        if self.plugin_params.dim_stale.value() && self.is_stale() {
+            frame.fill(&background, UITheme::STALE_SPECTRUM_DIM);
+        }
+
+        // Mark the strongest peak with its interpolated frequency and level
+        if self.plugin_params.peak_readout.value() {
+            self.draw_peak_readout(&mut frame, bounds.size());
+        }
+
+        // Label the loudest few peaks, stacked beneath the single peak readout
+        let peak_marker_count = self.plugin_params.peak_markers.value().max(0) as usize;
+        if peak_marker_count > 0 {
+            self.draw_peak_markers(&mut frame, bounds.size(), peak_marker_count);
+        }
+
+        // Draw the target level line on top of the curve
+        if self.plugin_params.reference_enabled.value() {
+            self.draw_reference_line(&mut frame, bounds.size());
+        }
+
+        // User marker lines; the shader grid can't draw them until it has dynamic buffers
+        if let Ok(frequency_markers) = self.plugin_params.frequency_markers.read() {
+            draw_frequency_markers(
+                &mut frame,
+                bounds.size(),
+                bounds.width - self.gutters().right,
+                self.plugin_params.low_frequency.value().to_hz(),
+                &frequency_markers,
+            );
+        }
+
+        // Frequency and level under the cursor, snapped while Ctrl or Alt is held
+        if let Some(position) = cursor.position_in(bounds) {
+            self.draw_cursor_readout(&mut frame, bounds.size(), position);
+        }
 
         vec![frame.into_geometry()]
     }
 }
 
 impl SpectrumDisplay {
-    /// Create smooth curves from a set of points using Catmull-Rom splines
+    /// Join a set of points in the configured curve style
     ///
-    /// Catmull-Rom splines provide better interpolation for noisy spectrum data
-    /// as they pass through all control points without the overshooting artifacts
-    /// that can occur with Bézier curves at high smoothing factors.
+    /// `Smooth` uses Catmull-Rom splines, which provide better interpolation for noisy
+    /// spectrum data as they pass through all control points without the overshooting
+    /// artifacts that can occur with Bézier curves at high smoothing factors.
+    /// `Linear` draws straight segments and `Stepped` holds each value until the next
+    /// point, for users who want no interpolation between points at all.
     fn add_smooth_curves_to_path(
         path_builder: &mut canvas::path::Builder,
         points: &[Point],
-        resolution: ResolutionLevel,
+        style: CurveStyle,
+        base_tension: f32,
         start_with_move: bool,
     ) {
         if points.len() < 2 {
@@ -89,12 +525,65 @@ impl SpectrumDisplay {
             path_builder.move_to(points[0]);
         }
 
-        let catmull_rom_segments = generate_catmull_rom_segments(points, resolution);
-        for (control1, control2, end_point) in catmull_rom_segments {
-            path_builder.bezier_curve_to(control1, control2, end_point);
+        match style {
+            CurveStyle::Smooth => {
+                let catmull_rom_segments = generate_catmull_rom_segments(points, base_tension);
+                for (control1, control2, end_point) in catmull_rom_segments {
+                    path_builder.bezier_curve_to(control1, control2, end_point);
+                }
+            }
+            CurveStyle::Linear => {
+                for &point in &points[1..] {
+                    path_builder.line_to(point);
+                }
+            }
+            CurveStyle::Stepped => {
+                for window in points.windows(2) {
+                    path_builder.line_to(Point::new(window[1].x, window[0].y));
+                    path_builder.line_to(window[1]);
+                }
+            }
         }
     }
 
+    /// How spectrum points are joined
+    fn curve_style(&self) -> CurveStyle {
+        self.curve_style
+    }
+
+    /// Follow the curve style parameter, starting a crossfade from the old style when
+    /// it changes and ending it once the crossfade time has passed
+    fn update_curve_style(&mut self, now: Instant) {
+        let curve_style = self.plugin_params.curve_style.value();
+        if curve_style != self.curve_style {
+            self.outgoing_curve_style = Some(self.curve_style);
+            self.curve_style = curve_style;
+            self.curve_style_changed = now;
+        }
+
+        if self.curve_style_crossfade_progress(now) >= 1.0 {
+            self.outgoing_curve_style = None;
+        }
+    }
+
+    /// How far the crossfade into the current curve style has got (0..1)
+    fn curve_style_crossfade_progress(&self, now: Instant) -> f32 {
+        let crossfade_seconds = self.plugin_params.curve_style_crossfade.value() / 1000.0;
+        if crossfade_seconds <= 0.0 {
+            return 1.0;
+        }
+        let elapsed = now.duration_since(self.curve_style_changed).as_secs_f32();
+        (elapsed / crossfade_seconds).min(1.0)
+    }
+
+    /// Base curve tension from the smoothness setting (or resolution when set to Auto)
+    fn curve_base_tension(&self) -> f32 {
+        curve_base_tension(
+            self.plugin_params.curve_smoothness.value(),
+            self.plugin_params.resolution.value(),
+        )
+    }
+
     /// Calculate display point with logarithmic frequency scaling and A-weighting
     fn calculate_spectrum_point_for_display(
         &self,
@@ -103,118 +592,809 @@ impl SpectrumDisplay {
         bins: &[f32],
         size: Size,
     ) -> Point {
-        let sample_rate = self.sample_rate.load(Ordering::Relaxed);
-        let frequency = calculate_log_frequency(i, num_points);
-        let db_value = interpolate_bin_value(bins, frequency, sample_rate);
+        let min_frequency = self.plugin_params.low_frequency.value().to_hz();
+        let frequency = calculate_log_frequency(i, num_points, min_frequency);
+        let db_value =
+            interpolate_bin_value(bins, frequency, self.frame.sample_rate, self.frame.floor_db);
+
+        self.to_screen(frequency, db_value, size)
+    }
+
+    /// Screen position of a spectrum value for the current axis settings and gutters
+    fn to_screen(&self, frequency: f32, db_value: f32, size: Size) -> Point {
+        spectrum_to_screen(
+            frequency,
+            db_value,
+            (
+                self.plugin_params.low_frequency.value().to_hz(),
+                constants::MAX_FREQUENCY,
+            ),
+            self.plugin_params.range.value().to_db_range(),
+            size.width - self.gutters().right,
+            size.height,
+        )
+    }
+
+    /// Draw a dashed horizontal line at the user's reference level
+    fn draw_reference_line(&self, frame: &mut Frame, size: Size) {
+        let normalized = self.db_to_normalized(self.plugin_params.reference_db.value());
+        let y = size.height * (1.0 - normalized);
+        let spectrum_width = size.width - self.gutters().right;
+
+        let path = Path::line(Point::new(0.0, y), Point::new(spectrum_width, y));
+        frame.stroke(&path, reference_line_stroke());
+    }
+
+    /// Draw a tick above the strongest peak with its frequency and level
+    ///
+    /// The level is the true (untilted) peak level, so the label is anchored to the
+    /// top edge rather than to the displayed curve.
+    fn draw_peak_readout(&self, frame: &mut Frame, size: Size) {
+        let peak = self.peak;
+        let label = format!(
+            "{}  {:.1} dB",
+            format_frequency(peak.frequency_hz),
+            peak.level_db
+        );
+        self.draw_labelled_peak(frame, size, peak, label, 1.0, UITheme::PEAK_MARKER);
+    }
 
-        // Use our instance method that respects the amplitude range
-        self.map_to_screen_coordinates(db_value, frequency, size, i, num_points)
+    /// Draw the held top-N peaks, one label row per rank below the peak readout
+    fn draw_peak_markers(&self, frame: &mut Frame, size: Size, count: usize) {
+        for (rank, &peak) in self.held_peaks.iter().take(count).enumerate() {
+            let label = format!(
+                "#{} {}  {:.1} dB",
+                rank + 1,
+                format_frequency(peak.frequency_hz),
+                peak.level_db
+            );
+            let label_y = 1.0 + (rank + 1) as f32 * PEAK_MARKER_ROW_HEIGHT;
+            self.draw_labelled_peak(frame, size, peak, label, label_y, UITheme::HELD_PEAK_MARKER);
+        }
     }
 
-    /// Maps dB value and frequency to screen coordinates with proper scaling.
-    fn map_to_screen_coordinates(
+    /// Draw a tick at the top edge above `peak` with `label` beside it at `label_y`
+    /// Peaks that are silent or outside the visible range are skipped
+    fn draw_labelled_peak(
         &self,
-        db_value: f32,
-        _frequency: f32,
+        frame: &mut Frame,
         size: Size,
-        point_index: usize,
-        total_points: usize,
-    ) -> Point {
-        // Map dB range to screen coordinates using current amplitude range
-        let normalized = self.db_to_normalized(db_value);
+        peak: SpectrumPeak,
+        label: String,
+        label_y: f32,
+        color: nih_plug_iced::Color,
+    ) {
+        let min_frequency = self.plugin_params.low_frequency.value().to_hz();
+        if peak.level_db <= constants::MIN_DB
+            || peak.frequency_hz < min_frequency
+            || peak.frequency_hz > constants::MAX_FREQUENCY
+        {
+            return;
+        }
 
-        // Use same width calculation as grid overlay for alignment
-        let spectrum_width = size.width - UITheme::SPECTRUM_MARGIN_RIGHT;
+        let spectrum_width = size.width - self.gutters().right;
+        let x = constants::freq_to_log_position(
+            peak.frequency_hz,
+            min_frequency,
+            constants::MAX_FREQUENCY,
+        ) * spectrum_width;
 
-        let x = (point_index as f32 / total_points as f32) * spectrum_width;
-        let y = size.height * (1.0 - normalized);
+        let tick = Path::line(Point::new(x, 0.0), Point::new(x, label_y + 5.0));
+        frame.stroke(&tick, Stroke::default().with_width(1.0).with_color(color));
+
+        // Flip the label to the left of the tick near the right edge
+        let align_x = if x > spectrum_width * 0.8 {
+            nih_plug_iced::alignment::Horizontal::Right
+        } else {
+            nih_plug_iced::alignment::Horizontal::Left
+        };
+        let offset = if align_x == nih_plug_iced::alignment::Horizontal::Right {
+            -3.0
+        } else {
+            3.0
+        };
 
-        Point::new(x, y)
+        frame.fill_text(Text {
+            content: label,
+            position: Point::new(x + offset, label_y),
+            color,
+            size: nih_plug_iced::Pixels(9.0),
+            font: Font::default(),
+            align_x: align_x.into(),
+            align_y: nih_plug_iced::alignment::Vertical::Top.into(),
+            line_height: nih_plug_iced::widget::text::LineHeight::default(),
+            shaping: nih_plug_iced::widget::text::Shaping::default(),
+            max_width: f32::INFINITY,
+        });
     }
 
-    fn draw_spectrum(&self, frame: &mut Frame, size: Size, spectrum_data: &SpectrumData) {
-        // Use the actual spectrum data - already sized correctly based on resolution
-        if spectrum_data.len() < 2 {
+    /// Draw a line at the cursor frequency with the frequency and level beside it
+    /// Snapped readouts are marked "@" and get a brighter line, free ones "≈"
+    fn draw_cursor_readout(&self, frame: &mut Frame, size: Size, position: Point) {
+        let min_frequency = self.plugin_params.low_frequency.value().to_hz();
+        let spectrum_width = size.width - self.gutters().right;
+        if position.x >= spectrum_width {
             return;
         }
 
+        let cursor_hz = constants::log_position_to_freq(
+            position.x / spectrum_width,
+            min_frequency,
+            constants::MAX_FREQUENCY,
+        );
+        let (frequency_hz, prefix, line_color) = match self.snap_target(cursor_hz, min_frequency) {
+            Some(snapped_hz) => (snapped_hz, "@", UITheme::CURSOR_SNAP_LINE),
+            None => (cursor_hz, "≈", UITheme::CURSOR_LINE),
+        };
+        let x =
+            constants::freq_to_log_position(frequency_hz, min_frequency, constants::MAX_FREQUENCY)
+                * spectrum_width;
+
+        let line = Path::line(Point::new(x, 0.0), Point::new(x, size.height));
+        frame.stroke(
+            &line,
+            Stroke::default().with_width(1.0).with_color(line_color),
+        );
+
+        let level_db = interpolate_bin_value(
+            &self.spectrum,
+            frequency_hz,
+            self.frame.sample_rate,
+            self.frame.floor_db,
+        );
+        let label = format!(
+            "{}{}  {:.1} dB",
+            prefix,
+            format_frequency(frequency_hz),
+            level_db
+        );
+
+        // Flip the label to the left of the line near the right edge
+        let (align_x, offset) = if x > spectrum_width * 0.8 {
+            (nih_plug_iced::alignment::Horizontal::Right, -3.0)
+        } else {
+            (nih_plug_iced::alignment::Horizontal::Left, 3.0)
+        };
+
+        frame.fill_text(Text {
+            content: label,
+            position: Point::new(x + offset, size.height - 3.0),
+            color: UITheme::CURSOR_READOUT,
+            size: nih_plug_iced::Pixels(9.0),
+            font: Font::default(),
+            align_x: align_x.into(),
+            align_y: nih_plug_iced::alignment::Vertical::Bottom.into(),
+            line_height: nih_plug_iced::widget::text::LineHeight::default(),
+            shaping: nih_plug_iced::widget::text::Shaping::default(),
+            max_width: f32::INFINITY,
+        });
+    }
+
+    /// Frequency the cursor readout snaps to, None when not snapping
+    /// Grid snapping uses the lines the grid currently draws; note snapping skips notes
+    /// off the visible axis
+    fn snap_target(&self, cursor_hz: f32, min_frequency: f32) -> Option<f32> {
+        let visible =
+            |frequency_hz: &f32| (min_frequency..=constants::MAX_FREQUENCY).contains(frequency_hz);
+        match self.cursor_snap {
+            CursorSnap::Off => None,
+            CursorSnap::Note => Some(constants::nearest_note_frequency(cursor_hz)).filter(visible),
+            CursorSnap::Grid => constants::grid_positions(
+                min_frequency,
+                constants::MAX_FREQUENCY,
+                self.plugin_params.musical_grid.value().to_hz(),
+                self.plugin_params.grid_density.value(),
+            )
+            .into_iter()
+            .map(|(frequency_hz, _)| frequency_hz)
+            .min_by(|a, b| {
+                let octaves_away = |frequency_hz: f32| (frequency_hz / cursor_hz).log2().abs();
+                octaves_away(*a).total_cmp(&octaves_away(*b))
+            }),
+        }
+    }
+
+    /// Highest frequency with real data: the axis maximum, or Nyquist if that is lower
+    fn display_max_frequency(&self) -> f32 {
+        constants::MAX_FREQUENCY.min(self.frame.sample_rate / 2.0)
+    }
+
+    /// Shade the frequencies above Nyquist and mark the boundary
+    fn draw_nyquist_boundary(&self, frame: &mut Frame, size: Size) {
+        let min_frequency = self.plugin_params.low_frequency.value().to_hz();
+        let spectrum_width = size.width - self.gutters().right;
+        let x = constants::freq_to_log_position(
+            self.display_max_frequency(),
+            min_frequency,
+            constants::MAX_FREQUENCY,
+        ) * spectrum_width;
+
+        let region = Path::rectangle(
+            Point::new(x, 0.0),
+            Size::new(spectrum_width - x, size.height),
+        );
+        frame.fill(&region, UITheme::BEYOND_NYQUIST);
+
+        let boundary = Path::line(Point::new(x, 0.0), Point::new(x, size.height));
+        frame.stroke(
+            &boundary,
+            Stroke::default()
+                .with_width(1.0)
+                .with_color(UITheme::NYQUIST_BOUNDARY),
+        );
+    }
+
+    /// Map every bin of a spectrum to its display point
+    /// Points above Nyquist are left out, so the curve ends where the data does
+    fn collect_display_points(&self, spectrum_data: &[f32], size: Size) -> Vec<Point> {
         // Use actual bin count from the spectrum data
         let num_points = spectrum_data.len();
+        let min_frequency = self.plugin_params.low_frequency.value().to_hz();
+        let max_frequency = self.display_max_frequency();
+
+        let bass_oversampling = self.plugin_params.bass_oversampling.value();
 
-        // Collect all points and shift them down by 5 pixels
+        // Collect all points and shift them down by 1 pixel
         let mut points = Vec::with_capacity(num_points);
         for i in 0..num_points {
+            let frequency = calculate_log_frequency(i, num_points, min_frequency);
+            if frequency > max_frequency {
+                break;
+            }
+
+            // Few bins span the bass, so linear interpolation kinks at every bin there;
+            // draw it with more points on a cubic through the bins instead
+            if bass_oversampling && frequency < BASS_OVERSAMPLE_BELOW_HZ {
+                for step in 0..BASS_OVERSAMPLE_FACTOR {
+                    let position = i as f32 + step as f32 / BASS_OVERSAMPLE_FACTOR as f32;
+                    let mut point =
+                        self.calculate_oversampled_point(position, num_points, spectrum_data, size);
+                    point.y += 1.0;
+                    points.push(point);
+                }
+                continue;
+            }
+
             let mut point =
                 self.calculate_spectrum_point_for_display(i, num_points, spectrum_data, size);
             // Shift all points down by 1 pixels - this pushes the floor line below the visible area
             point.y += 1.0;
             points.push(point);
         }
+        points
+    }
 
-        if points.len() < 3 {
+    /// Display point at a fractional point index, cubic-interpolated between bins
+    /// Uses the same frequency and screen mapping as `calculate_spectrum_point_for_display`,
+    /// so the oversampled region joins the rest of the curve
+    fn calculate_oversampled_point(
+        &self,
+        position: f32,
+        num_points: usize,
+        bins: &[f32],
+        size: Size,
+    ) -> Point {
+        let min_frequency = self.plugin_params.low_frequency.value().to_hz();
+        let frequency = min_frequency
+            * (constants::MAX_FREQUENCY / min_frequency).powf(position / num_points as f32);
+        let bin_position = frequency / (self.frame.sample_rate / 2.0) * (bins.len() - 1) as f32;
+        let db_value = cubic_interpolate(bins, bin_position);
+
+        self.to_screen(frequency, db_value, size)
+    }
+
+    /// Draw the long-term min and max envelope as faint lines
+    fn draw_envelope(&self, frame: &mut Frame, size: Size) {
+        let base_tension = self.curve_base_tension();
+        let stroke = Stroke::default()
+            .with_width(UITheme::GRID_LINE_WIDTH)
+            .with_color(UITheme::ENVELOPE_TRACE);
+
+        for envelope in [&self.frame.envelope_min, &self.frame.envelope_max] {
+            if envelope.len() < 3 {
+                continue;
+            }
+
+            let points = self.collect_display_points(envelope, size);
+            let mut path_builder = canvas::path::Builder::new();
+            Self::add_smooth_curves_to_path(
+                &mut path_builder,
+                &points,
+                self.curve_style(),
+                base_tension,
+                true,
+            );
+            frame.stroke(&path_builder.build(), stroke.clone());
+        }
+    }
+
+    /// Draw the recent per-bin maximum as a faint line
+    fn draw_max_trace(&self, frame: &mut Frame, size: Size) {
+        if self.max_trace.len() < 3 {
             return;
         }
 
-        // Create smooth curves using resolution-based smoothing
+        let points = self.collect_display_points(&self.max_trace, size);
         let mut path_builder = canvas::path::Builder::new();
-        let resolution = self.plugin_params.resolution.value();
-        Self::add_smooth_curves_to_path(&mut path_builder, &points, resolution, true);
+        let base_tension = self.curve_base_tension();
+        Self::add_smooth_curves_to_path(
+            &mut path_builder,
+            &points,
+            self.curve_style(),
+            base_tension,
+            true,
+        );
+
+        let stroke = Stroke::default()
+            .with_width(UITheme::GRID_LINE_WIDTH)
+            .with_color(UITheme::GHOST_TRACE);
+        frame.stroke(&path_builder.build(), stroke);
+    }
+
+    /// Draw the spectrum captured at the loudest peak with its legend entry
+    fn draw_captured_spectrum(&self, frame: &mut Frame, size: Size) {
+        if self.captured_spectrum.len() < 3 {
+            return;
+        }
+
+        let points = self.collect_display_points(&self.captured_spectrum, size);
+        let mut path_builder = canvas::path::Builder::new();
+        let base_tension = self.curve_base_tension();
+        Self::add_smooth_curves_to_path(
+            &mut path_builder,
+            &points,
+            self.curve_style(),
+            base_tension,
+            true,
+        );
+
+        let stroke = Stroke::default()
+            .with_width(UITheme::REFERENCE_LINE_WIDTH)
+            .with_color(UITheme::CAPTURED_TRACE);
+        frame.stroke(&path_builder.build(), stroke);
+
+        // Legend sits below the percentile legend
+        frame.fill_text(Text {
+            content: format!("Captured at {:.1} dB peak", self.captured_peak_db),
+            position: Point::new(8.0, 44.0),
+            color: UITheme::CAPTURED_TRACE,
+            size: nih_plug_iced::Pixels(9.0),
+            font: Font::default(),
+            align_x: nih_plug_iced::alignment::Horizontal::Left.into(),
+            align_y: nih_plug_iced::alignment::Vertical::Top.into(),
+            line_height: nih_plug_iced::widget::text::LineHeight::default(),
+            shaping: nih_plug_iced::widget::text::Shaping::default(),
+            max_width: f32::INFINITY,
+        });
+    }
+
+    /// Draw the percentile trace with its legend entry
+    fn draw_percentile_trace(&self, frame: &mut Frame, size: Size, window_seconds: f32) {
+        if self.percentile_trace.len() < 3 {
+            return;
+        }
+
+        let points = self.collect_display_points(&self.percentile_trace, size);
+        let mut path_builder = canvas::path::Builder::new();
+        let base_tension = self.curve_base_tension();
+        Self::add_smooth_curves_to_path(
+            &mut path_builder,
+            &points,
+            self.curve_style(),
+            base_tension,
+            true,
+        );
+        frame.stroke(&path_builder.build(), percentile_trace_stroke());
+
+        // Legend sits below the top-left badge area
+        frame.fill_text(Text {
+            content: format!("P{:.0} · {:.0} s", TRACE_PERCENTILE, window_seconds),
+            position: Point::new(8.0, 32.0),
+            color: UITheme::PERCENTILE_TRACE,
+            size: nih_plug_iced::Pixels(9.0),
+            font: Font::default(),
+            align_x: nih_plug_iced::alignment::Horizontal::Left.into(),
+            align_y: nih_plug_iced::alignment::Vertical::Top.into(),
+            line_height: nih_plug_iced::widget::text::LineHeight::default(),
+            shaping: nih_plug_iced::widget::text::Shaping::default(),
+            max_width: f32::INFINITY,
+        });
+    }
+
+    fn draw_spectrum(
+        &self,
+        frame: &mut Frame,
+        size: Size,
+        spectrum_data: &SpectrumData,
+        fill_data: &SpectrumData,
+    ) {
+        // Use the actual spectrum data - already sized correctly based on resolution
+        if spectrum_data.len() < 3 {
+            return;
+        }
+
+        let points = self.collect_display_points(spectrum_data, size);
+        if points.len() < 2 {
+            return;
+        }
+
+        // The fill follows its own slower envelope; fall back to the line if it isn't ready
+        let fill_points = if fill_data.len() == spectrum_data.len() {
+            self.collect_display_points(fill_data, size)
+        } else {
+            points.clone()
+        };
+
+        // Blend out of the previous curve style rather than popping to the new one
+        let plot = Size::new(size.width - self.gutters().right, size.height);
+        let opacity = self.low_level_opacity();
+        match self.outgoing_curve_style {
+            Some(outgoing_style) => {
+                let progress = self.curve_style_crossfade_progress(self.last_update);
+                self.draw_spectrum_curve(
+                    frame,
+                    plot,
+                    &points,
+                    &fill_points,
+                    outgoing_style,
+                    opacity * (1.0 - progress),
+                );
+                self.draw_spectrum_curve(
+                    frame,
+                    plot,
+                    &points,
+                    &fill_points,
+                    self.curve_style(),
+                    opacity * progress,
+                );
+            }
+            None => self.draw_spectrum_curve(
+                frame,
+                plot,
+                &points,
+                &fill_points,
+                self.curve_style(),
+                opacity,
+            ),
+        }
+
+        // Show the underlying bins so it's clear where interpolation shapes the curve
+        if self.plugin_params.bin_markers.value() {
+            self.draw_bin_markers(frame, size);
+        }
+    }
+
+    /// Draw the spectrum line and its fill in one curve style, faded by `opacity`
+    /// `plot` is the area the points were mapped into, excluding the label gutter
+    fn draw_spectrum_curve(
+        &self,
+        frame: &mut Frame,
+        plot: Size,
+        points: &[Point],
+        fill_points: &[Point],
+        style: CurveStyle,
+        opacity: f32,
+    ) {
+        // Join the points using the given curve style and configured smoothness
+        let mut path_builder = canvas::path::Builder::new();
+        let base_tension = self.curve_base_tension();
+        Self::add_smooth_curves_to_path(&mut path_builder, points, style, base_tension, true);
 
         let spectrum_path = path_builder.build();
 
         // Draw the line
+        let line_color = UITheme::SPECTRUM_LINE;
         let line_stroke = Stroke::default()
             .with_width(UITheme::GRID_LINE_WIDTH)
-            .with_color(UITheme::SPECTRUM_LINE);
+            .with_color(Color {
+                a: line_color.a * opacity,
+                ..line_color
+            });
         frame.stroke(&spectrum_path, line_stroke);
 
-        // Create fill path (closed polygon) with same smooth curves
+        // Fill only the stretches where the curve rises off the display floor, each closed
+        // down to the floor on its own, so floor-level noise leaves the grid untinted
+        let floor_y = plot.height;
         let mut fill_builder = canvas::path::Builder::new();
+        for run in fill_runs_above_floor(fill_points, floor_y - FILL_FLOOR_EPSILON_PX) {
+            let run_points = &fill_points[run];
+            fill_builder.move_to(Point::new(run_points[0].x, floor_y));
+            fill_builder.line_to(run_points[0]);
 
-        // Use same width calculation as spectrum points for X-axis alignment
-        let spectrum_width = size.width - UITheme::SPECTRUM_MARGIN_RIGHT;
+            // Add the spectrum curve using the same style and smoothness
+            Self::add_smooth_curves_to_path(
+                &mut fill_builder,
+                run_points,
+                style,
+                base_tension,
+                false,
+            );
 
-        // Start at bottom left (shifted down to hide floor line)
-        fill_builder.move_to(Point::new(0.0, size.height + 5.0));
+            let last_x = run_points[run_points.len() - 1].x;
+            fill_builder.line_to(Point::new(last_x, floor_y));
+            fill_builder.close();
+        }
 
-        // Add first point
-        fill_builder.line_to(points[0]);
+        let fill_path = fill_builder.build();
 
-        // Add smooth spectrum curve using resolution-based smoothing
-        Self::add_smooth_curves_to_path(&mut fill_builder, &points, resolution, false);
+        // Fill with semi-transparent color, clipped to the plot so curve overshoot past
+        // the floor or into the label gutter is never painted
+        let fill_color = UITheme::SPECTRUM_FILL;
+        frame.with_clip(Rectangle::new(Point::ORIGIN, plot), |frame| {
+            frame.fill(
+                &fill_path,
+                Color {
+                    a: fill_color.a * opacity,
+                    ..fill_color
+                },
+            );
+        });
+    }
 
-        // Close at bottom right (shifted down to hide floor line)
-        fill_builder.line_to(Point::new(spectrum_width, size.height + 5.0));
-        fill_builder.close();
+    /// Draw the live curve into the bass inspector, spanning only `frequency_range`
+    /// and `db_range` of a plot area `plot` in size
+    pub fn draw_inspector_curve(
+        &self,
+        frame: &mut Frame,
+        plot: Size,
+        frequency_range: (f32, f32),
+        db_range: (f32, f32),
+    ) {
+        let points = self.collect_inspector_points(&self.spectrum, plot, frequency_range, db_range);
+        if points.len() < 2 {
+            return;
+        }
 
-        let fill_path = fill_builder.build();
+        let fill_points = if self.fill_envelope.len() == self.spectrum.len() {
+            self.collect_inspector_points(&self.fill_envelope, plot, frequency_range, db_range)
+        } else {
+            points.clone()
+        };
 
-        // Fill with semi-transparent color
-        frame.fill(&fill_path, UITheme::SPECTRUM_FILL);
+        self.draw_spectrum_curve(
+            frame,
+            plot,
+            &points,
+            &fill_points,
+            self.curve_style(),
+            self.low_level_opacity(),
+        );
+    }
+
+    /// Map a spectrum onto the inspector's narrower axes
+    /// Only a handful of bins fall in the inspected range, so points are spaced by pixel
+    /// rather than by bin and cubic-interpolated between bins, as the bass oversampling does
+    fn collect_inspector_points(
+        &self,
+        spectrum_data: &[f32],
+        plot: Size,
+        (min_frequency, max_frequency): (f32, f32),
+        db_range: (f32, f32),
+    ) -> Vec<Point> {
+        let nyquist_frequency = self.frame.sample_rate / 2.0;
+        let last_frequency = max_frequency.min(nyquist_frequency);
+        if spectrum_data.len() < 3 || last_frequency <= min_frequency {
+            return Vec::new();
+        }
+
+        let num_points = (plot.width / INSPECTOR_POINT_SPACING_PX).ceil().max(1.0) as usize;
+        (0..=num_points)
+            .map(|i| {
+                let frequency = min_frequency
+                    * (last_frequency / min_frequency).powf(i as f32 / num_points as f32);
+                let bin_position = frequency / nyquist_frequency * (spectrum_data.len() - 1) as f32;
+                let mut point = spectrum_to_screen(
+                    frequency,
+                    cubic_interpolate(spectrum_data, bin_position),
+                    (min_frequency, max_frequency),
+                    db_range,
+                    plot.width,
+                    plot.height,
+                );
+                // Same 1px shift as the main curve, so the floor sits just out of view
+                point.y += 1.0;
+                point
+            })
+            .collect()
+    }
+
+    /// Draw a dot at each raw (un-interpolated) bin of the latest frame
+    fn draw_bin_markers(&self, frame: &mut Frame, size: Size) {
+        let bins = &self.frame.data;
+        if bins.len() < 2 {
+            return;
+        }
+
+        let min_frequency = self.plugin_params.low_frequency.value().to_hz();
+        let spectrum_width = size.width - self.gutters().right;
+        // Frame bins are evenly spaced from DC to Nyquist
+        let bin_spacing_hz = self.frame.sample_rate / 2.0 / (bins.len() - 1) as f32;
+
+        let dots = Path::new(|builder| {
+            for (bin, &db_value) in bins.iter().enumerate() {
+                let frequency = bin as f32 * bin_spacing_hz;
+                if frequency < min_frequency || frequency > constants::MAX_FREQUENCY {
+                    continue;
+                }
+
+                let x = constants::freq_to_log_position(
+                    frequency,
+                    min_frequency,
+                    constants::MAX_FREQUENCY,
+                ) * spectrum_width;
+                let y = size.height * (1.0 - self.db_to_normalized(db_value));
+                builder.circle(Point::new(x, y), UITheme::BIN_MARKER_RADIUS);
+            }
+        });
+        frame.fill(&dots, UITheme::BIN_MARKER);
+    }
+}
+
+/// Format a frequency for readouts ("440 Hz", "1.02 kHz", "12.5 kHz")
+pub fn format_frequency(frequency_hz: f32) -> String {
+    if frequency_hz >= 10000.0 {
+        format!("{:.1} kHz", frequency_hz / 1000.0)
+    } else if frequency_hz >= 1000.0 {
+        format!("{:.2} kHz", frequency_hz / 1000.0)
+    } else {
+        format!("{:.0} Hz", frequency_hz)
+    }
+}
+
+/// Dashed stroke shared by the spectrum and meter reference lines
+pub fn reference_line_stroke() -> Stroke<'static> {
+    Stroke {
+        line_dash: LineDash {
+            segments: &UITheme::REFERENCE_LINE_DASH,
+            offset: 0,
+        },
+        ..Stroke::default()
+            .with_width(UITheme::REFERENCE_LINE_WIDTH)
+            .with_color(UITheme::REFERENCE_LINE)
+    }
+}
+
+/// Dashed stroke for the percentile trace
+pub fn percentile_trace_stroke() -> Stroke<'static> {
+    Stroke {
+        line_dash: LineDash {
+            segments: &UITheme::PERCENTILE_TRACE_DASH,
+            offset: 0,
+        },
+        ..Stroke::default()
+            .with_width(UITheme::REFERENCE_LINE_WIDTH)
+            .with_color(UITheme::PERCENTILE_TRACE)
+    }
+}
+
+/// Apply the fill afterglow envelope
+///
+/// The fill rises instantly with the spectrum but falls back towards it with an
+/// exponential release, so it lags behind the crisp line and gives the display depth.
+/// A decay time of zero (or a size change after a resolution switch) makes the fill
+/// track the spectrum exactly.
+pub fn apply_fill_decay(
+    spectrum: &[f32],
+    previous_envelope: &[f32],
+    decay_ms: f32,
+    elapsed_seconds: f32,
+) -> Vec<f32> {
+    if decay_ms <= 0.0 || previous_envelope.len() != spectrum.len() {
+        return spectrum.to_vec();
     }
+
+    let release_factor = (-elapsed_seconds / (decay_ms / 1000.0)).exp();
+
+    spectrum
+        .iter()
+        .zip(previous_envelope.iter())
+        .map(|(&current_db, &previous_db)| {
+            if current_db >= previous_db {
+                current_db
+            } else {
+                previous_db * release_factor + current_db * (1.0 - release_factor)
+            }
+        })
+        .collect()
 }
 
 /// Calculate logarithmic frequency for a display point index
 ///
 /// Maps point indices to frequencies using logarithmic scaling for musical perception.
-/// Lower indices represent lower frequencies, following the standard 20Hz-20kHz range.
-pub fn calculate_log_frequency(point_index: usize, total_points: usize) -> f32 {
+/// Lower indices represent lower frequencies, from `min_freq` up to 20kHz.
+pub fn calculate_log_frequency(point_index: usize, total_points: usize, min_freq: f32) -> f32 {
     use crate::audio::constants;
-    let min_freq = constants::MIN_FREQUENCY;
     let max_freq = constants::MAX_FREQUENCY;
 
     let norm_pos = point_index as f32 / total_points as f32;
     min_freq * (max_freq / min_freq).powf(norm_pos)
 }
 
+/// Index ranges of the runs of `points` above `floor_y` (screen y grows downwards)
+///
+/// Each run is widened by one point on either side, so a fill built from it meets the
+/// curve where the curve comes down onto the floor rather than stopping short of it.
+pub fn fill_runs_above_floor(points: &[Point], floor_y: f32) -> Vec<Range<usize>> {
+    let mut runs = Vec::new();
+    let mut run_start = None;
+    for (index, point) in points.iter().enumerate() {
+        match (point.y < floor_y, run_start) {
+            (true, None) => run_start = Some(index),
+            (false, Some(start)) => {
+                runs.push(start.saturating_sub(1)..index + 1);
+                run_start = None;
+            }
+            _ => {}
+        }
+    }
+    if let Some(start) = run_start {
+        runs.push(start.saturating_sub(1)..points.len());
+    }
+    runs
+}
+
+/// Screen position of a spectrum value within a spectrum area of `spectrum_width` x `height`
+///
+/// Frequency uses the same log axis as the grid lines (`freq_to_log_position`) and level
+/// the same clamped axis as the dB grid (`db_to_normalized`), so a 1 kHz point always
+/// sits on the 1 kHz line and a level at the top of the range on the top line.
+/// The main display spans `min_frequency..MAX_FREQUENCY`; the bass inspector a narrower
+/// range on the same mapping.
+pub fn spectrum_to_screen(
+    frequency: f32,
+    db_value: f32,
+    (min_frequency, max_frequency): (f32, f32),
+    (min_db, max_db): (f32, f32),
+    spectrum_width: f32,
+    height: f32,
+) -> Point {
+    let x =
+        constants::freq_to_log_position(frequency, min_frequency, max_frequency) * spectrum_width;
+    let y = height * (1.0 - constants::db_to_normalized(db_value, min_db, max_db));
+    Point::new(x, y)
+}
+
+/// Linearly blend two spectra into `output`, reusing its allocation
+///
+/// `amount` is clamped to 0..1 so the result never leaves the range spanned by
+/// the two inputs. If the lengths differ (resolution change) the target is used as-is.
+pub fn blend_frames(previous: &[f32], target: &[f32], amount: f32, output: &mut SpectrumData) {
+    output.clear();
+    if previous.len() != target.len() {
+        output.extend_from_slice(target);
+        return;
+    }
+
+    let amount = amount.clamp(0.0, 1.0);
+    output.extend(
+        previous
+            .iter()
+            .zip(target)
+            .map(|(&from, &to)| from + (to - from) * amount),
+    );
+}
+
 /// Interpolate magnitude value from FFT bins at a specific frequency
 ///
 /// Uses linear interpolation between adjacent bins to provide smooth frequency response.
-/// Handles edge cases where the frequency maps outside the available bin range.
-pub fn interpolate_bin_value(bins: &[f32], frequency: f32, sample_rate: f32) -> f32 {
+/// Frequencies that map outside the available bin range (negative, above Nyquist, or
+/// not finite), empty spectra and sample rates that aren't positive and finite return
+/// `floor_db`.
+pub fn interpolate_bin_value(bins: &[f32], frequency: f32, sample_rate: f32, floor_db: f32) -> f32 {
+    if bins.is_empty() || !(sample_rate.is_finite() && sample_rate > 0.0) {
+        return floor_db;
+    }
+
     let nyquist_frequency = sample_rate / 2.0;
-    // Fix: bins.len() - 1 because indices go from 0 to len-1
-    let bin_position = (frequency / nyquist_frequency) * (bins.len() - 1) as f32;
+    // Checked before scaling by the bin count, which for a single bin would map every
+    // frequency onto it. A negative position would also saturate to bin 0 and
+    // extrapolate with a negative fraction
+    let normalized_frequency = frequency / nyquist_frequency;
+    if !(0.0..=1.0).contains(&normalized_frequency) {
+        return floor_db;
+    }
+    // bins.len() - 1 because indices go from 0 to len-1
+    let bin_position = normalized_frequency * (bins.len() - 1) as f32;
     let bin_index = bin_position.floor() as usize;
     let bin_fraction = bin_position.fract();
 
@@ -226,21 +1406,116 @@ pub fn interpolate_bin_value(bins: &[f32], frequency: f32, sample_rate: f32) ->
     } else if bin_index < bins.len() {
         bins[bin_index]
     } else {
-        -100.0 // Out of range
+        floor_db // Out of range
     };
 
     result
 }
 
+/// Upsample a magnitude spectrum in the frequency domain
+///
+/// Inserts interpolated values between the existing bins so low resolutions render
+/// a smooth curve without running a larger FFT. The first and last bins keep their
+/// positions (DC and Nyquist), so `interpolate_bin_value` maps frequencies identically
+/// before and after upsampling. The factor is chosen to bring the spectrum up to
+/// roughly `TARGET_INTERPOLATED_BINS`; spectra already that dense are returned as-is.
+pub fn upsample_spectrum(bins: &[f32], interpolation: SpectrumInterpolation) -> Vec<f32> {
+    let factor = (TARGET_INTERPOLATED_BINS / bins.len().max(1)).max(1);
+    if interpolation == SpectrumInterpolation::Off || factor == 1 || bins.len() < 2 {
+        return bins.to_vec();
+    }
+
+    let output_len = (bins.len() - 1) * factor + 1;
+    (0..output_len)
+        .map(|i| {
+            let position = i as f32 / factor as f32;
+            match interpolation {
+                SpectrumInterpolation::Off => bins[i / factor],
+                SpectrumInterpolation::Cubic => cubic_interpolate(bins, position),
+                SpectrumInterpolation::Sinc => lanczos_interpolate(bins, position),
+            }
+        })
+        .collect()
+}
+
+/// Catmull-Rom cubic interpolation at a fractional bin position (edges clamped)
+fn cubic_interpolate(bins: &[f32], position: f32) -> f32 {
+    let index = position.floor() as isize;
+    let t = position - index as f32;
+    let sample = |offset: isize| bins[(index + offset).clamp(0, bins.len() as isize - 1) as usize];
+
+    let (p0, p1, p2, p3) = (sample(-1), sample(0), sample(1), sample(2));
+    p1 + 0.5
+        * t
+        * (p2 - p0 + t * (2.0 * p0 - 5.0 * p1 + 4.0 * p2 - p3 + t * (3.0 * (p1 - p2) + p3 - p0)))
+}
+
+/// Windowed-sinc (Lanczos) interpolation at a fractional bin position
+///
+/// Approximates the band-limited interpolation that zero-padding the FFT would give.
+/// Weights are normalised so edge bins (where the kernel is truncated) keep their level.
+fn lanczos_interpolate(bins: &[f32], position: f32) -> f32 {
+    let index = position.floor() as isize;
+    let mut weighted_sum = 0.0;
+    let mut weight_total = 0.0;
+
+    for k in (index - LANCZOS_LOBES + 1)..=(index + LANCZOS_LOBES) {
+        if k < 0 || k >= bins.len() as isize {
+            continue;
+        }
+        let weight = lanczos_kernel(position - k as f32);
+        weighted_sum += bins[k as usize] * weight;
+        weight_total += weight;
+    }
+
+    if weight_total.abs() > f32::EPSILON {
+        weighted_sum / weight_total
+    } else {
+        bins[index.clamp(0, bins.len() as isize - 1) as usize]
+    }
+}
+
+/// Lanczos kernel: sinc(x) * sinc(x / a) inside the window, zero outside
+fn lanczos_kernel(x: f32) -> f32 {
+    let lobes = LANCZOS_LOBES as f32;
+    if x.abs() < f32::EPSILON {
+        1.0
+    } else if x.abs() >= lobes {
+        0.0
+    } else {
+        let pi_x = std::f32::consts::PI * x;
+        lobes * pi_x.sin() * (pi_x / lobes).sin() / (pi_x * pi_x)
+    }
+}
+
+/// Base Catmull-Rom tension for a smoothness setting
+///
+/// `Auto` keeps the curve coupled to analysis resolution (coarser data, rounder curve);
+/// the other settings pick a fixed tension regardless of resolution.
+pub fn curve_base_tension(smoothness: CurveSmoothness, resolution: ResolutionLevel) -> f32 {
+    match smoothness {
+        CurveSmoothness::Auto => match resolution {
+            ResolutionLevel::Low => 0.4,      // Large radius curves - very smooth
+            ResolutionLevel::Medium => 0.25,  // Medium radius curves
+            ResolutionLevel::High => 0.18,    // Smaller radius curves - more detailed
+            ResolutionLevel::Maximum => 0.12, // Tight radius curves - most precise
+        },
+        CurveSmoothness::Tight => 0.12,
+        CurveSmoothness::Balanced => 0.18,
+        CurveSmoothness::Smooth => 0.25,
+        CurveSmoothness::VerySmooth => 0.4,
+    }
+}
+
 /// Generate Catmull-Rom spline segments for natural curve interpolation
 ///
 /// Catmull-Rom splines pass through all control points, providing smoother
 /// interpolation for noisy data like high-frequency spectrum without overshooting.
 /// Each segment is represented as a cubic curve with computed control points.
-/// Adaptive smoothing provides resolution-specific smoothing patterns.
+/// `base_tension` sets how rounded the curve is; see [`curve_base_tension`].
 pub fn generate_catmull_rom_segments(
     points: &[Point],
-    resolution: ResolutionLevel,
+    base_tension: f32,
 ) -> Vec<(Point, Point, Point)> {
     if points.len() < 4 {
         // Fall back to simple lines for short point sequences
@@ -272,14 +1547,8 @@ pub fn generate_catmull_rom_segments(
         let p2 = points[i + 1];
         let p3 = points[i + 2];
 
-        // Calculate tension based on resolution level and frequency position
+        // Calculate tension based on the smoothness setting and frequency position
         let progress = i as f32 / points.len() as f32;
-        let base_tension = match resolution {
-            ResolutionLevel::Low => 0.4,      // Large radius curves - very smooth
-            ResolutionLevel::Medium => 0.25,  // Medium radius curves
-            ResolutionLevel::High => 0.18,    // Smaller radius curves - more detailed
-            ResolutionLevel::Maximum => 0.12, // Tight radius curves - most precise
-        };
 
         // Apply frequency-aware scaling: larger curves for low frequencies, tighter for high frequencies
         let frequency_scale = if progress < 0.3 {
@@ -308,3 +1577,247 @@ pub fn generate_catmull_rom_segments(
 
     segments
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const TEST_SAMPLE_RATE: f32 = 48000.0;
+    const TEST_FLOOR_DB: f32 = -140.0;
+
+    /// Deterministic LCG values in 0..1
+    fn random_values(count: usize, seed: u64) -> Vec<f32> {
+        let mut state = seed;
+        (0..count)
+            .map(|_| {
+                state = state
+                    .wrapping_mul(6364136223846793005)
+                    .wrapping_add(1442695040888963407);
+                (state >> 40) as f32 / (1u64 << 24) as f32
+            })
+            .collect()
+    }
+
+    /// Bin centre frequency of `bin` in a spectrum of `bin_count` bins from DC to Nyquist
+    fn bin_frequency(bin: usize, bin_count: usize) -> f32 {
+        bin as f32 / (bin_count - 1) as f32 * TEST_SAMPLE_RATE / 2.0
+    }
+
+    #[test]
+    fn interpolating_nothing_reads_the_floor() {
+        for frequency in [0.0, 1000.0, 24000.0] {
+            assert_eq!(
+                interpolate_bin_value(&[], frequency, TEST_SAMPLE_RATE, TEST_FLOOR_DB),
+                TEST_FLOOR_DB
+            );
+        }
+    }
+
+    #[test]
+    fn a_single_bin_covers_only_dc_to_nyquist() {
+        let bins = [-20.0];
+        for frequency in [0.0, 1000.0, TEST_SAMPLE_RATE / 2.0] {
+            assert_eq!(
+                interpolate_bin_value(&bins, frequency, TEST_SAMPLE_RATE, TEST_FLOOR_DB),
+                -20.0,
+                "{frequency} Hz"
+            );
+        }
+        for frequency in [-1.0, TEST_SAMPLE_RATE / 2.0 + 1.0, 1e30] {
+            assert_eq!(
+                interpolate_bin_value(&bins, frequency, TEST_SAMPLE_RATE, TEST_FLOOR_DB),
+                TEST_FLOOR_DB,
+                "{frequency} Hz"
+            );
+        }
+    }
+
+    #[test]
+    fn out_of_range_and_nan_positions_read_the_floor() {
+        let bins = [-10.0, -20.0, -30.0, -40.0, -50.0];
+        let nyquist = TEST_SAMPLE_RATE / 2.0;
+        let frequencies = [
+            -1.0,
+            -nyquist,
+            nyquist * 1.01,
+            nyquist * 2.0,
+            f32::NAN,
+            f32::INFINITY,
+            f32::NEG_INFINITY,
+        ];
+        for frequency in frequencies {
+            assert_eq!(
+                interpolate_bin_value(&bins, frequency, TEST_SAMPLE_RATE, TEST_FLOOR_DB),
+                TEST_FLOOR_DB,
+                "{frequency} Hz"
+            );
+        }
+
+        // A bad sample rate maps every frequency outside the bins
+        for sample_rate in [0.0, -48000.0, f32::NAN, f32::INFINITY] {
+            assert_eq!(
+                interpolate_bin_value(&bins, 1000.0, sample_rate, TEST_FLOOR_DB),
+                TEST_FLOOR_DB,
+                "{sample_rate} Hz sample rate"
+            );
+        }
+    }
+
+    #[test]
+    fn bin_centres_read_their_bin_and_the_edges_their_edge_bins() {
+        let bins = [-10.0, -20.0, -30.0, -40.0, -50.0];
+        for (bin, &level) in bins.iter().enumerate() {
+            let frequency = bin_frequency(bin, bins.len());
+            let value = interpolate_bin_value(&bins, frequency, TEST_SAMPLE_RATE, TEST_FLOOR_DB);
+            assert!((value - level).abs() < 1e-4, "bin {bin} read {value} dB");
+        }
+    }
+
+    #[test]
+    fn interpolated_values_stay_between_their_neighbouring_bins() {
+        for seed in 0..200 {
+            let bin_count = 2 + seed as usize % 63;
+            let bins: Vec<f32> = random_values(bin_count, seed)
+                .into_iter()
+                .map(|value| value * 140.0 - 140.0)
+                .collect();
+
+            for position in random_values(50, seed + 1000) {
+                let frequency = position * TEST_SAMPLE_RATE / 2.0;
+                let value =
+                    interpolate_bin_value(&bins, frequency, TEST_SAMPLE_RATE, TEST_FLOOR_DB);
+
+                let bin_position = position * (bin_count - 1) as f32;
+                let below = bins[bin_position.floor() as usize];
+                let above = bins[(bin_position.ceil() as usize).min(bin_count - 1)];
+                let (low, high) = (below.min(above), below.max(above));
+                assert!(
+                    value >= low - 1e-3 && value <= high + 1e-3,
+                    "{bin_count} bins, {frequency} Hz: {value} dB outside {low}..{high}"
+                );
+            }
+        }
+    }
+
+    #[test]
+    fn interpolation_never_panics_on_random_inputs() {
+        let special = [
+            0.0,
+            -1.0,
+            1e30,
+            -1e30,
+            f32::NAN,
+            f32::INFINITY,
+            f32::NEG_INFINITY,
+        ];
+        for seed in 0..100 {
+            let bins = random_values(seed as usize % 5, seed);
+            let scale = 1e5 * random_values(1, seed + 500)[0];
+            for frequency in special.iter().copied().chain([scale, -scale]) {
+                for sample_rate in special.iter().copied().chain([TEST_SAMPLE_RATE, scale]) {
+                    let value = interpolate_bin_value(&bins, frequency, sample_rate, TEST_FLOOR_DB);
+                    assert!(
+                        value.is_finite(),
+                        "{frequency} Hz at {sample_rate} Hz: {value}"
+                    );
+                }
+            }
+        }
+    }
+
+    /// Spectrum area the mapping tests draw into (px)
+    const TEST_WIDTH: f32 = 800.0;
+    const TEST_HEIGHT: f32 = 300.0;
+
+    /// The main display's default axes
+    const TEST_FREQUENCY_RANGE: (f32, f32) = (constants::MIN_FREQUENCY, constants::MAX_FREQUENCY);
+    const TEST_DB_RANGE: (f32, f32) = (-100.0, 0.0);
+
+    fn to_test_screen(frequency: f32, db_value: f32) -> Point {
+        spectrum_to_screen(
+            frequency,
+            db_value,
+            TEST_FREQUENCY_RANGE,
+            TEST_DB_RANGE,
+            TEST_WIDTH,
+            TEST_HEIGHT,
+        )
+    }
+
+    #[test]
+    fn frequency_range_ends_map_to_the_plot_edges() {
+        let (min_frequency, max_frequency) = TEST_FREQUENCY_RANGE;
+        assert!(to_test_screen(min_frequency, -50.0).x.abs() < 1e-3);
+        assert!((to_test_screen(max_frequency, -50.0).x - TEST_WIDTH).abs() < 1e-3);
+
+        // A narrower range, as the bass inspector uses, spans the same width
+        let point = spectrum_to_screen(250.0, -50.0, (20.0, 250.0), TEST_DB_RANGE, 400.0, 100.0);
+        assert!((point.x - 400.0).abs() < 1e-3, "x = {}", point.x);
+    }
+
+    #[test]
+    fn db_range_ends_map_to_the_bottom_and_top() {
+        let (min_db, max_db) = TEST_DB_RANGE;
+        assert!((to_test_screen(1000.0, min_db).y - TEST_HEIGHT).abs() < 1e-3);
+        assert!(to_test_screen(1000.0, max_db).y.abs() < 1e-3);
+
+        let midpoint = to_test_screen(1000.0, (min_db + max_db) / 2.0);
+        assert!(
+            (midpoint.y - TEST_HEIGHT / 2.0).abs() < 1e-3,
+            "y = {}",
+            midpoint.y
+        );
+    }
+
+    #[test]
+    fn levels_outside_the_db_range_are_clamped_to_the_plot() {
+        let (min_db, max_db) = TEST_DB_RANGE;
+        for db_value in [min_db - 1.0, min_db - 100.0, -140.0, f32::NEG_INFINITY] {
+            assert_eq!(
+                to_test_screen(1000.0, db_value).y,
+                TEST_HEIGHT,
+                "{db_value} dB"
+            );
+        }
+        for db_value in [max_db + 1.0, max_db + 24.0, f32::INFINITY] {
+            assert_eq!(to_test_screen(1000.0, db_value).y, 0.0, "{db_value} dB");
+        }
+    }
+
+    #[test]
+    fn frequencies_outside_the_range_run_off_the_plot_on_the_same_axis() {
+        // Not clamped: the curve carries on past the edges and the canvas clips it
+        let (min_frequency, max_frequency) = TEST_FREQUENCY_RANGE;
+        let below = to_test_screen(min_frequency / 2.0, -50.0).x;
+        let above = to_test_screen(max_frequency * 2.0, -50.0).x;
+        let octave_px = TEST_WIDTH / (max_frequency / min_frequency).log2();
+        assert!((below + octave_px).abs() < 1e-2, "x = {below}");
+        assert!((above - TEST_WIDTH - octave_px).abs() < 1e-2, "x = {above}");
+    }
+
+    #[test]
+    fn spectrum_points_land_on_the_grid_lines() {
+        let (min_frequency, max_frequency) = TEST_FREQUENCY_RANGE;
+        for &(frequency, label) in constants::visible_frequency_markers(min_frequency) {
+            let grid_x = constants::freq_to_log_position(frequency, min_frequency, max_frequency)
+                * TEST_WIDTH;
+            let point = to_test_screen(frequency, -50.0);
+            assert_eq!(point.x, grid_x, "{label} point off its grid line");
+        }
+
+        // 1 kHz is log10(50) decades above 20 Hz, out of the three from 20 Hz to 20 kHz
+        let one_khz = to_test_screen(1000.0, -50.0).x;
+        let expected = (1000.0_f32 / 20.0).log10() / 3.0 * TEST_WIDTH;
+        assert!((one_khz - expected).abs() < 1e-2, "1K at x = {one_khz}");
+
+        let (min_db, max_db) = TEST_DB_RANGE;
+        for db_value in (min_db as i32..=max_db as i32)
+            .step_by(10)
+            .map(|db| db as f32)
+        {
+            let grid_y =
+                TEST_HEIGHT * (1.0 - constants::db_to_normalized(db_value, min_db, max_db));
+            assert_eq!(to_test_screen(1000.0, db_value).y, grid_y, "{db_value} dB");
+        }
+    }
+}
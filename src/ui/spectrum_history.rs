@@ -0,0 +1,242 @@
+/// Number of display frames kept for UI-side analytics
+pub const HISTORY_CAPACITY: usize = 128;
+
+/// Ring of recent display frames for min/max/percentile traces
+///
+/// Frames are stored back to back in one flat buffer so pushing never allocates
+/// once the ring has filled. A frame with a different length (resolution or
+/// interpolation change) clears the history, since old bins no longer line up.
+pub struct SpectrumHistory {
+    /// `capacity * bin_count` values, oldest overwritten first
+    frames: Vec<f32>,
+    /// Bins per frame
+    bin_count: usize,
+    /// Maximum number of frames kept
+    capacity: usize,
+    /// Number of valid frames (up to `capacity`)
+    len: usize,
+    /// Slot the next frame is written to
+    write_index: usize,
+    /// Per-bin scratch for percentile selection
+    scratch: Vec<f32>,
+}
+
+impl SpectrumHistory {
+    pub fn new(capacity: usize) -> Self {
+        Self {
+            frames: Vec::new(),
+            bin_count: 0,
+            capacity: capacity.max(1),
+            len: 0,
+            write_index: 0,
+            scratch: Vec::with_capacity(capacity.max(1)),
+        }
+    }
+
+    /// Number of frames currently held
+    #[allow(dead_code)]
+    pub fn len(&self) -> usize {
+        self.len
+    }
+
+    #[allow(dead_code)]
+    pub fn is_empty(&self) -> bool {
+        self.len == 0
+    }
+
+    /// Drop all frames
+    pub fn clear(&mut self) {
+        self.len = 0;
+        self.write_index = 0;
+    }
+
+    /// Add a frame, overwriting the oldest once full
+    pub fn push(&mut self, frame: &[f32]) {
+        if frame.is_empty() {
+            return;
+        }
+
+        if frame.len() != self.bin_count {
+            self.bin_count = frame.len();
+            self.frames.clear();
+            self.frames.resize(self.bin_count * self.capacity, 0.0);
+            self.clear();
+        }
+
+        let start = self.write_index * self.bin_count;
+        self.frames[start..start + self.bin_count].copy_from_slice(frame);
+        self.write_index = (self.write_index + 1) % self.capacity;
+        self.len = (self.len + 1).min(self.capacity);
+    }
+
+    /// Per-bin maximum over the held frames, written into `output`
+    pub fn max(&self, output: &mut Vec<f32>) {
+        self.fold_into(output, f32::max);
+    }
+
+    /// Per-bin minimum over the held frames, written into `output`
+    #[allow(dead_code)] // Part of the history API, no min trace yet
+    pub fn min(&self, output: &mut Vec<f32>) {
+        self.fold_into(output, f32::min);
+    }
+
+    /// Per-bin percentile (0..=100, nearest rank) over the held frames, written into `output`
+    pub fn percentile(&mut self, p: f32, output: &mut Vec<f32>) {
+        output.clear();
+        if self.len == 0 {
+            return;
+        }
+
+        let rank = percentile_rank(p, self.len);
+        for bin in 0..self.bin_count {
+            self.scratch.clear();
+            self.scratch
+                .extend((0..self.len).map(|slot| self.frames[slot * self.bin_count + bin]));
+            let (_, value, _) = self.scratch.select_nth_unstable_by(rank, f32::total_cmp);
+            output.push(*value);
+        }
+    }
+
+    /// Combine all held frames bin by bin with `combine`
+    fn fold_into(&self, output: &mut Vec<f32>, combine: fn(f32, f32) -> f32) {
+        output.clear();
+        if self.len == 0 {
+            return;
+        }
+
+        output.extend_from_slice(&self.frames[..self.bin_count]);
+        for slot in 1..self.len {
+            let start = slot * self.bin_count;
+            let frame = &self.frames[start..start + self.bin_count];
+            for (acc, &value) in output.iter_mut().zip(frame) {
+                *acc = combine(*acc, value);
+            }
+        }
+    }
+}
+
+/// Index of the `p`th percentile in a sorted list of `count` values (nearest rank)
+pub fn percentile_rank(p: f32, count: usize) -> usize {
+    let fraction = (p / 100.0).clamp(0.0, 1.0);
+    ((fraction * count as f32).ceil() as usize).clamp(1, count) - 1
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// Deterministic pseudo-random levels in -120..0 dB (64-bit LCG)
+    fn test_levels(count: usize, seed: u64) -> Vec<f32> {
+        let mut state = seed;
+        (0..count)
+            .map(|_| {
+                state = state
+                    .wrapping_mul(6364136223846793005)
+                    .wrapping_add(1442695040888963407);
+                (state >> 40) as f32 / (1u64 << 24) as f32 * -120.0
+            })
+            .collect()
+    }
+
+    /// Nearest-rank percentile by sorting: the smallest value with at least `p`% of
+    /// the values at or below it
+    fn naive_percentile(values: &[f32], p: f32) -> f32 {
+        let mut sorted = values.to_vec();
+        sorted.sort_by(f32::total_cmp);
+        let count = sorted.len() as f32;
+        sorted
+            .iter()
+            .enumerate()
+            .find(|&(index, _)| (index + 1) as f32 / count >= p / 100.0)
+            .map(|(_, &value)| value)
+            .unwrap_or(sorted[sorted.len() - 1])
+    }
+
+    /// Push `frame_count` random frames of `bin_count` bins, returning them oldest first
+    fn fill_history(
+        history: &mut SpectrumHistory,
+        frame_count: usize,
+        bin_count: usize,
+    ) -> Vec<Vec<f32>> {
+        let frames: Vec<Vec<f32>> = (0..frame_count)
+            .map(|frame| test_levels(bin_count, frame as u64 + 1))
+            .collect();
+        for frame in &frames {
+            history.push(frame);
+        }
+        frames
+    }
+
+    /// Values of `bin` across the frames the history still holds
+    fn held_bin_values(frames: &[Vec<f32>], capacity: usize, bin: usize) -> Vec<f32> {
+        let first_held = frames.len().saturating_sub(capacity);
+        frames[first_held..]
+            .iter()
+            .map(|frame| frame[bin])
+            .collect()
+    }
+
+    #[test]
+    fn percentile_matches_naive_sort() {
+        // Partly filled, exactly full and wrapped several times
+        for frame_count in [1, 7, HISTORY_CAPACITY, HISTORY_CAPACITY * 3 + 5] {
+            let mut history = SpectrumHistory::new(HISTORY_CAPACITY);
+            let frames = fill_history(&mut history, frame_count, 16);
+
+            for p in [0.0, 1.0, 25.0, 50.0, 95.0, 99.9, 100.0] {
+                let mut output = Vec::new();
+                history.percentile(p, &mut output);
+                assert_eq!(output.len(), 16);
+
+                for (bin, &value) in output.iter().enumerate() {
+                    let held = held_bin_values(&frames, HISTORY_CAPACITY, bin);
+                    assert_eq!(
+                        value,
+                        naive_percentile(&held, p),
+                        "{frame_count} frames, p{p}, bin {bin}"
+                    );
+                }
+            }
+        }
+    }
+
+    #[test]
+    fn min_and_max_match_held_frames() {
+        let mut history = SpectrumHistory::new(8);
+        let frames = fill_history(&mut history, 21, 4);
+
+        let (mut min, mut max) = (Vec::new(), Vec::new());
+        history.min(&mut min);
+        history.max(&mut max);
+        for bin in 0..4 {
+            let held = held_bin_values(&frames, 8, bin);
+            assert_eq!(min[bin], held.iter().copied().fold(f32::INFINITY, f32::min));
+            assert_eq!(
+                max[bin],
+                held.iter().copied().fold(f32::NEG_INFINITY, f32::max)
+            );
+        }
+    }
+
+    #[test]
+    fn resolution_change_clears_history() {
+        let mut history = SpectrumHistory::new(8);
+        fill_history(&mut history, 5, 4);
+        history.push(&[-10.0; 6]);
+
+        let mut output = Vec::new();
+        history.percentile(50.0, &mut output);
+        assert_eq!(history.len(), 1);
+        assert_eq!(output, vec![-10.0; 6]);
+    }
+
+    #[test]
+    fn empty_history_writes_nothing() {
+        let mut history = SpectrumHistory::new(8);
+        let mut output = vec![1.0; 3];
+        history.percentile(95.0, &mut output);
+        assert!(output.is_empty());
+        history.max(&mut output);
+        assert!(output.is_empty());
+    }
+}
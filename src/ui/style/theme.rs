@@ -18,22 +18,101 @@ impl UITheme {
     pub const SPECTRUM_LINE: Color = Color::from_rgb(0.3, 1.0, 0.8); // Cyan curve
     pub const SPECTRUM_FILL: Color = Color::from_rgba(0.3, 1.0, 0.8, 0.15); // Semi-transparent fill
 
+    /// Max-hold ghost trace drawn behind the live curve
+    pub const GHOST_TRACE: Color = Color::from_rgba(0.3, 1.0, 0.8, 0.35);
+
+    /// 95th-percentile trace and its legend
+    pub const PERCENTILE_TRACE: Color = Color::from_rgba(1.0, 0.8, 0.3, 0.8); // Amber dashes
+
+    /// Spectrum captured at the loudest peak, and its legend
+    pub const CAPTURED_TRACE: Color = Color::from_rgba(1.0, 0.45, 0.45, 0.8);
+
+    /// Long-term min/max envelope bracketing the live curve
+    pub const ENVELOPE_TRACE: Color = Color::from_rgba(0.7, 0.7, 0.9, 0.3);
+
+    /// Raw FFT bin dots (debug/educational overlay)
+    pub const BIN_MARKER: Color = Color::from_rgba(1.0, 1.0, 1.0, 0.6);
+
+    /// Peak marker and readout
+    pub const PEAK_MARKER: Color = Color::from_rgb(1.0, 1.0, 1.0);
+
+    /// Cursor readout line, brighter while snapped to a gridline or note
+    pub const CURSOR_LINE: Color = Color::from_rgba(1.0, 1.0, 1.0, 0.3);
+    pub const CURSOR_SNAP_LINE: Color = Color::from_rgba(0.4, 0.9, 1.0, 0.9);
+    pub const CURSOR_READOUT: Color = Color::from_rgba(1.0, 1.0, 1.0, 0.9);
+
+    /// Held top-N peak markers
+    pub const HELD_PEAK_MARKER: Color = Color::from_rgba(1.0, 0.85, 0.5, 0.9);
+
+    /// Meter peak marker LED and peak-hold line
+    pub const METER_PEAK_MARKER: Color = Color::from_rgb(0.95, 0.95, 0.9);
+    pub const METER_PEAK_HOLD: Color = Color::from_rgba(1.0, 1.0, 1.0, 0.7);
+    pub const METER_PEAK_OVER: Color = Color::from_rgb(1.0, 0.2, 0.15); // Cap on 0 dBFS overs
+    pub const METER_ALIGNMENT_LINE: Color = Color::from_rgb(0.3, 0.8, 1.0); // Cyan 0 VU / +4 dBu
+
+    /// User frequency marker colours, cycled in the marker editor
+    pub const FREQUENCY_MARKER_AMBER: Color = Color::from_rgba(1.0, 0.75, 0.3, 0.8);
+    pub const FREQUENCY_MARKER_CYAN: Color = Color::from_rgba(0.4, 0.85, 1.0, 0.8);
+    pub const FREQUENCY_MARKER_MAGENTA: Color = Color::from_rgba(0.95, 0.45, 0.95, 0.8);
+    pub const FREQUENCY_MARKER_GREEN: Color = Color::from_rgba(0.5, 0.95, 0.5, 0.8);
+
+    /// Reference level line (target peak/RMS)
+    pub const REFERENCE_LINE: Color = Color::from_rgba(1.0, 0.45, 0.85, 0.8); // Pink dashes
+
     /// Text and label colors
     pub const TEXT_SECONDARY: Color = Color::from_rgba(0.6, 0.6, 0.6, 0.8);
     pub const TEXT_DB_MARKER: Color = Color::from_rgb(1.0, 1.0, 0.6); // Yellow for dB labels
 
+    /// Status badge colors
+    pub const BADGE_TEXT: Color = Color::from_rgb(1.0, 0.75, 0.3); // Amber for "PAUSED"
+    pub const BADGE_BACKGROUND: Color = Color::from_rgba(0.0, 0.0, 0.0, 0.6);
+    pub const CALIBRATION_PASS: Color = Color::from_rgb(0.4, 0.9, 0.4);
+    pub const CALIBRATION_FAIL: Color = Color::from_rgb(1.0, 0.35, 0.3);
+    pub const DC_OFFSET_WARNING: Color = Color::from_rgb(1.0, 0.35, 0.3);
+    pub const BAND_ALARM: Color = Color::from_rgb(1.0, 0.35, 0.3);
+    pub const READ_WARNING: Color = Color::from_rgb(1.0, 0.75, 0.3);
+
+    /// Veil over the spectrum when no new frames are arriving
+    pub const STALE_SPECTRUM_DIM: Color = Color::from_rgba(0.0, 0.0, 0.0, 0.45);
+
+    /// Region above Nyquist at low sample rates, and its boundary line
+    pub const BEYOND_NYQUIST: Color = Color::from_rgba(0.0, 0.0, 0.0, 0.35);
+    pub const NYQUIST_BOUNDARY: Color = Color::from_rgba(0.6, 0.6, 0.6, 0.5);
+
+    /// Tilt meter bar when brighter or darker than the target slope
+    pub const TILT_BRIGHTER: Color = Color::from_rgb(1.0, 0.75, 0.3);
+    pub const TILT_DARKER: Color = Color::from_rgb(0.35, 0.6, 1.0);
+
+    /// Clip ticks and latch in the peak history strip
+    pub const CLIP_INDICATOR: Color = Color::from_rgb(1.0, 0.2, 0.15);
+
     // === DIMENSIONS ===
     pub const METER_WIDTH: f32 = 40.0;
+    pub const LEVEL_HISTORY_HEIGHT: f32 = 30.0;
+    pub const PEAK_HISTORY_HEIGHT: f32 = 40.0;
+    pub const TILT_METER_HEIGHT: f32 = 16.0;
 
     /// Margins and padding
     pub const PADDING_SMALL: f32 = 5.0;
 
-    pub const SPECTRUM_MARGIN_BOTTOM: f32 = 30.0; // Space for frequency labels
-    pub const SPECTRUM_MARGIN_RIGHT: f32 = 30.0; // Space for dB labels on right side
+    /// Axis label font sizes; the label gutters are sized from these
+    pub const FREQUENCY_LABEL_SIZE: f32 = 9.0;
+    pub const DB_LABEL_SIZE: f32 = 10.0;
+    pub const AXIS_TITLE_SIZE: f32 = 9.0;
 
     /// Grid and labels
     pub const GRID_LINE_WIDTH: f32 = 0.5;
 
+    /// Reference line dash pattern (dash, gap)
+    pub const REFERENCE_LINE_DASH: [f32; 2] = [4.0, 3.0];
+    pub const REFERENCE_LINE_WIDTH: f32 = 1.0;
+
+    /// Raw FFT bin dot size
+    pub const BIN_MARKER_RADIUS: f32 = 1.5;
+
+    /// Percentile trace dash pattern (dash, gap)
+    pub const PERCENTILE_TRACE_DASH: [f32; 2] = [2.0, 2.0];
+
     // === VISUAL HELPER FUNCTIONS ===
     pub fn background_dark(_theme: &Theme) -> Style {
         Style {
@@ -42,4 +121,12 @@ impl UITheme {
             ..Style::default()
         }
     }
+
+    pub fn badge(_theme: &Theme) -> Style {
+        Style {
+            background: Some(Self::BADGE_BACKGROUND.into()),
+            border: border::rounded(2),
+            ..Style::default()
+        }
+    }
 }
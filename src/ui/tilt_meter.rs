@@ -0,0 +1,88 @@
+use crate::audio::meter::MeterBallistics;
+use crate::ui::UITheme;
+use nih_plug_iced::widget::canvas::{Frame, Geometry, Path, Program, Text};
+use nih_plug_iced::{mouse, Font, Point, Rectangle, Renderer, Size, Theme};
+
+/// Deviation from the target slope at either end of the bar (dB/octave)
+const TILT_METER_RANGE: f32 = 3.0;
+
+/// Centred bar showing how far the spectral slope is from a target slope
+/// Right of centre (warm) is brighter than the target, left (cool) is darker
+pub struct TiltMeter {
+    /// Smoothed measured slope minus target slope, None while nothing is measured
+    deviation: Option<f32>,
+}
+
+impl TiltMeter {
+    pub fn new() -> Self {
+        Self { deviation: None }
+    }
+
+    /// Move the deviation towards `measured - target` with the meter's release time
+    /// The deviation swings both ways, so one time constant keeps the bar symmetric
+    /// A missing measurement (silence) clears the bar
+    pub fn update(&mut self, measured: Option<f32>, target: f32, ballistics: MeterBallistics) {
+        self.deviation = measured.map(|slope| {
+            let deviation = slope - target;
+            match self.deviation {
+                Some(current) => current + (deviation - current) * ballistics.release_alpha,
+                None => deviation,
+            }
+        });
+    }
+}
+
+impl<Message> Program<Message, Theme> for TiltMeter {
+    type State = ();
+
+    fn draw(
+        &self,
+        _state: &Self::State,
+        renderer: &Renderer,
+        _theme: &Theme,
+        bounds: Rectangle,
+        _cursor: mouse::Cursor,
+    ) -> Vec<Geometry> {
+        let mut frame = Frame::new(renderer, bounds.size());
+        let size = bounds.size();
+
+        let background = Path::rectangle(Point::ORIGIN, size);
+        frame.fill(&background, UITheme::BACKGROUND_MAIN);
+
+        let center_x = size.width / 2.0;
+        if let Some(deviation) = self.deviation {
+            let normalized = (deviation / TILT_METER_RANGE).clamp(-1.0, 1.0);
+            let bar_width = normalized * center_x;
+            let (x, color) = if bar_width >= 0.0 {
+                (center_x, UITheme::TILT_BRIGHTER)
+            } else {
+                (center_x + bar_width, UITheme::TILT_DARKER)
+            };
+            let bar = Path::rectangle(Point::new(x, 0.0), Size::new(bar_width.abs(), size.height));
+            frame.fill(&bar, color);
+        }
+
+        let center_tick =
+            Path::rectangle(Point::new(center_x - 0.5, 0.0), Size::new(1.0, size.height));
+        frame.fill(&center_tick, UITheme::TEXT_SECONDARY);
+
+        let label = match self.deviation {
+            Some(deviation) => format!("{:+.1}", deviation),
+            None => "--".to_string(),
+        };
+        frame.fill_text(Text {
+            content: label,
+            position: Point::new(center_x, size.height / 2.0),
+            color: UITheme::BADGE_TEXT,
+            size: nih_plug_iced::Pixels(7.0),
+            font: Font::default(),
+            align_x: nih_plug_iced::alignment::Horizontal::Center.into(),
+            align_y: nih_plug_iced::alignment::Vertical::Center.into(),
+            line_height: nih_plug_iced::widget::text::LineHeight::default(),
+            shaping: nih_plug_iced::widget::text::Shaping::default(),
+            max_width: f32::INFINITY,
+        });
+
+        vec![frame.into_geometry()]
+    }
+}